@@ -0,0 +1,226 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use identus_did_prism::did::CanonicalPrismDid;
+use identus_did_prism::dlt::OperationMetadata;
+use identus_did_prism::prelude::*;
+use lru::LruCache;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+type CachedOperations = Vec<(OperationMetadata, SignedPrismOperation)>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResolutionCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize,
+    pub capacity: usize,
+}
+
+/// Bounded LRU cache of published operations keyed by canonical DID.
+///
+/// An entry is evicted whenever the indexer reports a newly indexed operation for its DID.
+/// If the invalidation channel lags or closes, the cache is cleared since freshness can no longer be guaranteed.
+#[derive(Clone)]
+pub struct ResolutionCache {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    state: Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+struct CacheState {
+    entries: LruCache<CanonicalPrismDid, CachedOperations>,
+    /// Incremented on every invalidation so that a read racing with the indexer is not cached.
+    generation: u64,
+    enabled: bool,
+}
+
+impl ResolutionCache {
+    pub fn new(capacity: NonZeroUsize, mut invalidation_rx: broadcast::Receiver<CanonicalPrismDid>) -> Self {
+        let inner = Arc::new(Inner {
+            state: Mutex::new(CacheState {
+                entries: LruCache::new(capacity),
+                generation: 0,
+                enabled: true,
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        });
+
+        let worker_inner = inner.clone();
+        tokio::spawn(async move {
+            loop {
+                match invalidation_rx.recv().await {
+                    Ok(did) => {
+                        let mut state = worker_inner.lock();
+                        state.generation += 1;
+                        state.entries.pop(&did);
+                    }
+                    Err(RecvError::Lagged(n)) => {
+                        tracing::warn!("Resolution cache missed {} invalidations, clearing all entries", n);
+                        let mut state = worker_inner.lock();
+                        state.generation += 1;
+                        state.entries.clear();
+                    }
+                    Err(RecvError::Closed) => {
+                        tracing::warn!("Resolution cache invalidation channel is closed, disabling the cache");
+                        let mut state = worker_inner.lock();
+                        state.generation += 1;
+                        state.entries.clear();
+                        state.enabled = false;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Self { inner }
+    }
+
+    pub fn get(&self, did: &CanonicalPrismDid) -> Option<CachedOperations> {
+        let mut state = self.inner.lock();
+        match state.entries.get(did) {
+            Some(operations) => {
+                self.inner.hits.fetch_add(1, Ordering::Relaxed);
+                Some(operations.clone())
+            }
+            None => {
+                self.inner.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Returns the current generation which must be captured before reading from the database.
+    pub fn generation(&self) -> u64 {
+        self.inner.lock().generation
+    }
+
+    /// Insert the operations only if no invalidation happened since `generation` was captured.
+    pub fn insert(&self, did: CanonicalPrismDid, operations: CachedOperations, generation: u64) {
+        let mut state = self.inner.lock();
+        if state.enabled && state.generation == generation {
+            state.entries.put(did, operations);
+        }
+    }
+
+    pub fn stats(&self) -> ResolutionCacheStats {
+        let state = self.inner.lock();
+        ResolutionCacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            size: state.entries.len(),
+            capacity: state.entries.cap().get(),
+        }
+    }
+}
+
+impl Inner {
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    use identus_did_prism::did::CanonicalPrismDid;
+    use tokio::sync::broadcast;
+
+    use super::ResolutionCache;
+
+    fn did(n: u8) -> CanonicalPrismDid {
+        CanonicalPrismDid::from_suffix_str(&format!("{n:02x}").repeat(32)).unwrap()
+    }
+
+    fn new_cache(channel_capacity: usize) -> (ResolutionCache, broadcast::Sender<CanonicalPrismDid>) {
+        let (tx, rx) = broadcast::channel(channel_capacity);
+        let cache = ResolutionCache::new(NonZeroUsize::new(10).unwrap(), rx);
+        (cache, tx)
+    }
+
+    /// Wait for the invalidation worker to process the pending messages.
+    async fn wait_for_generation(cache: &ResolutionCache, generation: u64) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while cache.generation() < generation {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("invalidation worker did not catch up");
+    }
+
+    #[tokio::test]
+    async fn get_counts_hits_and_misses() {
+        let (cache, _tx) = new_cache(16);
+        assert!(cache.get(&did(1)).is_none());
+        cache.insert(did(1), vec![], cache.generation());
+        assert_eq!(cache.get(&did(1)), Some(vec![]));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.size, 1);
+        assert_eq!(stats.capacity, 10);
+    }
+
+    #[tokio::test]
+    async fn invalidation_evicts_only_the_indexed_did() {
+        let (cache, tx) = new_cache(16);
+        cache.insert(did(1), vec![], cache.generation());
+        cache.insert(did(2), vec![], cache.generation());
+
+        tx.send(did(1)).unwrap();
+        wait_for_generation(&cache, 1).await;
+        assert!(cache.get(&did(1)).is_none());
+        assert!(cache.get(&did(2)).is_some());
+    }
+
+    #[tokio::test]
+    async fn insert_is_dropped_after_invalidation() {
+        let (cache, tx) = new_cache(16);
+        // the generation is captured before a read that races with the indexer
+        let generation = cache.generation();
+        tx.send(did(1)).unwrap();
+        wait_for_generation(&cache, generation + 1).await;
+
+        cache.insert(did(1), vec![], generation);
+        assert!(cache.get(&did(1)).is_none());
+        cache.insert(did(1), vec![], cache.generation());
+        assert!(cache.get(&did(1)).is_some());
+    }
+
+    #[tokio::test]
+    async fn lagged_invalidation_clears_all_entries() {
+        let (cache, tx) = new_cache(1);
+        cache.insert(did(1), vec![], cache.generation());
+        cache.insert(did(2), vec![], cache.generation());
+
+        // the worker has not run yet, so these overflow the channel
+        for _ in 0..3 {
+            tx.send(did(3)).unwrap();
+        }
+        wait_for_generation(&cache, 1).await;
+        assert_eq!(cache.stats().size, 0);
+    }
+
+    #[tokio::test]
+    async fn closed_invalidation_disables_the_cache() {
+        let (cache, tx) = new_cache(16);
+        cache.insert(did(1), vec![], cache.generation());
+
+        drop(tx);
+        wait_for_generation(&cache, 1).await;
+        assert_eq!(cache.stats().size, 0);
+        cache.insert(did(1), vec![], cache.generation());
+        assert!(cache.get(&did(1)).is_none());
+    }
+}
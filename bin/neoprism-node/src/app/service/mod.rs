@@ -1,21 +1,98 @@
+use std::str::FromStr;
+
+use cache::{ResolutionCache, ResolutionCacheStats};
 use error::{InvalidDid, ResolutionError};
-use identus_did_prism::did::{CanonicalPrismDid, DidState, PrismDid, PrismDidOps};
-use identus_did_prism::dlt::{BlockNo, SlotNo};
-use identus_did_prism::protocol::resolver::{ResolutionDebug, resolve_published, resolve_unpublished};
+use identus_apollo::crypto::EncodeVec;
+use identus_apollo::hash::Sha256Digest;
+use identus_apollo::hex::HexStr;
+use identus_did_prism::did::operation::PublicKey;
+use identus_did_prism::did::{CanonicalPrismDid, DidResolver, DidState, PrismDid, PrismDidOps, StorageState};
+use identus_did_prism::dlt::{BlockNo, OperationMetadata, SlotNo, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::protocol::error::ProcessError;
+use identus_did_prism::protocol::resolver::{
+    ResolutionDebug, StorageVersion, resolve_published, resolve_published_transition, resolve_storage_history,
+    resolve_unpublished,
+};
 use identus_did_prism::utils::paging::Paginated;
+use identus_did_prism_indexer::find_affected_did;
 use identus_did_prism_indexer::repo::OperationRepo;
-use node_storage::PostgresDb;
+use node_storage::NodeDb;
 
+pub mod cache;
 pub mod error;
 
+const SEARCH_LIMIT: u32 = 20;
+const DEFAULT_REFERENCE_LIMIT: u32 = 100;
+const MAX_REFERENCE_LIMIT: u32 = 1000;
+
+/// The indexing status of a published operation as seen by the legacy PRISM Node API.
+///
+/// The variants are ordered by precedence when the same operation is published more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OperationIndexStatus {
+    NotIndexed,
+    Rejected,
+    Applied,
+}
+
+/// A published operation with the outcome of processing it on the DID it belongs to.
+#[derive(Debug)]
+pub struct OperationDetail {
+    pub metadata: OperationMetadata,
+    pub signed_operation: SignedPrismOperation,
+    pub did: Option<CanonicalPrismDid>,
+    pub outcome: OperationOutcome,
+}
+
+#[derive(Debug)]
+pub enum OperationOutcome {
+    /// The operation is not yet indexed or does not belong to any DID.
+    NotIndexed,
+    Applied {
+        /// The DID state before the operation or `None` if the operation created the DID.
+        before: Option<DidState>,
+        after: DidState,
+    },
+    Rejected {
+        error: ProcessError,
+    },
+}
+
+/// The matches of an explorer search query.
+#[derive(Debug, Default)]
+pub struct SearchResult {
+    /// DIDs whose suffix starts with the query.
+    pub dids: Vec<CanonicalPrismDid>,
+    /// Operations whose operation hash or Cardano transaction id is the query.
+    pub operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    /// Public keys currently declared by a DID whose key material is the query.
+    pub public_keys: Vec<(CanonicalPrismDid, PublicKey)>,
+}
+
 #[derive(Clone)]
 pub struct DidService {
-    db: PostgresDb,
+    db: NodeDb,
+    cache: Option<ResolutionCache>,
 }
 
 impl DidService {
-    pub fn new(db: &PostgresDb) -> Self {
-        Self { db: db.clone() }
+    pub fn new(db: &NodeDb) -> Self {
+        Self {
+            db: db.clone(),
+            cache: None,
+        }
+    }
+
+    pub fn with_cache(self, cache: ResolutionCache) -> Self {
+        Self {
+            cache: Some(cache),
+            ..self
+        }
+    }
+
+    pub fn get_cache_stats(&self) -> Option<ResolutionCacheStats> {
+        self.cache.as_ref().map(|c| c.stats())
     }
 
     pub async fn get_indexer_stats(&self) -> anyhow::Result<Option<(SlotNo, BlockNo)>> {
@@ -37,14 +114,7 @@ impl DidService {
         let did: PrismDid = did.parse().map_err(|e| InvalidDid::ParsingFail { source: e })?;
         let canonical_did = did.clone().into_canonical();
 
-        let operations = self
-            .db
-            .get_raw_operations_by_did(&canonical_did)
-            .await
-            .map_err(|e| ResolutionError::InternalError { source: e.into() })?
-            .into_iter()
-            .map(|(_, meta, signed_operation)| (meta, signed_operation))
-            .collect::<Vec<_>>();
+        let operations = self.get_operations_by_did(&canonical_did).await?;
 
         if operations.is_empty() {
             match &did {
@@ -68,9 +138,310 @@ impl DidService {
         }
     }
 
+    async fn get_operations_by_did(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(OperationMetadata, SignedPrismOperation)>, ResolutionError> {
+        if let Some(operations) = self.cache.as_ref().and_then(|c| c.get(did)) {
+            return Ok(operations);
+        }
+
+        let generation = self.cache.as_ref().map(|c| c.generation());
+        let operations = self
+            .db
+            .get_raw_operations_by_did(did)
+            .await
+            .map_err(|e| ResolutionError::InternalError { source: e.into() })?
+            .into_iter()
+            .map(|(_, meta, signed_operation)| (meta, signed_operation))
+            .collect::<Vec<_>>();
+
+        if let (Some(cache), Some(generation)) = (&self.cache, generation) {
+            cache.insert(did.clone(), operations.clone(), generation);
+        }
+        Ok(operations)
+    }
+
+    /// Get the indexing status of the published operations with the given hash
+    /// or `None` if no such operation has been seen on the DLT yet.
+    pub async fn get_operation_status(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> anyhow::Result<Option<OperationIndexStatus>> {
+        let raw_operations = self.db.get_raw_operations_by_operation_hash(operation_hash).await?;
+        let mut result = None;
+        for (_, _, signed_operation) in raw_operations {
+            let status = self.get_signed_operation_status(&signed_operation).await?;
+            result = result.max(Some(status));
+        }
+        Ok(result)
+    }
+
+    /// Check whether the signed operation is already indexed and whether it was applied to the DID.
+    async fn get_signed_operation_status(
+        &self,
+        signed_operation: &SignedPrismOperation,
+    ) -> anyhow::Result<OperationIndexStatus> {
+        let Some(did) = find_affected_did(&self.db, signed_operation.clone()).await? else {
+            return Ok(OperationIndexStatus::NotIndexed);
+        };
+        let operations = self.get_operations_by_did(&did).await?;
+        let (_, debug) = resolve_published(operations);
+        let status = debug
+            .into_iter()
+            .find(|(_, op, _)| op == signed_operation)
+            .map(|(_, _, error)| match error {
+                None => OperationIndexStatus::Applied,
+                Some(_) => OperationIndexStatus::Rejected,
+            })
+            .unwrap_or(OperationIndexStatus::NotIndexed);
+        Ok(status)
+    }
+
+    /// Get the current state of all active VDR entries owned by the DID.
+    pub async fn get_vdr_entries(&self, did: &CanonicalPrismDid) -> anyhow::Result<Vec<StorageState>> {
+        let operations = self.get_operations_by_did(did).await?;
+        let (did_state, _) = resolve_published(operations);
+        Ok(did_state.map(|s| s.storage).unwrap_or_default())
+    }
+
+    /// Get the current state of a VDR entry identified by the hash of the operation that created it.
+    pub async fn get_vdr_entry(
+        &self,
+        init_operation_hash: &Sha256Digest,
+    ) -> anyhow::Result<Option<(CanonicalPrismDid, StorageState)>> {
+        let Some(did) = self.get_vdr_entry_owner(init_operation_hash).await? else {
+            return Ok(None);
+        };
+        let entry = self
+            .get_vdr_entries(&did)
+            .await?
+            .into_iter()
+            .find(|s| *s.init_operation_hash == *init_operation_hash);
+        Ok(entry.map(|e| (did, e)))
+    }
+
+    /// Get every version of a VDR entry from its creation to the latest operation, including the deactivation.
+    pub async fn get_vdr_entry_history(
+        &self,
+        init_operation_hash: &Sha256Digest,
+    ) -> anyhow::Result<Option<(CanonicalPrismDid, Vec<StorageVersion>)>> {
+        let Some(did) = self.get_vdr_entry_owner(init_operation_hash).await? else {
+            return Ok(None);
+        };
+        let operations = self.get_operations_by_did(&did).await?;
+        let history = resolve_storage_history(operations, init_operation_hash);
+        if history.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some((did, history)))
+    }
+
+    async fn get_vdr_entry_owner(
+        &self,
+        init_operation_hash: &Sha256Digest,
+    ) -> anyhow::Result<Option<CanonicalPrismDid>> {
+        let Some((_, _, signed_operation)) = self
+            .db
+            .get_raw_operation_vdr_by_operation_hash(init_operation_hash)
+            .await?
+        else {
+            return Ok(None);
+        };
+        find_affected_did(&self.db, signed_operation).await
+    }
+
+    /// Get the details of all published operations with the given hash, which is usually only one operation.
+    pub async fn get_operation_details(&self, operation_hash: &Sha256Digest) -> anyhow::Result<Vec<OperationDetail>> {
+        let raw_operations = self.db.get_raw_operations_by_operation_hash(operation_hash).await?;
+
+        let mut operations_with_did = Vec::with_capacity(raw_operations.len());
+        for (_, metadata, signed_operation) in raw_operations {
+            let did_operations = match find_affected_did(&self.db, signed_operation.clone()).await? {
+                Some(did) => {
+                    let operations = self.get_operations_by_did(&did).await?;
+                    Some((did, operations))
+                }
+                None => None,
+            };
+            operations_with_did.push((metadata, signed_operation, did_operations));
+        }
+
+        // DidState is not Send, so it is only computed after all awaits
+        let details = operations_with_did
+            .into_iter()
+            .map(|(metadata, signed_operation, did_operations)| match did_operations {
+                Some((did, operations)) => OperationDetail {
+                    outcome: operation_outcome(operations, &metadata),
+                    metadata,
+                    signed_operation,
+                    did: Some(did),
+                },
+                None => OperationDetail {
+                    metadata,
+                    signed_operation,
+                    did: None,
+                    outcome: OperationOutcome::NotIndexed,
+                },
+            })
+            .collect();
+        Ok(details)
+    }
+
+    /// Get a page of published operations ordered from the latest to the oldest.
+    pub async fn get_recent_operations(
+        &self,
+        page: Option<u32>,
+    ) -> anyhow::Result<Paginated<(OperationMetadata, SignedPrismOperation)>> {
+        let page = page.unwrap_or(0);
+        let operations = self.db.get_raw_operations(page, 50).await?;
+        Ok(Paginated {
+            items: operations
+                .items
+                .into_iter()
+                .map(|(_, metadata, signed_operation)| (metadata, signed_operation))
+                .collect(),
+            current_page: operations.current_page,
+            page_size: operations.page_size,
+            total_items: operations.total_items,
+        })
+    }
+
+    /// Search DIDs, operations and public keys by a hexadecimal query.
+    ///
+    /// The query can be a DID or a prefix of its suffix, an operation hash, a Cardano transaction id
+    /// or the key material of a public key (compressed for EC keys).
+    pub async fn search(&self, query: &str) -> anyhow::Result<SearchResult> {
+        let query = query.trim().to_lowercase();
+        let query = query.strip_prefix("did:prism:").unwrap_or(&query);
+        // only the suffix of a long-form DID is searchable
+        let query = query.split(':').next().unwrap_or_default();
+        if query.is_empty() || !query.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(SearchResult::default());
+        }
+
+        let dids = self.db.get_dids_by_suffix_prefix(query, SEARCH_LIMIT).await?;
+        let Ok(bytes) = HexStr::from_str(query).map(|i| i.to_bytes()) else {
+            return Ok(SearchResult {
+                dids,
+                ..Default::default()
+            });
+        };
+
+        let mut operations = vec![];
+        if let Ok(hash) = Sha256Digest::from_bytes(&bytes) {
+            operations.extend(self.db.get_raw_operations_by_operation_hash(&hash).await?);
+            operations.extend(self.db.get_raw_operations_by_tx_hash(&TxId::from(hash)).await?);
+        }
+
+        let mut key_owners = vec![];
+        for (did, _) in self.db.get_dids_by_public_key(&bytes, 0, SEARCH_LIMIT).await?.items {
+            if !key_owners.contains(&did) {
+                key_owners.push(did);
+            }
+        }
+        let mut key_candidates = Vec::with_capacity(key_owners.len());
+        for did in key_owners {
+            let did_operations = self.get_operations_by_did(&did).await?;
+            key_candidates.push((did, did_operations));
+        }
+
+        // DidState is not Send, so it is only computed after all awaits
+        let public_keys = key_candidates
+            .into_iter()
+            .filter_map(|(did, did_operations)| resolve_published(did_operations).0.map(|state| (did, state)))
+            .flat_map(|(did, state)| {
+                state
+                    .public_keys
+                    .into_iter()
+                    .filter(|pk| pk.data.encode_vec() == bytes)
+                    .map(move |pk| (did.clone(), pk))
+            })
+            .collect();
+
+        Ok(SearchResult {
+            dids,
+            operations: operations
+                .into_iter()
+                .map(|(_, metadata, signed_operation)| (metadata, signed_operation))
+                .collect(),
+            public_keys,
+        })
+    }
+
+    /// Get a page of the DIDs currently declaring the public key material (compressed for EC keys) along with the key id.
+    pub async fn get_dids_by_public_key(
+        &self,
+        key_data: &[u8],
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<Paginated<(CanonicalPrismDid, String)>> {
+        let (page, limit) = reference_page(page, limit);
+        let dids = self.db.get_dids_by_public_key(key_data, page, limit).await?;
+        Ok(dids)
+    }
+
+    /// Get a page of the DIDs currently declaring the service endpoint URL along with the service id.
+    pub async fn get_dids_by_service_endpoint(
+        &self,
+        url: &str,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<Paginated<(CanonicalPrismDid, String)>> {
+        let (page, limit) = reference_page(page, limit);
+        let dids = self.db.get_dids_by_service_endpoint(url, page, limit).await?;
+        Ok(dids)
+    }
+
     pub async fn get_all_dids(&self, page: Option<u32>) -> anyhow::Result<Paginated<CanonicalPrismDid>> {
         let page = page.unwrap_or(0);
         let dids = self.db.get_all_dids(page, 100).await?;
         Ok(dids)
     }
 }
+
+fn reference_page(page: Option<u32>, limit: Option<u32>) -> (u32, u32) {
+    let limit = limit.unwrap_or(DEFAULT_REFERENCE_LIMIT).clamp(1, MAX_REFERENCE_LIMIT);
+    (page.unwrap_or(0), limit)
+}
+
+fn operation_outcome(
+    mut operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    metadata: &OperationMetadata,
+) -> OperationOutcome {
+    operations.sort_by(|a, b| OperationMetadata::compare_time_asc(&a.0, &b.0));
+    let Some(position) = operations
+        .iter()
+        .position(|(m, _)| OperationMetadata::compare_time_asc(m, metadata).is_eq())
+    else {
+        return OperationOutcome::NotIndexed;
+    };
+    operations.truncate(position + 1);
+    let (before, after, mut debug) = resolve_published_transition(operations);
+    match (after, debug.pop()) {
+        (_, Some((_, _, Some(error)))) => OperationOutcome::Rejected { error },
+        (Some(after), _) => OperationOutcome::Applied { before, after },
+        (None, _) => OperationOutcome::NotIndexed,
+    }
+}
+
+#[async_trait::async_trait]
+impl DidResolver for DidService {
+    type Error = ResolutionError;
+
+    async fn resolve(&self, did: &PrismDid) -> Result<Option<DidState>, Self::Error> {
+        let (result, _) = self.resolve_did(&did.to_string()).await;
+        match result {
+            Ok((_, did_state)) => Ok(Some(did_state)),
+            Err(ResolutionError::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_operations(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        self.get_operations_by_did(did).await
+    }
+}
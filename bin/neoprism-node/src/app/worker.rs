@@ -1,10 +1,11 @@
+use identus_did_prism::did::CanonicalPrismDid;
 use identus_did_prism::dlt::DltCursor;
 use identus_did_prism_indexer::{DltSource, run_indexer_loop, run_sync_loop};
-use node_storage::PostgresDb;
-use tokio::sync::watch;
+use node_storage::NodeDb;
+use tokio::sync::{broadcast, watch};
 
 pub struct DltSyncWorker<Src> {
-    store: PostgresDb,
+    store: NodeDb,
     source: Src,
 }
 
@@ -12,7 +13,7 @@ impl<Src> DltSyncWorker<Src>
 where
     Src: DltSource,
 {
-    pub fn new(store: PostgresDb, source: Src) -> Self {
+    pub fn new(store: NodeDb, source: Src) -> Self {
         Self { store, source }
     }
 
@@ -26,18 +27,27 @@ where
 }
 
 pub struct DltIndexWorker {
-    store: PostgresDb,
+    store: NodeDb,
     index_interval: u64,
+    indexed_did_tx: broadcast::Sender<CanonicalPrismDid>,
 }
 
 impl DltIndexWorker {
-    pub fn new(store: PostgresDb, index_interval: u64) -> Self {
-        Self { store, index_interval }
+    pub fn new(store: NodeDb, index_interval: u64, indexed_did_tx: broadcast::Sender<CanonicalPrismDid>) -> Self {
+        Self {
+            store,
+            index_interval,
+            indexed_did_tx,
+        }
     }
 
     pub async fn run(self) -> anyhow::Result<()> {
         loop {
-            let result = run_indexer_loop(&self.store).await;
+            let result = run_indexer_loop(&self.store, |did| {
+                // no receiver simply means nothing is caching
+                let _ = self.indexed_did_tx.send(did.clone());
+            })
+            .await;
             if let Err(e) = result {
                 tracing::error!("{:?}", e);
             }
@@ -0,0 +1,120 @@
+use std::marker::PhantomData;
+
+use bytes::{Buf, BufMut};
+use protobuf::Message;
+use tonic::Status;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+
+/// A gRPC codec for messages generated by rust-protobuf,
+/// since tonic only provides a codec for prost messages.
+pub struct ProtobufCodec<E, D>(PhantomData<(E, D)>);
+
+impl<E, D> Default for ProtobufCodec<E, D> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E, D> Codec for ProtobufCodec<E, D>
+where
+    E: Message + Send + 'static,
+    D: Message + Send + 'static,
+{
+    type Encode = E;
+    type Decode = D;
+    type Encoder = ProtobufEncoder<E>;
+    type Decoder = ProtobufDecoder<D>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        ProtobufEncoder(PhantomData)
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        ProtobufDecoder(PhantomData)
+    }
+}
+
+pub struct ProtobufEncoder<T>(PhantomData<T>);
+
+impl<T: Message> Encoder for ProtobufEncoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        let bytes = item
+            .write_to_bytes()
+            .map_err(|e| Status::internal(format!("unable to encode protobuf message: {e}")))?;
+        dst.put_slice(&bytes);
+        Ok(())
+    }
+}
+
+pub struct ProtobufDecoder<T>(PhantomData<T>);
+
+impl<T: Message> Decoder for ProtobufDecoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let bytes = src.copy_to_bytes(src.remaining());
+        let item = T::parse_from_bytes(&bytes)
+            .map_err(|e| Status::invalid_argument(format!("unable to decode protobuf message: {e}")))?;
+        Ok(Some(item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::future::{Ready, ready};
+
+    use identus_did_prism::proto::node_api::DIDData;
+    use identus_did_prism::proto::prism_ssi::PublicKey;
+    use tonic::client::Grpc as GrpcClient;
+    use tonic::codegen::http;
+    use tonic::codegen::http::uri::PathAndQuery;
+    use tonic::server::{Grpc, UnaryService};
+    use tonic::{Request, Response, Status};
+
+    use super::ProtobufCodec;
+
+    struct Echo;
+
+    impl UnaryService<DIDData> for Echo {
+        type Response = DIDData;
+        type Future = Ready<Result<Response<DIDData>, Status>>;
+
+        fn call(&mut self, request: Request<DIDData>) -> Self::Future {
+            ready(Ok(Response::new(request.into_inner())))
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let transport = tower::service_fn(|req: http::Request<tonic::body::Body>| async move {
+            let mut grpc = Grpc::new(ProtobufCodec::<DIDData, DIDData>::default());
+            Ok::<_, Infallible>(grpc.unary(Echo, req).await)
+        });
+        let mut client = GrpcClient::new(transport);
+        client.ready().await.unwrap();
+
+        let did_data = DIDData {
+            id: "0123456789abcdef".to_string(),
+            public_keys: vec![PublicKey {
+                id: "master-0".to_string(),
+                ..Default::default()
+            }],
+            context: vec!["https://www.w3.org/ns/did/v1".to_string()],
+            ..Default::default()
+        };
+        let response = client
+            .unary(
+                Request::new(did_data.clone()),
+                PathAndQuery::from_static("/test.Echo/Echo"),
+                ProtobufCodec::<DIDData, DIDData>::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner(), did_data);
+    }
+}
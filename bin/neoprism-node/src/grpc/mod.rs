@@ -0,0 +1,186 @@
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, MutexGuard};
+
+use identus_apollo::hash::Sha256Digest;
+use identus_did_prism::proto::node_api::operation_output::Operation_maybe;
+use identus_did_prism::proto::node_api::{
+    GetDidDocumentRequest, GetDidDocumentResponse, GetOperationInfoRequest, GetOperationInfoResponse, OperationOutput,
+    OperationStatus, ScheduleOperationsRequest, ScheduleOperationsResponse,
+};
+use lru::LruCache;
+use server::{NodeService, NodeServiceServer};
+use tonic::{Request, Response, Status};
+
+use crate::AppState;
+use crate::app::service::OperationIndexStatus;
+use crate::app::service::error::ResolutionError;
+
+mod codec;
+mod server;
+
+/// Maximum number of operations scheduled through this node that are tracked until they are seen on chain.
+const SCHEDULED_OPERATIONS_CAPACITY: NonZeroUsize = NonZeroUsize::new(10_000).unwrap();
+
+/// Serve the gRPC API of the legacy PRISM node so existing clients can use NeoPRISM without changes.
+pub async fn serve(app_state: AppState, addr: SocketAddr) -> anyhow::Result<()> {
+    let service = NodeServiceServer::new(NodeGrpcService::new(app_state));
+    tracing::info!("gRPC server is listening on {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(service)
+        .serve(addr)
+        .await?;
+    Ok(())
+}
+
+/// The operation id is the operation hash, so the status of any published operation
+/// is derived from the indexed operations regardless of which node scheduled it.
+/// Operations scheduled through this node are also tracked in memory until they are seen on chain.
+struct NodeGrpcService {
+    state: AppState,
+    scheduled_operations: Mutex<LruCache<Sha256Digest, SubmissionStatus>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SubmissionStatus {
+    /// The operation is being submitted to the DLT sink
+    Pending,
+    /// The transaction containing the operation is submitted but not yet seen on chain
+    Submitted,
+}
+
+impl NodeGrpcService {
+    fn new(state: AppState) -> Self {
+        Self {
+            state,
+            scheduled_operations: Mutex::new(LruCache::new(SCHEDULED_OPERATIONS_CAPACITY)),
+        }
+    }
+
+    fn scheduled_operations(&self) -> MutexGuard<'_, LruCache<Sha256Digest, SubmissionStatus>> {
+        self.scheduled_operations.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[tonic::async_trait]
+impl NodeService for NodeGrpcService {
+    async fn get_did_document(
+        &self,
+        request: Request<GetDidDocumentRequest>,
+    ) -> Result<Response<GetDidDocumentResponse>, Status> {
+        let did = request.into_inner().did;
+        let (result, _) = self.state.did_service.resolve_did(&did).await;
+        match result {
+            Ok((_, did_state)) => Ok(Response::new(GetDidDocumentResponse {
+                document: Some(did_state.into()).into(),
+                ..Default::default()
+            })),
+            Err(ResolutionError::NotFound) => Ok(Response::new(GetDidDocumentResponse::default())),
+            Err(e @ ResolutionError::InvalidDid { .. }) => Err(Status::invalid_argument(e.to_string())),
+            Err(ResolutionError::InternalError { source }) => {
+                tracing::error!("{}", source);
+                Err(Status::internal("unexpected server error"))
+            }
+        }
+    }
+
+    async fn schedule_operations(
+        &self,
+        request: Request<ScheduleOperationsRequest>,
+    ) -> Result<Response<ScheduleOperationsResponse>, Status> {
+        let Some(dlt_sink) = self.state.dlt_sink.as_ref() else {
+            return Err(Status::unimplemented(
+                "operation submission is not enabled on this node",
+            ));
+        };
+
+        let mut outputs = vec![];
+        let mut operations = vec![];
+        let mut operation_hashes = vec![];
+        for signed_operation in request.into_inner().signed_operations {
+            let operation_maybe = match signed_operation.operation_hash() {
+                Some(operation_hash) => {
+                    operations.push(signed_operation);
+                    operation_hashes.push(operation_hash.clone());
+                    Operation_maybe::OperationId(operation_hash.to_vec())
+                }
+                None => Operation_maybe::Error("signed operation does not contain an operation".to_string()),
+            };
+            outputs.push(OperationOutput {
+                operation_maybe: Some(operation_maybe),
+                ..Default::default()
+            });
+        }
+
+        if !operations.is_empty() {
+            let operation_count = operations.len();
+            {
+                let mut scheduled_operations = self.scheduled_operations();
+                for operation_hash in &operation_hashes {
+                    scheduled_operations.put(operation_hash.clone(), SubmissionStatus::Pending);
+                }
+            }
+            let result = dlt_sink.publish_operations(operations).await;
+            let mut scheduled_operations = self.scheduled_operations();
+            let tx_id = match result {
+                Ok(tx_id) => tx_id,
+                Err(e) => {
+                    for operation_hash in &operation_hashes {
+                        scheduled_operations.pop(operation_hash);
+                    }
+                    tracing::error!("{}", e);
+                    return Err(Status::internal("unable to publish operations"));
+                }
+            };
+            for operation_hash in operation_hashes {
+                scheduled_operations.put(operation_hash, SubmissionStatus::Submitted);
+            }
+            tracing::info!("Scheduled {} operations in transaction {}", operation_count, tx_id);
+        }
+
+        Ok(Response::new(ScheduleOperationsResponse {
+            outputs,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_operation_info(
+        &self,
+        request: Request<GetOperationInfoRequest>,
+    ) -> Result<Response<GetOperationInfoResponse>, Status> {
+        let operation_id = request.into_inner().operation_id;
+        let operation_hash = Sha256Digest::from_bytes(&operation_id)
+            .map_err(|_| Status::invalid_argument("operation id is not a valid operation hash"))?;
+        let status = self
+            .state
+            .did_service
+            .get_operation_status(&operation_hash)
+            .await
+            .map_err(|e| {
+                tracing::error!("{:?}", e);
+                Status::internal("unable to get operation status")
+            })?;
+
+        // operations not seen on chain yet are only known if they were scheduled through this node
+        let operation_status = match status {
+            None => match self.scheduled_operations().get(&operation_hash) {
+                Some(SubmissionStatus::Pending) => OperationStatus::PENDING_SUBMISSION,
+                Some(SubmissionStatus::Submitted) => OperationStatus::AWAIT_CONFIRMATION,
+                None => OperationStatus::UNKNOWN_OPERATION,
+            },
+            Some(status) => {
+                self.scheduled_operations().pop(&operation_hash);
+                match status {
+                    OperationIndexStatus::NotIndexed => OperationStatus::AWAIT_CONFIRMATION,
+                    OperationIndexStatus::Applied => OperationStatus::CONFIRMED_AND_APPLIED,
+                    OperationIndexStatus::Rejected => OperationStatus::CONFIRMED_AND_REJECTED,
+                }
+            }
+        };
+
+        Ok(Response::new(GetOperationInfoResponse {
+            operation_status: operation_status.into(),
+            ..Default::default()
+        }))
+    }
+}
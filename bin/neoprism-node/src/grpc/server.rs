@@ -0,0 +1,397 @@
+use identus_did_prism::proto::node_api::{
+    GetDidDocumentRequest, GetDidDocumentResponse, GetOperationInfoRequest, GetOperationInfoResponse,
+    ScheduleOperationsRequest, ScheduleOperationsResponse,
+};
+use protobuf::Message;
+use tonic::codegen::*;
+use tonic::server::{Grpc, NamedService, UnaryService};
+use tonic::{Request, Response, Status};
+
+use super::codec::ProtobufCodec;
+
+pub const SERVICE_NAME: &str = "io.iohk.atala.prism.protos.NodeService";
+
+/// The subset of the legacy PRISM `NodeService` that is served by NeoPRISM.
+#[async_trait]
+pub trait NodeService: Send + Sync + 'static {
+    async fn get_did_document(
+        &self,
+        request: Request<GetDidDocumentRequest>,
+    ) -> Result<Response<GetDidDocumentResponse>, Status>;
+
+    async fn schedule_operations(
+        &self,
+        request: Request<ScheduleOperationsRequest>,
+    ) -> Result<Response<ScheduleOperationsResponse>, Status>;
+
+    async fn get_operation_info(
+        &self,
+        request: Request<GetOperationInfoRequest>,
+    ) -> Result<Response<GetOperationInfoResponse>, Status>;
+}
+
+#[derive(Debug)]
+pub struct NodeServiceServer<T> {
+    inner: Arc<T>,
+}
+
+impl<T> NodeServiceServer<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner: Arc::new(inner) }
+    }
+}
+
+impl<T> Clone for NodeServiceServer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> NamedService for NodeServiceServer<T> {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+impl<T, B> Service<http::Request<B>> for NodeServiceServer<T>
+where
+    T: NodeService,
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let inner = self.inner.clone();
+        match req.uri().path().strip_prefix(&format!("/{SERVICE_NAME}/")) {
+            Some("GetDidDocument") => unary(inner, req, |inner, req| {
+                Box::pin(async move { inner.get_did_document(req).await })
+            }),
+            Some("ScheduleOperations") => unary(inner, req, |inner, req| {
+                Box::pin(async move { inner.schedule_operations(req).await })
+            }),
+            Some("GetOperationInfo") => unary(inner, req, |inner, req| {
+                Box::pin(async move { inner.get_operation_info(req).await })
+            }),
+            _ => {
+                let path = req.uri().path().to_string();
+                Box::pin(
+                    async move { Ok(Status::unimplemented(format!("method {path} is not supported")).into_http()) },
+                )
+            }
+        }
+    }
+}
+
+type UnaryFn<T, Req, Res> = fn(Arc<T>, Request<Req>) -> BoxFuture<Response<Res>, Status>;
+
+struct UnaryMethod<T, Req, Res> {
+    inner: Arc<T>,
+    method: UnaryFn<T, Req, Res>,
+}
+
+impl<T, Req, Res> UnaryService<Req> for UnaryMethod<T, Req, Res> {
+    type Response = Res;
+    type Future = BoxFuture<Response<Res>, Status>;
+
+    fn call(&mut self, request: Request<Req>) -> Self::Future {
+        (self.method)(self.inner.clone(), request)
+    }
+}
+
+fn unary<T, Req, Res, B>(
+    inner: Arc<T>,
+    req: http::Request<B>,
+    method: UnaryFn<T, Req, Res>,
+) -> BoxFuture<http::Response<tonic::body::Body>, std::convert::Infallible>
+where
+    T: NodeService,
+    Req: Message + Send + 'static,
+    Res: Message + Send + 'static,
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    Box::pin(async move {
+        let mut grpc = Grpc::new(ProtobufCodec::<Res, Req>::default());
+        Ok(grpc.unary(UnaryMethod { inner, method }, req).await)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::DateTime;
+    use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+    use identus_apollo::hash::sha256;
+    use identus_did_prism::did::operation::builder::{
+        CreateDidOperationBuilder, UpdateDidOperationBuilder, sign_operation,
+    };
+    use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+    use identus_did_prism::did::{CanonicalPrismDid, PrismDidOps};
+    use identus_did_prism::dlt::{BlockMetadata, OperationMetadata, TxId};
+    use identus_did_prism::prelude::*;
+    use identus_did_prism::proto::node_api::operation_output::Operation_maybe;
+    use identus_did_prism::proto::node_api::{
+        GetDidDocumentRequest, GetDidDocumentResponse, GetOperationInfoRequest, GetOperationInfoResponse,
+        OperationStatus, ScheduleOperationsRequest, ScheduleOperationsResponse,
+    };
+    use identus_did_prism::protocol::resolver::resolve_published;
+    use identus_did_prism_indexer::repo::OperationRepo;
+    use identus_did_prism_indexer::run_indexer_loop;
+    use identus_did_prism_submitter::DltSink;
+    use node_storage::NodeDb;
+    use protobuf::Message;
+    use tokio::sync::Notify;
+    use tonic::client::Grpc;
+    use tonic::codegen::http::uri::PathAndQuery;
+    use tonic::{Code, Request, Status};
+
+    use super::{NodeService, NodeServiceServer, SERVICE_NAME};
+    use crate::RunMode;
+    use crate::grpc::NodeGrpcService;
+    use crate::grpc::codec::ProtobufCodec;
+    use crate::test_utils::new_app_state;
+
+    const MASTER_KEY: [u8; 32] = [1; 32];
+
+    struct NoopSink;
+
+    #[async_trait::async_trait]
+    impl DltSink for NoopSink {
+        async fn publish_operations(&self, _operations: Vec<SignedPrismOperation>) -> Result<TxId, String> {
+            Ok(TxId::from(sha256("tx")))
+        }
+    }
+
+    struct FailingSink;
+
+    #[async_trait::async_trait]
+    impl DltSink for FailingSink {
+        async fn publish_operations(&self, _operations: Vec<SignedPrismOperation>) -> Result<TxId, String> {
+            Err("wallet is unavailable".to_string())
+        }
+    }
+
+    /// A sink that only publishes operations once it is released.
+    #[derive(Default)]
+    struct BlockingSink {
+        started: Notify,
+        released: Notify,
+    }
+
+    #[async_trait::async_trait]
+    impl DltSink for BlockingSink {
+        async fn publish_operations(&self, _operations: Vec<SignedPrismOperation>) -> Result<TxId, String> {
+            self.started.notify_one();
+            self.released.notified().await;
+            Ok(TxId::from(sha256("tx")))
+        }
+    }
+
+    async fn new_service(dlt_sink: Arc<dyn DltSink>) -> (NodeDb, NodeServiceServer<NodeGrpcService>) {
+        let (db, state) = new_app_state(RunMode::Standalone, Some(dlt_sink)).await;
+        (db, NodeServiceServer::new(NodeGrpcService::new(state)))
+    }
+
+    async fn schedule_operations(
+        service: &NodeServiceServer<impl NodeService>,
+        signed_operations: Vec<SignedPrismOperation>,
+    ) -> Result<Vec<Vec<u8>>, Status> {
+        let request = ScheduleOperationsRequest {
+            signed_operations,
+            ..Default::default()
+        };
+        let response: ScheduleOperationsResponse = call(service, "ScheduleOperations", request).await?;
+        let operation_ids = response
+            .outputs
+            .into_iter()
+            .map(|output| match output.operation_maybe {
+                Some(Operation_maybe::OperationId(id)) => id,
+                _ => panic!("operation is not scheduled"),
+            })
+            .collect();
+        Ok(operation_ids)
+    }
+
+    async fn call<Req, Res>(
+        service: &NodeServiceServer<impl NodeService>,
+        method: &str,
+        request: Req,
+    ) -> Result<Res, Status>
+    where
+        Req: Message + Send + 'static,
+        Res: Message + Send + 'static,
+    {
+        let mut client = Grpc::new(service.clone());
+        client.ready().await.unwrap();
+        let path = PathAndQuery::try_from(format!("/{SERVICE_NAME}/{method}")).unwrap();
+        let response = client
+            .unary(Request::new(request), path, ProtobufCodec::<Req, Res>::default())
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    async fn get_operation_status(
+        service: &NodeServiceServer<impl NodeService>,
+        operation_id: Vec<u8>,
+    ) -> Result<OperationStatus, Status> {
+        let request = GetOperationInfoRequest {
+            operation_id,
+            ..Default::default()
+        };
+        let response: GetOperationInfoResponse = call(service, "GetOperationInfo", request).await?;
+        Ok(response.operation_status.enum_value().unwrap())
+    }
+
+    fn metadata(block_number: u64) -> OperationMetadata {
+        OperationMetadata {
+            block_metadata: BlockMetadata {
+                slot_number: (block_number * 10).into(),
+                block_number: block_number.into(),
+                block_hash: None,
+                cbt: DateTime::UNIX_EPOCH,
+                absn: 0,
+                tx_hash: None,
+            },
+            osn: 0,
+        }
+    }
+
+    fn create_did_operation() -> SignedPrismOperation {
+        let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+        let operation = CreateDidOperationBuilder::new()
+            .add_key(
+                "master-0",
+                KeyUsage::MasterKey,
+                NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+            )
+            .build();
+        sign_operation(operation, "master-0", &master_sk)
+    }
+
+    #[tokio::test]
+    async fn get_did_document() {
+        let (db, service) = new_service(Arc::new(NoopSink)).await;
+        let create_did_op = create_did_operation();
+        let did = CanonicalPrismDid::from_operation(create_did_op.operation.as_ref().unwrap()).unwrap();
+        db.insert_raw_operations(vec![(metadata(1), create_did_op)])
+            .await
+            .unwrap();
+        run_indexer_loop(&db, |_| {}).await.unwrap();
+
+        let request = GetDidDocumentRequest {
+            did: did.to_string(),
+            ..Default::default()
+        };
+        let response: GetDidDocumentResponse = call(&service, "GetDidDocument", request).await.unwrap();
+        let document = response.document.into_option().unwrap();
+        assert_eq!(document.id, did.suffix_hex().to_string());
+        assert_eq!(document.public_keys.len(), 1);
+        assert_eq!(document.public_keys[0].id, "master-0");
+
+        let request = GetDidDocumentRequest {
+            did: format!("did:prism:{}", "0".repeat(64)),
+            ..Default::default()
+        };
+        let response: GetDidDocumentResponse = call(&service, "GetDidDocument", request).await.unwrap();
+        assert!(response.document.is_none());
+
+        let request = GetDidDocumentRequest {
+            did: "did:prism:invalid".to_string(),
+            ..Default::default()
+        };
+        let result = call::<_, GetDidDocumentResponse>(&service, "GetDidDocument", request).await;
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn get_operation_info() {
+        let (db, service) = new_service(Arc::new(NoopSink)).await;
+        let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+        let create_did_op = create_did_operation();
+        let state = resolve_published(vec![(metadata(1), create_did_op.clone())]).0.unwrap();
+        // removing a key that does not exist is rejected
+        let rejected_op = sign_operation(
+            UpdateDidOperationBuilder::new(&state).remove_key("missing-0").build(),
+            "master-0",
+            &master_sk,
+        );
+        let create_did_id = create_did_op.operation_hash().unwrap().to_vec();
+        let rejected_id = rejected_op.operation_hash().unwrap().to_vec();
+
+        let status = get_operation_status(&service, create_did_id.clone()).await.unwrap();
+        assert_eq!(status, OperationStatus::UNKNOWN_OPERATION);
+
+        // the operation id returned when scheduling is used to query the status
+        let operation_ids = schedule_operations(&service, vec![create_did_op.clone(), rejected_op.clone()])
+            .await
+            .unwrap();
+        assert_eq!(operation_ids, vec![create_did_id.clone(), rejected_id.clone()]);
+
+        let status = get_operation_status(&service, create_did_id.clone()).await.unwrap();
+        assert_eq!(status, OperationStatus::AWAIT_CONFIRMATION);
+
+        db.insert_raw_operations(vec![(metadata(1), create_did_op), (metadata(2), rejected_op)])
+            .await
+            .unwrap();
+        let status = get_operation_status(&service, create_did_id.clone()).await.unwrap();
+        assert_eq!(status, OperationStatus::AWAIT_CONFIRMATION);
+
+        run_indexer_loop(&db, |_| {}).await.unwrap();
+        let status = get_operation_status(&service, create_did_id).await.unwrap();
+        assert_eq!(status, OperationStatus::CONFIRMED_AND_APPLIED);
+        let status = get_operation_status(&service, rejected_id).await.unwrap();
+        assert_eq!(status, OperationStatus::CONFIRMED_AND_REJECTED);
+
+        let result = get_operation_status(&service, vec![0; 4]).await;
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn get_operation_info_pending_submission() {
+        let sink = Arc::new(BlockingSink::default());
+        let (_, service) = new_service(sink.clone()).await;
+        let create_did_op = create_did_operation();
+        let create_did_id = create_did_op.operation_hash().unwrap().to_vec();
+
+        let scheduling = tokio::spawn({
+            let service = service.clone();
+            async move { schedule_operations(&service, vec![create_did_op]).await }
+        });
+        sink.started.notified().await;
+        let status = get_operation_status(&service, create_did_id.clone()).await.unwrap();
+        assert_eq!(status, OperationStatus::PENDING_SUBMISSION);
+
+        sink.released.notify_one();
+        scheduling.await.unwrap().unwrap();
+        let status = get_operation_status(&service, create_did_id).await.unwrap();
+        assert_eq!(status, OperationStatus::AWAIT_CONFIRMATION);
+    }
+
+    #[tokio::test]
+    async fn get_operation_info_after_failed_submission() {
+        let (_, service) = new_service(Arc::new(FailingSink)).await;
+        let create_did_op = create_did_operation();
+        let create_did_id = create_did_op.operation_hash().unwrap().to_vec();
+
+        let result = schedule_operations(&service, vec![create_did_op]).await;
+        assert_eq!(result.unwrap_err().code(), Code::Internal);
+        let status = get_operation_status(&service, create_did_id).await.unwrap();
+        assert_eq!(status, OperationStatus::UNKNOWN_OPERATION);
+    }
+
+    #[tokio::test]
+    async fn unsupported_method() {
+        let (_, service) = new_service(Arc::new(NoopSink)).await;
+        let request = GetOperationInfoRequest::default();
+        let result = call::<_, GetOperationInfoResponse>(&service, "GetLastSyncedBlockTimestamp", request).await;
+        assert_eq!(result.unwrap_err().code(), Code::Unimplemented);
+    }
+}
@@ -1,4 +1,12 @@
-use identus_did_prism::dlt::NetworkIdentifier;
+use identus_apollo::hex::HexStr;
+use identus_did_prism::did::CanonicalPrismDid;
+use identus_did_prism::did::operation::{StorageData, StorageUpdate};
+use identus_did_prism::dlt::{NetworkIdentifier, OperationMetadata, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::proto::prism::prism_operation::Operation;
+use identus_did_prism::proto::prism_ssi::public_key::Key_data;
+use identus_did_prism::proto::prism_ssi::update_didaction::Action;
+use identus_did_prism::proto::prism_ssi::{PublicKey, Service};
 use maud::{DOCTYPE, Markup, html};
 
 use crate::VERSION;
@@ -42,6 +50,7 @@ fn navbar(title: &str, network: Option<NetworkIdentifier>) -> Markup {
                     ul class="menu menu-sm dropdown-content mt-3 z-[1] p-2 shadow bg-base-200 rounded-box w-36 border" tabindex="0" {
                         li { a class="btn btn-ghost" href=(urls::Resolver::new_uri(None)) { "Resolver" } }
                         li { a class="btn btn-ghost" href=(urls::Explorer::new_uri(None)) { "Explorer" } }
+                        li { a class="btn btn-ghost" href=(urls::ExplorerOperationList::new_uri(None)) { "Operations" } }
                         li { a class="btn btn-ghost" href=(urls::Swagger::new_uri()) { "API Docs" } }
                     }
                 }
@@ -62,3 +71,364 @@ fn navbar(title: &str, network: Option<NetworkIdentifier>) -> Markup {
         }
     }
 }
+
+/// Render the block and transaction where the operation was published.
+pub fn operation_metadata(network: Option<NetworkIdentifier>, metadata: &OperationMetadata) -> Markup {
+    let block_metadata = &metadata.block_metadata;
+    html! {
+        strong { "Block time: " } (block_metadata.cbt.to_rfc3339())
+        br;
+        strong { "Slot no: " } (block_metadata.slot_number)
+        br;
+        strong { "Block no: " } (block_metadata.block_number)
+        br;
+        strong { "Block hash: " }
+        @match &block_metadata.block_hash {
+            Some(block_hash) => span class="font-mono" { (block_hash) },
+            None => "-",
+        }
+        br;
+        strong { "Block seq no: " } (block_metadata.absn)
+        br;
+        strong { "Transaction hash: " }
+        @match &block_metadata.tx_hash {
+            Some(tx_hash) => (tx_link(network, tx_hash)),
+            None => "-",
+        }
+        br;
+        strong { "Operation seq no: " } (metadata.osn)
+    }
+}
+
+/// Render the transaction hash, linked to a block explorer when the network is known.
+pub fn tx_link(network: Option<NetworkIdentifier>, tx_hash: &TxId) -> Markup {
+    let explorer_url = network.map(|network| match network {
+        NetworkIdentifier::Mainnet => format!("https://cardanoscan.io/transaction/{tx_hash}"),
+        NetworkIdentifier::Preprod => format!("https://preprod.cardanoscan.io/transaction/{tx_hash}"),
+        NetworkIdentifier::Preview => format!("https://preview.cardanoscan.io/transaction/{tx_hash}"),
+    });
+    html! {
+        @match explorer_url {
+            Some(url) => a class="link link-hover font-mono" href=(url) target="_blank" rel="noopener noreferrer" { (tx_hash) },
+            None => span class="font-mono" { (tx_hash) },
+        }
+    }
+}
+
+/// Maximum number of bytes shown when rendering binary data.
+const BYTES_PREVIEW_LEN: usize = 64;
+
+pub fn operation_type(signed_operation: &SignedPrismOperation) -> &'static str {
+    match signed_operation.operation.as_ref().and_then(|op| op.operation.as_ref()) {
+        Some(Operation::CreateDid(_)) => "CreateDid",
+        Some(Operation::UpdateDid(_)) => "UpdateDid",
+        Some(Operation::DeactivateDid(_)) => "DeactivateDid",
+        Some(Operation::ProtocolVersionUpdate(_)) => "ProtocolVersionUpdate",
+        Some(Operation::CreateStorageEntry(_)) => "CreateStorageEntry",
+        Some(Operation::UpdateStorageEntry(_)) => "UpdateStorageEntry",
+        Some(Operation::DeactivateStorageEntry(_)) => "DeactivateStorageEntry",
+        Some(_) | None => "Unknown",
+    }
+}
+
+/// Render the fields of a signed operation by the operation type.
+pub fn signed_operation(signed_operation: &SignedPrismOperation, ipfs_gateway_url: &str) -> Markup {
+    let operation = signed_operation.operation.as_ref();
+    html! {
+        div class="bg-base-300 text-sm p-3 wrap-anywhere" {
+            strong { "Type: " } (operation_type(signed_operation))
+            br;
+            strong { "Signed with: " } (signed_operation.signed_with)
+            br;
+            strong { "Signature: " } (hex(&signed_operation.signature))
+            @if let Some(operation) = operation {
+                br;
+                (operation_fields(operation, ipfs_gateway_url))
+            }
+        }
+    }
+}
+
+fn operation_fields(operation: &PrismOperation, ipfs_gateway_url: &str) -> Markup {
+    match operation.operation.as_ref() {
+        Some(Operation::CreateDid(op)) => {
+            let did = CanonicalPrismDid::from_operation(operation).ok();
+            let did_data = op.did_data.as_ref();
+            html! {
+                strong { "DID: " } (did.map(|i| i.to_string()).unwrap_or_else(|| "-".to_string()))
+                br;
+                strong { "Public keys: " }
+                ul class="list-disc list-inside" {
+                    @for pk in did_data.map(|i| i.public_keys.as_slice()).unwrap_or_default() {
+                        li { (public_key(pk)) }
+                    }
+                }
+                strong { "Services: " }
+                ul class="list-disc list-inside" {
+                    @for svc in did_data.map(|i| i.services.as_slice()).unwrap_or_default() {
+                        li { (service(svc)) }
+                    }
+                }
+                strong { "Context: " }
+                ul class="list-disc list-inside" {
+                    @for ctx in did_data.map(|i| i.context.as_slice()).unwrap_or_default() {
+                        li { (ctx) }
+                    }
+                }
+            }
+        }
+        Some(Operation::UpdateDid(op)) => html! {
+            strong { "DID suffix: " } (op.id)
+            br;
+            strong { "Previous operation hash: " } (hex(&op.previous_operation_hash))
+            br;
+            strong { "Actions: " }
+            ul class="list-disc list-inside" {
+                @for action in op.actions.iter().filter_map(|i| i.action.as_ref()) {
+                    li { (update_did_action(action)) }
+                }
+            }
+        },
+        Some(Operation::DeactivateDid(op)) => html! {
+            strong { "DID suffix: " } (op.id)
+            br;
+            strong { "Previous operation hash: " } (hex(&op.previous_operation_hash))
+        },
+        Some(Operation::ProtocolVersionUpdate(op)) => {
+            let version = op.version.as_ref();
+            let protocol_version = version
+                .and_then(|i| i.protocol_version.as_ref())
+                .map(|i| format!("{}.{}", i.major_version, i.minor_version))
+                .unwrap_or_else(|| "-".to_string());
+            html! {
+                strong { "Proposer DID suffix: " } (op.proposer_did)
+                br;
+                strong { "Version name: " } (version.map(|i| i.version_name.as_str()).unwrap_or_default())
+                br;
+                strong { "Protocol version: " } (protocol_version)
+                br;
+                strong { "Effective since block: " } (version.map(|i| i.effective_since).unwrap_or_default())
+            }
+        }
+        Some(Operation::CreateStorageEntry(op)) => html! {
+            strong { "DID suffix: " } (hex(&op.did_prism_hash))
+            br;
+            strong { "Nonce: " } (hex(&op.nonce))
+            @if let Some(data) = op.data.clone() {
+                br;
+                (storage_data(&data.into(), ipfs_gateway_url))
+            }
+        },
+        Some(Operation::UpdateStorageEntry(op)) => html! {
+            strong { "Previous operation hash: " } (hex(&op.previous_event_hash))
+            @if let Some(data) = op.data.clone() {
+                br;
+                (storage_update(&data.into(), ipfs_gateway_url))
+            }
+        },
+        Some(Operation::DeactivateStorageEntry(op)) => html! {
+            strong { "Previous operation hash: " } (hex(&op.previous_event_hash))
+        },
+        Some(_) | None => html! {},
+    }
+}
+
+fn update_did_action(action: &Action) -> Markup {
+    match action {
+        Action::AddKey(action) => html! {
+            "AddKey "
+            @if let Some(pk) = action.key.as_ref() { (public_key(pk)) }
+        },
+        Action::RemoveKey(action) => html! { "RemoveKey " span class="font-mono" { (action.keyId) } },
+        Action::AddService(action) => html! {
+            "AddService "
+            @if let Some(svc) = action.service.as_ref() { (service(svc)) }
+        },
+        Action::RemoveService(action) => html! { "RemoveService " span class="font-mono" { (action.serviceId) } },
+        Action::UpdateService(action) => html! {
+            "UpdateService "
+            span class="font-mono" { (action.serviceId) }
+            @if !action.type_.is_empty() {
+                br;
+                "type: " span class="font-mono" { (action.type_) }
+            }
+            @if !action.service_endpoints.is_empty() {
+                br;
+                "endpoint: " span class="font-mono" { (action.service_endpoints) }
+            }
+        },
+        Action::PatchContext(action) => {
+            html! { "PatchContext " span class="font-mono" { (action.context.join(", ")) } }
+        }
+        _ => html! { "Unknown action" },
+    }
+}
+
+fn public_key(pk: &PublicKey) -> Markup {
+    let usage = format!("{:?}", pk.usage.enum_value_or_default());
+    let (curve, key_data) = match pk.key_data.as_ref() {
+        Some(Key_data::CompressedEcKeyData(data)) => (data.curve.as_str(), hex(&data.data)),
+        Some(Key_data::EcKeyData(data)) => (
+            data.curve.as_str(),
+            html! { "x: " (hex(&data.x)) ", y: " (hex(&data.y)) },
+        ),
+        Some(_) | None => ("-", html! { "-" }),
+    };
+    html! {
+        span class="font-mono" { (pk.id) }
+        " (" (usage) ", " (curve) ") "
+        (key_data)
+    }
+}
+
+fn service(svc: &Service) -> Markup {
+    html! {
+        span class="font-mono" { (svc.id) }
+        br;
+        "type: " span class="font-mono" { (svc.type_) }
+        br;
+        "endpoint: " span class="font-mono" { (svc.service_endpoint) }
+    }
+}
+
+/// Render VDR entry data by its type, linking IPFS content to the given gateway.
+pub fn storage_data(data: &StorageData, ipfs_gateway_url: &str) -> Markup {
+    match data {
+        StorageData::Bytes(bytes) => html! {
+            strong { "Data type: " } "Bytes"
+            br;
+            strong { "Size: " } (bytes.len()) " bytes"
+            br;
+            strong { "Value: " } (hex(bytes))
+        },
+        StorageData::Ipfs(cid) => {
+            let gateway_url = format!("{}/{cid}", ipfs_gateway_url.trim_end_matches('/'));
+            html! {
+                strong { "Data type: " } "IPFS"
+                br;
+                strong { "CID: " }
+                // values that are not a CID are rendered as text so they cannot alter the link
+                @if is_valid_cid(cid) {
+                    a class="link link-hover font-mono" href=(gateway_url) target="_blank" rel="noopener noreferrer" { (cid) }
+                } @else {
+                    span class="font-mono" { (cid) }
+                }
+            }
+        }
+        StorageData::StatusList(status_list) => html! {
+            strong { "Data type: " } "Status list"
+            br;
+            strong { "State: " } (status_list.state)
+            br;
+            strong { "Name: " } (status_list.name)
+            br;
+            strong { "Details: " } (status_list.detail)
+            br;
+            strong { "List size: " } (status_list.list_size)
+            br;
+            strong { "Status size: " } (status_list.status_size) " bits"
+            br;
+            strong { "Statuses: " } (hex(&status_list.int_status))
+        },
+    }
+}
+
+/// Render bytes as a hexadecimal string.
+/// Values longer than [`BYTES_PREVIEW_LEN`] bytes are truncated and can be expanded to the full value.
+fn hex(bytes: &[u8]) -> Markup {
+    if bytes.len() <= BYTES_PREVIEW_LEN {
+        return html! { span class="font-mono" { (HexStr::from(bytes).to_string()) } };
+    }
+    let preview = HexStr::from(&bytes[..BYTES_PREVIEW_LEN]).to_string();
+    html! {
+        details class="inline" {
+            summary class="font-mono cursor-pointer" { (preview) "… (show all)" }
+            span class="font-mono" { (HexStr::from(bytes).to_string()) }
+        }
+    }
+}
+
+/// Check the syntax of a CID so that it can be appended to the gateway URL as a single path segment.
+///
+/// A CIDv0 is a base58btc multihash starting with `Qm` while a CIDv1 is a multibase string,
+/// of which only the encodings without reserved URL characters are accepted.
+fn is_valid_cid(cid: &str) -> bool {
+    const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    let is_cid_v0 = cid.len() == 46 && cid.starts_with("Qm") && cid.chars().all(|c| BASE58_ALPHABET.contains(c));
+    let is_cid_v1 = match cid.split_at_checked(1) {
+        Some((prefix, data)) => {
+            matches!(prefix, "b" | "B" | "c" | "C" | "f" | "F" | "k" | "K" | "z" | "u")
+                && !data.is_empty()
+                && data.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }
+        None => false,
+    };
+    is_cid_v0 || is_cid_v1
+}
+
+/// Render the data of a VDR entry update, where a status list update only contains the change.
+fn storage_update(update: &StorageUpdate, ipfs_gateway_url: &str) -> Markup {
+    let unchanged = || "unchanged".to_string();
+    match update {
+        StorageUpdate::Bytes(bytes) => storage_data(&StorageData::Bytes(bytes.clone()), ipfs_gateway_url),
+        StorageUpdate::Ipfs(cid) => storage_data(&StorageData::Ipfs(cid.clone()), ipfs_gateway_url),
+        StorageUpdate::StatusList(status_list) => html! {
+            strong { "Data type: " } "Status list update"
+            br;
+            strong { "State: " } (status_list.state.map(|i| i.to_string()).unwrap_or_else(unchanged))
+            br;
+            strong { "Name: " } (status_list.name.clone().unwrap_or_else(unchanged))
+            br;
+            strong { "Details: " } (status_list.detail.clone().unwrap_or_else(unchanged))
+            br;
+            strong { "List size: " } (status_list.list_size.map(|i| i.to_string()).unwrap_or_else(unchanged))
+            br;
+            strong { "Flipped status bits: " } (hex(&status_list.int_status))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use identus_did_prism::did::operation::StorageData;
+
+    use super::{is_valid_cid, storage_data};
+
+    #[test]
+    fn valid_cid() {
+        for cid in [
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            "zdj7WWeQ43G6JJvLWQWZpyHuAMq6uYWRjkBXFad11vE2LHhQ7",
+        ] {
+            assert!(is_valid_cid(cid), "{cid}");
+        }
+    }
+
+    #[test]
+    fn invalid_cid() {
+        for cid in [
+            "",
+            "b",
+            "Qm0wAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/../../evil",
+            "bafy?x=1",
+            "javascript:alert(1)",
+            "mAXASIJlWTlXMIrf8MGRZLOHNdo5C3rxrCfqBTbsz1D1dxGi8",
+        ] {
+            assert!(!is_valid_cid(cid), "{cid}");
+        }
+    }
+
+    #[test]
+    fn invalid_cid_is_rendered_as_text() {
+        let cid = "../evil\"><script>";
+        let markup = storage_data(&StorageData::Ipfs(cid.to_string()), "https://ipfs.io/ipfs").into_string();
+        assert!(!markup.contains("href"));
+        assert!(!markup.contains("<script>"));
+
+        let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+        let markup = storage_data(&StorageData::Ipfs(cid.to_string()), "https://ipfs.io/ipfs/").into_string();
+        assert!(markup.contains(&format!("href=\"https://ipfs.io/ipfs/{cid}\"")));
+    }
+}
@@ -1,61 +1,225 @@
+use std::error::Report;
+use std::str::FromStr;
+
 use axum::Json;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use identus_apollo::base64::Base64UrlStrNoPad;
 use identus_apollo::hex::HexStr;
 use identus_did_core::{Did, DidDocument};
-use identus_did_prism::did::PrismDidOps;
+use identus_did_prism::did::did_doc::DidResolutionResult;
+use identus_did_prism::did::{DidResolver, PrismDid, PrismDidOps};
+use identus_did_prism::jws;
 use identus_did_prism::proto::MessageExt;
 use identus_did_prism::proto::node_api::DIDData;
 use utoipa::OpenApi;
 
 use crate::AppState;
 use crate::app::service::error::ResolutionError;
-use crate::http::features::api::indexer::models::IndexerStats;
+use crate::http::features::api::indexer::models::{
+    DidOperation, DidPublicKey, DidReference, DidReferenceQuery, IndexerStats, ResolutionCacheStats, ResolveDidQuery,
+    SearchQuery, SearchResponse, VerifyJwsRequest, VerifyJwsResponse,
+};
 use crate::http::features::api::tags;
-use crate::http::urls::{ApiDid, ApiDidData, ApiIndexerStats};
+use crate::http::urls::{
+    ApiDid, ApiDidData, ApiDidOperations, ApiDidReferences, ApiIndexerStats, ApiSearch, ApiVerifyJws,
+};
 
 #[derive(OpenApi)]
-#[openapi(paths(resolve_did, did_data, indexer_stats))]
+#[openapi(paths(
+    resolve_did,
+    did_data,
+    did_operations,
+    indexer_stats,
+    verify_jws,
+    search,
+    did_references
+))]
 pub struct IndexerOpenApiDoc;
 
 mod models {
-    use identus_did_prism::dlt::{BlockNo, SlotNo};
+    use identus_did_prism::did::did_doc::VerificationMethodFormat;
+    use identus_did_prism::did::operation::SignedPrismOperationHexStr;
+    use identus_did_prism::dlt::{BlockNo, OperationMetadata, SlotNo};
+    use identus_did_prism::jws::VerificationRelationship;
     use serde::{Deserialize, Serialize};
-    use utoipa::ToSchema;
+    use utoipa::{IntoParams, ToSchema};
+
+    #[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub struct ResolveDidQuery {
+        /// The type and public key representation of the verification methods. Defaults to `JsonWebKey2020`.
+        #[serde(rename = "verificationMethodFormat")]
+        pub verification_method_format: Option<VerificationMethodFormat>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct DidOperation {
+        pub metadata: OperationMetadata,
+        pub signed_operation: SignedPrismOperationHexStr,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct VerifyJwsRequest {
+        /// The JWS or JWT in compact serialization whose `kid` header is a PRISM DID URL.
+        pub jws: String,
+        /// The verification relationship the signing key must belong to. Defaults to `assertionMethod`.
+        pub verification_relationship: Option<VerificationRelationship>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct VerifyJwsResponse {
+        pub verified: bool,
+        /// The DID URL of the key that verified the signature.
+        pub kid: Option<String>,
+        pub header: Option<serde_json::Value>,
+        /// The base64url-encoded payload.
+        pub payload: Option<String>,
+        /// The reason the JWS could not be verified.
+        pub error: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub struct SearchQuery {
+        /// A DID or a prefix of its suffix, an operation hash, a Cardano transaction id
+        /// or the hexadecimal key material of a public key (compressed for EC keys).
+        pub q: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct SearchResponse {
+        /// DIDs whose suffix starts with the query.
+        pub dids: Vec<String>,
+        /// Operations whose operation hash or Cardano transaction id is the query.
+        pub operations: Vec<DidOperation>,
+        /// Public keys currently declared by a DID whose key material is the query.
+        pub public_keys: Vec<DidPublicKey>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct DidPublicKey {
+        pub did: String,
+        pub key_id: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub struct DidReferenceQuery {
+        /// The hexadecimal key material of a public key (compressed for EC keys).
+        pub public_key: Option<String>,
+        /// The URL of a service endpoint.
+        pub service_endpoint: Option<String>,
+        /// The zero-based page number. Defaults to `0`.
+        pub page: Option<u32>,
+        /// The maximum number of DIDs in a page. Defaults to `100`, capped at `1000`.
+        pub limit: Option<u32>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct DidReference {
+        pub did: String,
+        /// The id of the public key or the service declaring the query.
+        pub id: String,
+    }
 
     #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
     pub struct IndexerStats {
         pub last_prism_slot_number: Option<SlotNo>,
         pub last_prism_block_number: Option<BlockNo>,
+        pub resolution_cache: Option<ResolutionCacheStats>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct ResolutionCacheStats {
+        pub hits: u64,
+        pub misses: u64,
+        pub size: usize,
+        pub capacity: usize,
+    }
+
+    impl From<crate::app::service::cache::ResolutionCacheStats> for ResolutionCacheStats {
+        fn from(value: crate::app::service::cache::ResolutionCacheStats) -> Self {
+            Self {
+                hits: value.hits,
+                misses: value.misses,
+                size: value.size,
+                capacity: value.capacity,
+            }
+        }
     }
 }
 
+/// The media type a client sends in the `Accept` header to get the DID resolution result.
+const DID_RESOLUTION_MEDIA_TYPE: &str = r#"application/ld+json;profile="https://w3id.org/did-resolution""#;
+
 #[utoipa::path(
     get,
     summary = "W3C DID resolution endpoint",
+    description = "Returns the DID document by default, or the DID resolution result with its metadata \
+        when requested with `Accept: application/ld+json;profile=\"https://w3id.org/did-resolution\"`.",
     path = ApiDid::AXUM_PATH,
     tags = [tags::OP_INDEX],
     responses(
-        (status = OK, description = "Resolve DID successfully", body = DidDocument),
+        (status = OK, description = "Resolve DID successfully", content(
+            (DidDocument = "application/json"),
+            (DidResolutionResult = "application/ld+json;profile=\"https://w3id.org/did-resolution\""),
+        )),
         (status = BAD_REQUEST, description = "Invalid DID"),
         (status = NOT_FOUND, description = "DID not found"),
         (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
     ),
-    params(("did" = Did, Path, description = "The DID to resolve"))
+    params(
+        ("did" = Did, Path, description = "The DID to resolve"),
+        ResolveDidQuery,
+    )
 )]
 pub async fn resolve_did(
     Path(did): Path<String>,
+    Query(query): Query<ResolveDidQuery>,
+    headers: HeaderMap,
     State(state): State<AppState>,
-) -> Result<Json<DidDocument>, StatusCode> {
-    let (result, _) = state.did_service.resolve_did(&did).await;
+) -> Result<Response, StatusCode> {
+    let (result, debug) = state.did_service.resolve_did(&did).await;
     match result {
         Err(ResolutionError::InvalidDid { .. }) => Err(StatusCode::BAD_REQUEST),
         Err(ResolutionError::NotFound) => Err(StatusCode::NOT_FOUND),
         Err(ResolutionError::InternalError { .. }) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-        Ok((did, did_state)) => Ok(Json(did_state.to_did_document(&did.to_did()))),
+        Ok((did, did_state)) => {
+            let format = query.verification_method_format.unwrap_or_default();
+            if accepts_resolution_result(&headers) {
+                let result = did_state.to_resolution_result(&did.to_did(), format, &debug);
+                Ok(([(header::CONTENT_TYPE, DID_RESOLUTION_MEDIA_TYPE)], Json(result)).into_response())
+            } else {
+                Ok(Json(did_state.to_did_document_with_format(&did.to_did(), format)).into_response())
+            }
+        }
     }
 }
 
+/// Whether any media range of the `Accept` header asks for the DID resolution result.
+fn accepts_resolution_result(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_range| {
+            let mut params = media_range.split(';').map(str::trim);
+            let is_ld_json = params
+                .next()
+                .is_some_and(|media_type| media_type.eq_ignore_ascii_case("application/ld+json"));
+            is_ld_json
+                && params.any(|param| {
+                    param.split_once('=').is_some_and(|(name, value)| {
+                        name.trim().eq_ignore_ascii_case("profile")
+                            && value.trim().trim_matches('"') == "https://w3id.org/did-resolution"
+                    })
+                })
+        })
+}
+
 #[utoipa::path(
     get,
     summary = "Adapter for returning DIDData protobuf message",
@@ -84,6 +248,41 @@ pub async fn did_data(Path(did): Path<String>, State(state): State<AppState>) ->
     }
 }
 
+#[utoipa::path(
+    get,
+    summary = "List all published operations of a DID including the invalid ones",
+    path = ApiDidOperations::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "Operations of the DID with their block metadata", body = Vec<DidOperation>),
+        (status = BAD_REQUEST, description = "Invalid DID"),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(("did" = Did, Path, description = "The DID to get the operations"))
+)]
+pub async fn did_operations(
+    Path(did): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<DidOperation>>, StatusCode> {
+    let did = PrismDid::from_str(&did).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let result = state.did_service.get_operations(&did.into_canonical()).await;
+    match result {
+        Ok(operations) => Ok(Json(
+            operations
+                .into_iter()
+                .map(|(metadata, signed_operation)| DidOperation {
+                    metadata,
+                    signed_operation: signed_operation.into(),
+                })
+                .collect(),
+        )),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 #[utoipa::path(
     get,
     path = ApiIndexerStats::AXUM_PATH,
@@ -94,14 +293,17 @@ pub async fn did_data(Path(did): Path<String>, State(state): State<AppState>) ->
 )]
 pub async fn indexer_stats(State(state): State<AppState>) -> Result<Json<IndexerStats>, StatusCode> {
     let result = state.did_service.get_indexer_stats().await;
+    let resolution_cache = state.did_service.get_cache_stats().map(ResolutionCacheStats::from);
     let stats = match result {
         Ok(None) => IndexerStats {
             last_prism_slot_number: None,
             last_prism_block_number: None,
+            resolution_cache,
         },
         Ok(Some((slot, block))) => IndexerStats {
             last_prism_block_number: Some(block),
             last_prism_slot_number: Some(slot),
+            resolution_cache,
         },
         Err(e) => {
             // TODO: improve error handling
@@ -111,3 +313,312 @@ pub async fn indexer_stats(State(state): State<AppState>) -> Result<Json<Indexer
     };
     Ok(Json(stats))
 }
+
+#[utoipa::path(
+    post,
+    summary = "Verify a JWS or JWT signed by a key of a PRISM DID",
+    path = ApiVerifyJws::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    request_body = VerifyJwsRequest,
+    responses(
+        (status = OK, description = "Verification result of the JWS", body = VerifyJwsResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    )
+)]
+pub async fn verify_jws(
+    State(state): State<AppState>,
+    Json(req): Json<VerifyJwsRequest>,
+) -> Result<Json<VerifyJwsResponse>, StatusCode> {
+    let relationship = req
+        .verification_relationship
+        .unwrap_or(jws::VerificationRelationship::AssertionMethod);
+    let result = jws::verify_jws(&state.did_service, &req.jws, relationship).await;
+    let response = match result {
+        Ok(verified) => VerifyJwsResponse {
+            verified: true,
+            kid: Some(verified.kid),
+            header: Some(verified.header.into()),
+            payload: Some(Base64UrlStrNoPad::from(verified.payload).to_string()),
+            error: None,
+        },
+        Err(jws::Error::Resolution { source, .. })
+            if matches!(
+                source.downcast_ref::<ResolutionError>(),
+                Some(ResolutionError::InternalError { .. })
+            ) =>
+        {
+            tracing::error!("{}", source);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)?
+        }
+        Err(e) => VerifyJwsResponse {
+            verified: false,
+            kid: None,
+            header: None,
+            payload: None,
+            error: Some(Report::new(e).to_string()),
+        },
+    };
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    summary = "Search DIDs, operations and public keys",
+    path = ApiSearch::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "Entities matching the query", body = SearchResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(SearchQuery)
+)]
+pub async fn search(
+    Query(query): Query<SearchQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<SearchResponse>, StatusCode> {
+    match state.did_service.search(&query.q).await {
+        Ok(result) => Ok(Json(SearchResponse {
+            dids: result.dids.iter().map(|did| did.to_string()).collect(),
+            operations: result
+                .operations
+                .into_iter()
+                .map(|(metadata, signed_operation)| DidOperation {
+                    metadata,
+                    signed_operation: signed_operation.into(),
+                })
+                .collect(),
+            public_keys: result
+                .public_keys
+                .into_iter()
+                .map(|(did, pk)| DidPublicKey {
+                    did: did.to_string(),
+                    key_id: pk.id.to_string(),
+                })
+                .collect(),
+        })),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    summary = "Find the DIDs currently declaring a public key or a service endpoint",
+    description = "Exactly one of `public_key` or `service_endpoint` must be provided.",
+    path = ApiDidReferences::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "DIDs declaring the public key or the service endpoint", body = Vec<DidReference>),
+        (status = BAD_REQUEST, description = "Invalid query"),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(DidReferenceQuery)
+)]
+pub async fn did_references(
+    Query(query): Query<DidReferenceQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<DidReference>>, StatusCode> {
+    let result = match (query.public_key, query.service_endpoint) {
+        (Some(public_key), None) => {
+            let key_data = HexStr::from_str(&public_key).map_err(|_| StatusCode::BAD_REQUEST)?;
+            state
+                .did_service
+                .get_dids_by_public_key(&key_data.to_bytes(), query.page, query.limit)
+                .await
+        }
+        (None, Some(service_endpoint)) => {
+            state
+                .did_service
+                .get_dids_by_service_endpoint(&service_endpoint, query.page, query.limit)
+                .await
+        }
+        _ => Err(StatusCode::BAD_REQUEST)?,
+    };
+    match result {
+        Ok(dids) => Ok(Json(
+            dids.items
+                .into_iter()
+                .map(|(did, id)| DidReference {
+                    did: did.to_string(),
+                    id,
+                })
+                .collect(),
+        )),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use axum::extract::{Path, Query, State};
+    use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+    use chrono::DateTime;
+    use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+    use identus_apollo::hash::sha256;
+    use identus_apollo::hex::HexStr;
+    use identus_did_prism::did::operation::builder::{
+        CreateDidOperationBuilder, UpdateDidOperationBuilder, sign_operation,
+    };
+    use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+    use identus_did_prism::did::{CanonicalPrismDid, LongFormPrismDid};
+    use identus_did_prism::dlt::{BlockHash, BlockMetadata, OperationMetadata, TxId};
+    use identus_did_prism::prelude::*;
+    use identus_did_prism::protocol::resolver::resolve_published;
+    use identus_did_prism_indexer::repo::OperationRepo;
+    use identus_did_prism_indexer::run_indexer_loop;
+    use serde_json::json;
+
+    use super::models::ResolveDidQuery;
+    use super::{DID_RESOLUTION_MEDIA_TYPE, resolve_did};
+    use crate::test_utils::new_app_state;
+    use crate::{AppState, RunMode};
+
+    const MASTER_KEY: [u8; 32] = [1; 32];
+
+    fn metadata(block_number: u64) -> OperationMetadata {
+        OperationMetadata {
+            block_metadata: BlockMetadata {
+                slot_number: (block_number * 10).into(),
+                block_number: block_number.into(),
+                block_hash: Some(BlockHash::from(sha256(format!("block-{block_number}")))),
+                cbt: DateTime::from_timestamp(block_number as i64 * 20, 0).unwrap(),
+                absn: 0,
+                tx_hash: Some(TxId::from(sha256(format!("tx-{block_number}")))),
+            },
+            osn: 0,
+        }
+    }
+
+    fn create_did_operation() -> (PrismOperation, Secp256k1PrivateKey) {
+        let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+        let operation = CreateDidOperationBuilder::new()
+            .add_key(
+                "master-0",
+                KeyUsage::MasterKey,
+                NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+            )
+            .build();
+        (operation, master_sk)
+    }
+
+    /// Resolve the DID with the given `Accept` header and return the response content type and body.
+    async fn resolve_with_accept(
+        state: &AppState,
+        did: String,
+        accept: Option<&'static str>,
+    ) -> Result<(String, serde_json::Value), StatusCode> {
+        let query = ResolveDidQuery {
+            verification_method_format: None,
+        };
+        let mut headers = HeaderMap::new();
+        if let Some(accept) = accept {
+            headers.insert(header::ACCEPT, HeaderValue::from_static(accept));
+        }
+        let response = resolve_did(Path(did), Query(query), headers, State(state.clone())).await?;
+        let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap().to_string();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        Ok((content_type, serde_json::from_slice(&body).unwrap()))
+    }
+
+    async fn resolve(state: &AppState, did: String) -> Result<serde_json::Value, StatusCode> {
+        let (_, result) = resolve_with_accept(state, did, Some(DID_RESOLUTION_MEDIA_TYPE)).await?;
+        Ok(result)
+    }
+
+    #[tokio::test]
+    async fn resolve_did_returns_did_document_by_default() {
+        let (_, state) = new_app_state(RunMode::Indexer, None).await;
+        let (operation, _) = create_did_operation();
+        let long_form_did = LongFormPrismDid::from_operation(&operation).unwrap();
+
+        for accept in [None, Some("application/json"), Some("application/ld+json")] {
+            let (content_type, result) = resolve_with_accept(&state, long_form_did.to_string(), accept)
+                .await
+                .unwrap();
+            assert_eq!(content_type, "application/json");
+            assert_eq!(result["id"], long_form_did.to_string());
+            assert!(result.get("didDocumentMetadata").is_none());
+        }
+
+        let accept = r#"application/json;q=0.5, application/ld+json; profile="https://w3id.org/did-resolution""#;
+        let (content_type, result) = resolve_with_accept(&state, long_form_did.to_string(), Some(accept))
+            .await
+            .unwrap();
+        assert_eq!(content_type, DID_RESOLUTION_MEDIA_TYPE);
+        assert_eq!(result["didDocument"]["id"], long_form_did.to_string());
+    }
+
+    #[tokio::test]
+    async fn resolve_did_returns_resolution_result() {
+        let (db, state) = new_app_state(RunMode::Indexer, None).await;
+        let (operation, master_sk) = create_did_operation();
+        let did = CanonicalPrismDid::from_operation(&operation).unwrap();
+        let create_did_op = sign_operation(operation, "master-0", &master_sk);
+        let did_state = resolve_published(vec![(metadata(1), create_did_op.clone())]).0.unwrap();
+        let update_did_op = UpdateDidOperationBuilder::new(&did_state)
+            .add_key(
+                "master-1",
+                KeyUsage::MasterKey,
+                NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+            )
+            .build();
+        let update_did_op = sign_operation(update_did_op, "master-0", &master_sk);
+        let update_op_hash = update_did_op.operation_hash().unwrap();
+        db.insert_raw_operations(vec![(metadata(1), create_did_op), (metadata(2), update_did_op)])
+            .await
+            .unwrap();
+        run_indexer_loop(&db, |_| {}).await.unwrap();
+
+        let result = resolve(&state, did.to_string()).await.unwrap();
+        assert_eq!(result["@context"], "https://w3id.org/did-resolution/v1");
+        assert_eq!(result["didDocument"]["id"], did.to_string());
+        assert_eq!(
+            result["didResolutionMetadata"]["contentType"],
+            "application/did+ld+json"
+        );
+        assert_eq!(
+            result["didDocumentMetadata"],
+            json!({
+                "versionId": HexStr::from(update_op_hash.as_bytes()).to_string(),
+                "created": "1970-01-01T00:00:20Z",
+                "updated": "1970-01-01T00:00:40Z",
+                "createdTxHash": HexStr::from(sha256("tx-1").as_bytes()).to_string(),
+                "createdBlockHash": HexStr::from(sha256("block-1").as_bytes()).to_string(),
+                "updatedTxHash": HexStr::from(sha256("tx-2").as_bytes()).to_string(),
+                "updatedBlockHash": HexStr::from(sha256("block-2").as_bytes()).to_string(),
+            })
+        );
+
+        let (operation, _) = create_did_operation();
+        let long_form_did = LongFormPrismDid::from_operation(&operation).unwrap();
+        let result = resolve(&state, long_form_did.to_string()).await.unwrap();
+        assert_eq!(result["didDocument"]["id"], long_form_did.to_string());
+        assert!(result["didDocumentMetadata"]["createdTxHash"].is_string());
+    }
+
+    #[tokio::test]
+    async fn resolve_unpublished_did() {
+        let (_, state) = new_app_state(RunMode::Indexer, None).await;
+        let (operation, _) = create_did_operation();
+        let operation_hash = operation.operation_hash();
+        let did = CanonicalPrismDid::from_operation(&operation).unwrap();
+        let long_form_did = LongFormPrismDid::from_operation(&operation).unwrap();
+
+        let result = resolve(&state, long_form_did.to_string()).await.unwrap();
+        assert_eq!(
+            result["didDocumentMetadata"],
+            json!({ "versionId": HexStr::from(operation_hash.as_bytes()).to_string() })
+        );
+
+        let result = resolve(&state, did.to_string()).await;
+        assert_eq!(result, Err(StatusCode::NOT_FOUND));
+        let result = resolve(&state, "did:prism:invalid".to_string()).await;
+        assert_eq!(result, Err(StatusCode::BAD_REQUEST));
+    }
+}
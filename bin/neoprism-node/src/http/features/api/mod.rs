@@ -6,12 +6,14 @@ use utoipa_swagger_ui::SwaggerUi;
 use crate::http::features::api::indexer::IndexerOpenApiDoc;
 use crate::http::features::api::submitter::SubmitterOpenApiDoc;
 use crate::http::features::api::system::SystemOpenApiDoc;
+use crate::http::features::api::vdr::VdrOpenApiDoc;
 use crate::http::urls;
 use crate::{AppState, RunMode};
 
 mod indexer;
 mod submitter;
 mod system;
+mod vdr;
 
 #[derive(OpenApi)]
 #[openapi(servers(
@@ -29,11 +31,11 @@ mod tags {
 
 pub fn router(mode: RunMode) -> Router<AppState> {
     let base_oas = BaseOpenApiDoc::openapi().merge_from(SystemOpenApiDoc::openapi());
-    let indexer_oas = IndexerOpenApiDoc::openapi();
+    let indexer_oas = IndexerOpenApiDoc::openapi().merge_from(VdrOpenApiDoc::openapi());
     let submitter_oas = SubmitterOpenApiDoc::openapi();
 
     let oas = match mode {
-        RunMode::Indexer => base_oas.merge_from(indexer_oas),
+        RunMode::Indexer | RunMode::Resolver => base_oas.merge_from(indexer_oas),
         RunMode::Submitter => base_oas.merge_from(submitter_oas),
         RunMode::Standalone => base_oas.merge_from(indexer_oas).merge_from(submitter_oas),
     };
@@ -46,7 +48,16 @@ pub fn router(mode: RunMode) -> Router<AppState> {
     let indexer_router = Router::new()
         .route(urls::ApiDid::AXUM_PATH, get(indexer::resolve_did))
         .route(urls::ApiDidData::AXUM_PATH, get(indexer::did_data))
-        .route(urls::ApiIndexerStats::AXUM_PATH, get(indexer::indexer_stats));
+        .route(urls::ApiDidOperations::AXUM_PATH, get(indexer::did_operations))
+        .route(urls::ApiIndexerStats::AXUM_PATH, get(indexer::indexer_stats))
+        .route(urls::ApiVerifyJws::AXUM_PATH, post(indexer::verify_jws))
+        .route(urls::ApiSearch::AXUM_PATH, get(indexer::search))
+        .route(urls::ApiDidReferences::AXUM_PATH, get(indexer::did_references))
+        .route(urls::ApiVdrEntry::AXUM_PATH, get(vdr::vdr_entry))
+        .route(urls::ApiVdrEntryData::AXUM_PATH, get(vdr::vdr_entry_data))
+        .route(urls::ApiVdrEntryHistory::AXUM_PATH, get(vdr::vdr_entry_history))
+        .route(urls::ApiVdrStatusList::AXUM_PATH, get(vdr::vdr_entry_status_list))
+        .route(urls::ApiDidVdrEntries::AXUM_PATH, get(vdr::did_vdr_entries));
 
     let submitter_router = Router::new().route(
         urls::ApiSignedOpSubmissions::AXUM_PATH,
@@ -54,7 +65,7 @@ pub fn router(mode: RunMode) -> Router<AppState> {
     );
 
     match mode {
-        RunMode::Indexer => base_router.merge(indexer_router),
+        RunMode::Indexer | RunMode::Resolver => base_router.merge(indexer_router),
         RunMode::Submitter => base_router.merge(submitter_router),
         RunMode::Standalone => base_router.merge(indexer_router).merge(submitter_router),
     }
@@ -28,6 +28,7 @@ mod models {
         Indexer,
         Submitter,
         Standalone,
+        Resolver,
     }
 
     impl From<RunMode> for AppMetaRunMode {
@@ -36,6 +37,7 @@ mod models {
                 RunMode::Indexer => Self::Indexer,
                 RunMode::Submitter => Self::Submitter,
                 RunMode::Standalone => Self::Standalone,
+                RunMode::Resolver => Self::Resolver,
             }
         }
     }
@@ -0,0 +1,315 @@
+use std::error::Report;
+use std::str::FromStr;
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use identus_apollo::hash::Sha256Digest;
+use identus_apollo::hex::HexStr;
+use identus_did_core::Did;
+use identus_did_prism::did::operation::StorageData;
+use identus_did_prism::did::{CanonicalPrismDid, PrismDid, PrismDidOps, StorageState};
+use identus_did_prism::status_list::{self, StatusListCredentialSubject};
+use utoipa::OpenApi;
+
+use crate::AppState;
+use crate::http::features::api::tags;
+use crate::http::features::api::vdr::models::{StatusListEntry, StatusListQuery, VdrEntry, VdrEntryVersion};
+use crate::http::urls::{ApiDidVdrEntries, ApiVdrEntry, ApiVdrEntryData, ApiVdrEntryHistory, ApiVdrStatusList};
+
+#[derive(OpenApi)]
+#[openapi(paths(vdr_entry, vdr_entry_data, vdr_entry_history, vdr_entry_status_list, did_vdr_entries))]
+pub struct VdrOpenApiDoc;
+
+mod models {
+    use identus_apollo::hex::HexStr;
+    use identus_did_prism::did::operation::{StatusListData, StorageData};
+    use identus_did_prism::did::{CanonicalPrismDid, StorageState};
+    use identus_did_prism::dlt::OperationMetadata;
+    use identus_did_prism::protocol::resolver::StorageVersion;
+    use identus_did_prism::status_list::StatusListFormat;
+    use serde::{Deserialize, Serialize};
+    use utoipa::{IntoParams, ToSchema};
+
+    #[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+    #[into_params(parameter_in = Query)]
+    pub struct StatusListQuery {
+        /// The status list format. Defaults to `BitstringStatusList`.
+        pub format: Option<StatusListFormat>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct VdrEntry {
+        /// The DID that owns the entry.
+        pub did: String,
+        /// The hash of the operation that created the entry, which is used as the entry identifier.
+        pub init_operation_hash: String,
+        /// The hash of the latest operation applied to the entry.
+        pub last_operation_hash: String,
+        pub data: VdrEntryData,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    pub enum VdrEntryData {
+        /// Hexadecimal string of the stored bytes.
+        Bytes {
+            value: String,
+        },
+        Ipfs {
+            cid: String,
+        },
+        StatusList {
+            value: StatusListEntry,
+        },
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct VdrEntryVersion {
+        /// The hash of the operation that produced this version.
+        pub operation_hash: String,
+        pub metadata: OperationMetadata,
+        /// The content of the entry after the operation or `null` if the operation deactivated the entry.
+        pub data: Option<VdrEntryData>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+    pub struct StatusListEntry {
+        pub state: i64,
+        pub name: String,
+        pub details: String,
+        pub list_size: u32,
+        pub status_size: u32,
+        /// Hexadecimal string of the statuses packed as a bitstring.
+        pub int_status: String,
+    }
+
+    impl From<&StatusListData> for StatusListEntry {
+        fn from(value: &StatusListData) -> Self {
+            Self {
+                state: value.state,
+                name: value.name.clone(),
+                details: value.detail.clone(),
+                list_size: value.list_size,
+                status_size: value.status_size,
+                int_status: HexStr::from(&value.int_status).to_string(),
+            }
+        }
+    }
+
+    impl From<&StorageData> for VdrEntryData {
+        fn from(value: &StorageData) -> Self {
+            match value {
+                StorageData::Bytes(bytes) => VdrEntryData::Bytes {
+                    value: HexStr::from(bytes).to_string(),
+                },
+                StorageData::Ipfs(cid) => VdrEntryData::Ipfs { cid: cid.clone() },
+                StorageData::StatusList(status_list) => VdrEntryData::StatusList {
+                    value: status_list.into(),
+                },
+            }
+        }
+    }
+
+    impl From<&StorageVersion> for VdrEntryVersion {
+        fn from(value: &StorageVersion) -> Self {
+            Self {
+                operation_hash: HexStr::from(value.operation_hash.as_bytes()).to_string(),
+                metadata: value.metadata.clone(),
+                data: value.data.as_ref().map(VdrEntryData::from),
+            }
+        }
+    }
+
+    impl VdrEntry {
+        pub fn new(did: &CanonicalPrismDid, storage: &StorageState) -> Self {
+            Self {
+                did: did.to_string(),
+                init_operation_hash: HexStr::from(storage.init_operation_hash.as_bytes()).to_string(),
+                last_operation_hash: HexStr::from(storage.last_operation_hash.as_bytes()).to_string(),
+                data: storage.data.as_ref().into(),
+            }
+        }
+    }
+}
+
+fn parse_operation_hash(hash: &str) -> Result<Sha256Digest, StatusCode> {
+    let bytes = HexStr::from_str(hash).map_err(|_| StatusCode::BAD_REQUEST)?.to_bytes();
+    Sha256Digest::from_bytes(&bytes).map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+async fn get_vdr_entry(
+    state: &AppState,
+    init_operation_hash: &str,
+) -> Result<(CanonicalPrismDid, StorageState), StatusCode> {
+    let init_operation_hash = parse_operation_hash(init_operation_hash)?;
+    match state.did_service.get_vdr_entry(&init_operation_hash).await {
+        Ok(Some(entry)) => Ok(entry),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    summary = "Get the current state of a VDR entry",
+    path = ApiVdrEntry::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "The VDR entry", body = VdrEntry),
+        (status = BAD_REQUEST, description = "Invalid operation hash"),
+        (status = NOT_FOUND, description = "VDR entry not found or deactivated"),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(("init_operation_hash" = String, Path, description = "Hexadecimal hash of the operation that created the entry"))
+)]
+pub async fn vdr_entry(
+    Path(init_operation_hash): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<VdrEntry>, StatusCode> {
+    let (did, entry) = get_vdr_entry(&state, &init_operation_hash).await?;
+    Ok(Json(VdrEntry::new(&did, &entry)))
+}
+
+#[utoipa::path(
+    get,
+    summary = "Get the content of a VDR entry",
+    description = "Bytes are returned as `application/octet-stream`, IPFS CIDs as `text/plain` and status list entries as `application/json`.",
+    path = ApiVdrEntryData::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "The content of the VDR entry", content(
+            (Vec<u8> = "application/octet-stream"),
+            (String = "text/plain"),
+            (StatusListEntry = "application/json"),
+        )),
+        (status = BAD_REQUEST, description = "Invalid operation hash"),
+        (status = NOT_FOUND, description = "VDR entry not found or deactivated"),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(("init_operation_hash" = String, Path, description = "Hexadecimal hash of the operation that created the entry"))
+)]
+pub async fn vdr_entry_data(
+    Path(init_operation_hash): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Response, StatusCode> {
+    let (_, entry) = get_vdr_entry(&state, &init_operation_hash).await?;
+    let response = match entry.data.as_ref() {
+        StorageData::Bytes(bytes) => {
+            ([(header::CONTENT_TYPE, "application/octet-stream")], bytes.clone()).into_response()
+        }
+        StorageData::Ipfs(cid) => ([(header::CONTENT_TYPE, "text/plain")], cid.clone()).into_response(),
+        StorageData::StatusList(status_list) => Json(StatusListEntry::from(status_list)).into_response(),
+    };
+    Ok(response)
+}
+
+#[utoipa::path(
+    get,
+    summary = "Get all versions of a VDR entry",
+    description = "Versions are ordered from the operation that created the entry to the latest operation. A deactivated entry ends with a version without data.",
+    path = ApiVdrEntryHistory::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "The versions of the VDR entry", body = Vec<VdrEntryVersion>),
+        (status = BAD_REQUEST, description = "Invalid operation hash"),
+        (status = NOT_FOUND, description = "VDR entry not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(("init_operation_hash" = String, Path, description = "Hexadecimal hash of the operation that created the entry"))
+)]
+pub async fn vdr_entry_history(
+    Path(init_operation_hash): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<VdrEntryVersion>>, StatusCode> {
+    let init_operation_hash = parse_operation_hash(&init_operation_hash)?;
+    match state.did_service.get_vdr_entry_history(&init_operation_hash).await {
+        Ok(Some((_, history))) => Ok(Json(history.iter().map(VdrEntryVersion::from).collect())),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    summary = "Get the status list credential subject of a VDR entry",
+    description = "Derive the `credentialSubject` of a status list credential from a status list entry. The issuer can wrap it in a credential and sign it.",
+    path = ApiVdrStatusList::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "The status list credential subject", body = StatusListCredentialSubject),
+        (status = BAD_REQUEST, description = "Invalid operation hash"),
+        (status = NOT_FOUND, description = "VDR entry not found, deactivated or not a status list"),
+        (status = UNPROCESSABLE_ENTITY, description = "Status size, list size or purpose of the status list is not supported by the requested format"),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(
+        ("init_operation_hash" = String, Path, description = "Hexadecimal hash of the operation that created the entry"),
+        StatusListQuery,
+    )
+)]
+pub async fn vdr_entry_status_list(
+    Path(init_operation_hash): Path<String>,
+    Query(query): Query<StatusListQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<StatusListCredentialSubject>, StatusCode> {
+    let (_, entry) = get_vdr_entry(&state, &init_operation_hash).await?;
+    let StorageData::StatusList(status_list) = entry.data.as_ref() else {
+        Err(StatusCode::NOT_FOUND)?
+    };
+    let id = format!(
+        "{}{}#list",
+        state.external_url,
+        ApiVdrStatusList::new_uri(init_operation_hash)
+    );
+    match StatusListCredentialSubject::new(id, status_list, query.format.unwrap_or_default()) {
+        Ok(subject) => Ok(Json(subject)),
+        Err(
+            e @ (status_list::Error::UnsupportedStatusSize { .. }
+            | status_list::Error::UnsupportedListSize { .. }
+            | status_list::Error::UnsupportedStatusPurpose { .. }),
+        ) => {
+            tracing::debug!("{}", e);
+            Err(StatusCode::UNPROCESSABLE_ENTITY)
+        }
+        Err(e) => {
+            tracing::error!("{}", Report::new(e).pretty(true));
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    summary = "List the current state of all active VDR entries of a DID",
+    path = ApiDidVdrEntries::AXUM_PATH,
+    tags = [tags::OP_INDEX],
+    responses(
+        (status = OK, description = "VDR entries owned by the DID", body = Vec<VdrEntry>),
+        (status = BAD_REQUEST, description = "Invalid DID"),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error"),
+    ),
+    params(("did" = Did, Path, description = "The DID that owns the entries"))
+)]
+pub async fn did_vdr_entries(
+    Path(did): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<VdrEntry>>, StatusCode> {
+    let did = PrismDid::from_str(&did)
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+        .into_canonical();
+    match state.did_service.get_vdr_entries(&did).await {
+        Ok(entries) => Ok(Json(entries.iter().map(|e| VdrEntry::new(&did, e)).collect())),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
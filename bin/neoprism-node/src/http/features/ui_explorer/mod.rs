@@ -1,8 +1,13 @@
+use std::str::FromStr;
+
 use axum::Router;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
 use axum::routing::get;
+use identus_apollo::hash::Sha256Digest;
+use identus_apollo::hex::HexStr;
 use maud::Markup;
-use models::PageQuery;
+use models::{PageQuery, SearchQuery};
 
 use crate::AppState;
 use crate::http::urls;
@@ -15,6 +20,10 @@ pub fn router() -> Router<AppState> {
         .route(urls::Explorer::AXUM_PATH, get(index))
         .route(urls::ExplorerDltCursor::AXUM_PATH, get(dlt_cursor))
         .route(urls::ExplorerDidList::AXUM_PATH, get(did_list))
+        .route(urls::ExplorerSearch::AXUM_PATH, get(search))
+        .route(urls::ExplorerOperationList::AXUM_PATH, get(operation_list))
+        .route(urls::ExplorerOperation::AXUM_PATH, get(operation))
+        .route(urls::ExplorerVdrEntry::AXUM_PATH, get(vdr_entry))
 }
 
 async fn index(Query(page): Query<PageQuery>, State(state): State<AppState>) -> Markup {
@@ -35,3 +44,75 @@ async fn did_list(Query(page): Query<PageQuery>, State(state): State<AppState>)
     let dids = state.did_service.get_all_dids(page).await.unwrap(); // FIXME: unwrap
     views::did_list(dids)
 }
+
+async fn search(Query(query): Query<SearchQuery>, State(state): State<AppState>) -> Result<Markup, StatusCode> {
+    let network = state.dlt_source.as_ref().map(|i| i.network);
+    let query = query.q.unwrap_or_default();
+    let result = state.did_service.search(&query).await.map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(views::search(network, &query, result))
+}
+
+async fn operation_list(Query(page): Query<PageQuery>, State(state): State<AppState>) -> Result<Markup, StatusCode> {
+    let page = page.page.map(|i| i.max(1) - 1);
+    let network = state.dlt_source.as_ref().map(|i| i.network);
+    let operations = state.did_service.get_recent_operations(page).await.map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(views::operation_list(network, operations))
+}
+
+async fn operation(Path(operation_hash): Path<String>, State(state): State<AppState>) -> Result<Markup, StatusCode> {
+    let network = state.dlt_source.as_ref().map(|i| i.network);
+    let Some(hash) = parse_operation_hash(&operation_hash) else {
+        return Ok(views::operation(
+            network,
+            &operation_hash,
+            vec![],
+            &state.ipfs_gateway_url,
+        ));
+    };
+    let details = state.did_service.get_operation_details(&hash).await.map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(views::operation(
+        network,
+        &operation_hash,
+        details,
+        &state.ipfs_gateway_url,
+    ))
+}
+
+async fn vdr_entry(
+    Path(init_operation_hash): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Markup, StatusCode> {
+    let network = state.dlt_source.as_ref().map(|i| i.network);
+    let Some(hash) = parse_operation_hash(&init_operation_hash) else {
+        return Ok(views::vdr_entry(
+            network,
+            &init_operation_hash,
+            None,
+            &state.ipfs_gateway_url,
+        ));
+    };
+    let history = state.did_service.get_vdr_entry_history(&hash).await.map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(views::vdr_entry(
+        network,
+        &init_operation_hash,
+        history,
+        &state.ipfs_gateway_url,
+    ))
+}
+
+fn parse_operation_hash(hash: &str) -> Option<Sha256Digest> {
+    let bytes = HexStr::from_str(hash).ok()?.to_bytes();
+    Sha256Digest::from_bytes(&bytes).ok()
+}
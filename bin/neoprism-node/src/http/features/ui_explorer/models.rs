@@ -4,3 +4,8 @@ use serde::{Deserialize, Serialize};
 pub struct PageQuery {
     pub page: Option<u32>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+}
@@ -1,9 +1,14 @@
+use std::error::Report;
+
 use identus_apollo::hex::HexStr;
-use identus_did_prism::did::CanonicalPrismDid;
-use identus_did_prism::dlt::{DltCursor, NetworkIdentifier};
+use identus_did_prism::did::{CanonicalPrismDid, DidState};
+use identus_did_prism::dlt::{DltCursor, NetworkIdentifier, OperationMetadata};
+use identus_did_prism::prelude::*;
+use identus_did_prism::protocol::resolver::StorageVersion;
 use identus_did_prism::utils::paging::Paginated;
 use maud::{Markup, html};
 
+use crate::app::service::{OperationDetail, OperationOutcome, SearchResult};
 use crate::http::features::ui_explorer::models::PageQuery;
 use crate::http::features::ui_resolver::models::DidQuery;
 use crate::http::{components, urls};
@@ -14,12 +19,95 @@ pub fn index(
     dids: Paginated<CanonicalPrismDid>,
 ) -> Markup {
     let body = html! {
+        (search_box(None))
         (dlt_cursor_card(cursor))
         (did_list(dids))
     };
     components::page_layout("Explorer", network, body)
 }
 
+fn search_box(query: Option<&str>) -> Markup {
+    html! {
+        div class="flex flex-col items-center min-w-screen pt-8" {
+            form
+                method="GET"
+                action=(urls::ExplorerSearch::new_uri(None))
+                class="form-control w-full" {
+                div class="flex flex-col flex-wrap items-center space-x-2 space-y-2" {
+                    input
+                        type="text"
+                        name="q"
+                        placeholder="DID, operation hash, transaction id or public key"
+                        value=[query]
+                        class="input input-bordered w-9/12 max-w-xl"
+                        required;
+                    button
+                        type="submit"
+                        class="btn btn-primary"
+                        { "Search" }
+                }
+            }
+        }
+    }
+}
+
+pub fn search(network: Option<NetworkIdentifier>, query: &str, result: SearchResult) -> Markup {
+    let did_link = |did: &CanonicalPrismDid, fragment: Option<&str>| {
+        let uri = urls::Resolver::new_uri(Some(DidQuery {
+            did: Some(did.to_string()),
+        }));
+        html! {
+            a class="link link-hover font-mono" href=(uri) {
+                (did.to_string())
+                @if let Some(fragment) = fragment { "#" (fragment) }
+            }
+        }
+    };
+    let is_empty = result.dids.is_empty() && result.operations.is_empty() && result.public_keys.is_empty();
+    let body = html! {
+        (search_box(Some(query)))
+        div class="flex flex-row w-screen justify-center" {
+            div class="flex flex-col w-full max-w-4xl" {
+                div class="w-full m-4 space-y-4 wrap-anywhere" {
+                    @if is_empty {
+                        p class="text-neutral-content text-center" { "No result found" }
+                    }
+                    @if !result.dids.is_empty() {
+                        p class="text-2xl font-bold" { "DIDs" }
+                        ul {
+                            @for did in &result.dids { li class="truncate" { (did_link(did, None)) } }
+                        }
+                    }
+                    @if !result.operations.is_empty() {
+                        p class="text-2xl font-bold" { "Operations" }
+                        ul {
+                            @for (metadata, signed_operation) in &result.operations {
+                                li class="truncate" {
+                                    span class="badge badge-outline mr-2" { (components::operation_type(signed_operation)) }
+                                    span class="mr-2" { "Block " (metadata.block_metadata.block_number) }
+                                    (operation_link(signed_operation))
+                                }
+                            }
+                        }
+                    }
+                    @if !result.public_keys.is_empty() {
+                        p class="text-2xl font-bold" { "Public keys" }
+                        ul {
+                            @for (did, pk) in &result.public_keys {
+                                li class="truncate" {
+                                    span class="badge badge-outline mr-2" { (format!("{:?}", pk.data.usage())) }
+                                    (did_link(did, Some(pk.id.as_str())))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+    components::page_layout("Explorer", network, body)
+}
+
 pub fn did_list(dids: Paginated<CanonicalPrismDid>) -> Markup {
     let did_elems = dids.items.iter().map(|did| {
         let uri = urls::Resolver::new_uri(Some(DidQuery {
@@ -31,10 +119,33 @@ pub fn did_list(dids: Paginated<CanonicalPrismDid>) -> Markup {
             }
         }
     });
-    let pagination_items = (0..dids.total_pages())
+    let pagination = pagination(&dids, |page| {
+        urls::Explorer::new_uri(Some(PageQuery { page: Some(page) }))
+    });
+    let hx_url = urls::ExplorerDidList::new_uri(Some(PageQuery {
+        page: Some(dids.current_page + 1),
+    }));
+    html! {
+        div
+            class="flex flex-col items-center w-full mx-2"
+            id="did-list"
+            hx-get=(hx_url)
+            hx-trigger="load delay:5s"
+            hx-swap="outerHTML"
+        {
+            (pagination)
+            @for elem in did_elems { (elem) }
+            (pagination)
+        }
+    }
+}
+
+fn pagination<T>(items: &Paginated<T>, goto_uri: impl Fn(u32) -> String) -> Markup {
+    let pagination_items = (0..items.total_pages())
         .map(|i| {
             // Only first, last and N pages before and after the current page
-            let should_display = |i: u32| i == 0 || i == (dids.total_pages() - 1) || i.abs_diff(dids.current_page) <= 2;
+            let should_display =
+                |i: u32| i == 0 || i == (items.total_pages() - 1) || i.abs_diff(items.current_page) <= 2;
             (i, should_display(i), should_display(i + 1))
         })
         .filter_map(
@@ -45,7 +156,7 @@ pub fn did_list(dids: Paginated<CanonicalPrismDid>) -> Markup {
             },
         )
         .map(|(i, is_3dots)| {
-            let is_active = i == dids.current_page;
+            let is_active = i == items.current_page;
             let classes = if is_active {
                 "join-item btn btn-active".to_string()
             } else {
@@ -57,32 +168,13 @@ pub fn did_list(dids: Paginated<CanonicalPrismDid>) -> Markup {
                 }
             } else {
                 let userfacing_page = i + 1;
-                let goto_uri = urls::Explorer::new_uri(Some(PageQuery {
-                    page: Some(userfacing_page),
-                }));
-                html! { a href=(goto_uri) class=(classes) { (userfacing_page) } }
+                html! { a href=(goto_uri(userfacing_page)) class=(classes) { (userfacing_page) } }
             }
         });
-    let pagination = html! {
+    html! {
         div class="join my-2" {
             @for i in pagination_items { (i) }
         }
-    };
-    let hx_url = urls::ExplorerDidList::new_uri(Some(PageQuery {
-        page: Some(dids.current_page + 1),
-    }));
-    html! {
-        div
-            class="flex flex-col items-center w-full mx-2"
-            id="did-list"
-            hx-get=(hx_url)
-            hx-trigger="load delay:5s"
-            hx-swap="outerHTML"
-        {
-            (pagination)
-            @for elem in did_elems { (elem) }
-            (pagination)
-        }
     }
 }
 
@@ -122,3 +214,292 @@ pub fn dlt_cursor_card(cursor: Option<DltCursor>) -> Markup {
         }
     }
 }
+
+pub fn vdr_entry(
+    network: Option<NetworkIdentifier>,
+    init_operation_hash: &str,
+    history: Option<(CanonicalPrismDid, Vec<StorageVersion>)>,
+    ipfs_gateway_url: &str,
+) -> Markup {
+    let content = match history {
+        None => html! {
+            p class="text-neutral-content" { "VDR entry not found" }
+        },
+        Some((did, versions)) => {
+            let did_uri = urls::Resolver::new_uri(Some(DidQuery {
+                did: Some(did.to_string()),
+            }));
+            let api_uri = urls::ApiVdrEntryHistory::new_uri(init_operation_hash.to_string());
+            html! {
+                p { strong { "Owner: " } a class="link link-hover font-mono" href=(did_uri) { (did.to_string()) } }
+                a class="btn btn-xs btn-outline" href=(api_uri) target="_blank" { "History API" }
+                ol class="space-y-2" {
+                    @for version in versions.iter().rev() { (vdr_entry_version(network, version, ipfs_gateway_url)) }
+                }
+            }
+        }
+    };
+    let body = html! {
+        div class="flex flex-row w-screen justify-center" {
+            div class="flex flex-col w-full max-w-4xl" {
+                div class="w-full m-4 space-y-4 wrap-anywhere" {
+                    p class="text-2xl font-bold" { "VDR entry history" }
+                    p class="font-mono text-sm" { (init_operation_hash) }
+                    (content)
+                }
+            }
+        }
+    };
+    components::page_layout("Explorer", network, body)
+}
+
+fn vdr_entry_version(network: Option<NetworkIdentifier>, version: &StorageVersion, ipfs_gateway_url: &str) -> Markup {
+    let operation_hash = HexStr::from(version.operation_hash.as_bytes()).to_string();
+    html! {
+        li class="border p-2 rounded-md bg-base-200 border-gray-700 wrap-anywhere" {
+            strong { "Operation hash: " } span class="font-mono" { (operation_hash) }
+            br;
+            (components::operation_metadata(network, &version.metadata))
+            br;
+            div class="bg-base-300 text-sm p-3 mt-2" {
+                @match version.data.as_ref() {
+                    Some(data) => (components::storage_data(data, ipfs_gateway_url)),
+                    None => "Deactivated",
+                }
+            }
+        }
+    }
+}
+
+pub fn operation_list(
+    network: Option<NetworkIdentifier>,
+    operations: Paginated<(OperationMetadata, SignedPrismOperation)>,
+) -> Markup {
+    // operations are ordered from the latest, so operations of the same block and transaction are consecutive
+    let blocks = operations
+        .items
+        .chunk_by(|a, b| a.0.block_metadata.block_number == b.0.block_metadata.block_number)
+        .map(|block| {
+            block
+                .chunk_by(|a, b| a.0.block_metadata.absn == b.0.block_metadata.absn)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let block_elems = blocks.iter().map(|txs| {
+        let block_metadata = &txs[0][0].0.block_metadata;
+        html! {
+            li class="card bg-base-200 border border-gray-700" {
+                div class="card-body" {
+                    h2 class="card-title" { "Block " (block_metadata.block_number) }
+                    p class="text-sm text-neutral-content" {
+                        "Slot " (block_metadata.slot_number) " · " (block_metadata.cbt.to_rfc3339())
+                    }
+                    @for tx in txs {
+                        div class="border p-2 rounded-md border-gray-700" {
+                            strong { "Transaction seq no: " (tx[0].0.block_metadata.absn) }
+                            @if let Some(tx_hash) = &tx[0].0.block_metadata.tx_hash {
+                                p class="truncate text-sm" { (components::tx_link(network, tx_hash)) }
+                            }
+                            ul {
+                                @for (metadata, signed_operation) in tx.iter().rev() {
+                                    li class="truncate" {
+                                        span class="badge badge-outline mr-2" { (components::operation_type(signed_operation)) }
+                                        span class="mr-2" { "#" (metadata.osn) }
+                                        (operation_link(signed_operation))
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+    let pagination = pagination(&operations, |page| {
+        urls::ExplorerOperationList::new_uri(Some(PageQuery { page: Some(page) }))
+    });
+    let body = html! {
+        div class="flex flex-row w-screen justify-center" {
+            div class="flex flex-col w-full max-w-4xl items-center m-4 space-y-4" {
+                p class="text-2xl font-bold" { "Recent operations" }
+                (pagination)
+                ul class="w-full space-y-2" {
+                    @for elem in block_elems { (elem) }
+                }
+                (pagination)
+            }
+        }
+    };
+    components::page_layout("Explorer", network, body)
+}
+
+pub fn operation(
+    network: Option<NetworkIdentifier>,
+    operation_hash: &str,
+    details: Vec<OperationDetail>,
+    ipfs_gateway_url: &str,
+) -> Markup {
+    let body = html! {
+        div class="flex flex-row w-screen justify-center" {
+            div class="flex flex-col w-full max-w-4xl" {
+                div class="w-full m-4 space-y-4 wrap-anywhere" {
+                    p class="text-2xl font-bold" { "Operation" }
+                    p class="font-mono text-sm" { (operation_hash) }
+                    @if details.is_empty() {
+                        p class="text-neutral-content" { "Operation not found" }
+                    }
+                    @for detail in &details { (operation_detail_card(network, detail, ipfs_gateway_url)) }
+                }
+            }
+        }
+    };
+    components::page_layout("Explorer", network, body)
+}
+
+fn operation_detail_card(
+    network: Option<NetworkIdentifier>,
+    detail: &OperationDetail,
+    ipfs_gateway_url: &str,
+) -> Markup {
+    let metadata = &detail.metadata;
+    let signed_operation = &detail.signed_operation;
+    let did_link = detail.did.as_ref().map(|did| {
+        let uri = urls::Resolver::new_uri(Some(DidQuery {
+            did: Some(did.to_string()),
+        }));
+        html! { a class="link link-hover font-mono" href=(uri) { (did.to_string()) } }
+    });
+    let (status, status_class) = match &detail.outcome {
+        OperationOutcome::NotIndexed => ("Not indexed", "badge-warning"),
+        OperationOutcome::Applied { .. } => ("Applied", "badge-success"),
+        OperationOutcome::Rejected { .. } => ("Rejected", "badge-error"),
+    };
+    html! {
+        div class="card bg-base-200 border border-gray-700" {
+            div class="card-body" {
+                h2 class="card-title" {
+                    (components::operation_type(signed_operation))
+                    span class=(format!("badge {status_class}")) { (status) }
+                }
+                div {
+                    strong { "DID: " }
+                    @match did_link {
+                        Some(link) => (link),
+                        None => "-",
+                    }
+                    br;
+                    (components::operation_metadata(network, metadata))
+                }
+                @match &detail.outcome {
+                    OperationOutcome::NotIndexed => {},
+                    OperationOutcome::Applied { before, after } => {
+                        strong { "State change: " }
+                        (state_change(before.as_ref(), after))
+                    },
+                    OperationOutcome::Rejected { error } => {
+                        strong { "Error: " }
+                        div class="bg-base-300 font-mono text-sm text-neutral-content p-3" {
+                            @for line in Report::new(error).pretty(true).to_string().split("\n") { (line) br; }
+                        }
+                    },
+                }
+                strong { "Operation: " }
+                (components::signed_operation(signed_operation, ipfs_gateway_url))
+            }
+        }
+    }
+}
+
+fn state_change(before: Option<&DidState>, after: &DidState) -> Markup {
+    let mut changes = vec![];
+    if before.is_none() {
+        changes.push(("+", "DID", after.did.to_string()));
+    }
+
+    let before_contexts = before.map(|s| s.context.as_slice()).unwrap_or_default();
+    for ctx in before_contexts.iter().filter(|c| !after.context.contains(c)) {
+        changes.push(("-", "Context", ctx.clone()));
+    }
+    for ctx in after.context.iter().filter(|c| !before_contexts.contains(c)) {
+        changes.push(("+", "Context", ctx.clone()));
+    }
+
+    let before_keys = before.map(|s| s.public_keys.as_slice()).unwrap_or_default();
+    for pk in before_keys
+        .iter()
+        .filter(|pk| !after.public_keys.iter().any(|i| i.id == pk.id))
+    {
+        changes.push(("-", "Public key", pk.id.to_string()));
+    }
+    for pk in after.public_keys.iter() {
+        let usage = format!("{:?}", pk.data.usage());
+        match before_keys.iter().find(|i| i.id == pk.id) {
+            None => changes.push(("+", "Public key", format!("{} ({usage})", pk.id))),
+            Some(prev) if prev != pk => changes.push(("~", "Public key", format!("{} ({usage})", pk.id))),
+            Some(_) => (),
+        }
+    }
+
+    let before_services = before.map(|s| s.services.as_slice()).unwrap_or_default();
+    for svc in before_services
+        .iter()
+        .filter(|svc| !after.services.iter().any(|i| i.id == svc.id))
+    {
+        changes.push(("-", "Service", svc.id.to_string()));
+    }
+    for svc in after.services.iter() {
+        match before_services.iter().find(|i| i.id == svc.id) {
+            None => changes.push(("+", "Service", svc.id.to_string())),
+            Some(prev) if prev != svc => changes.push(("~", "Service", svc.id.to_string())),
+            Some(_) => (),
+        }
+    }
+
+    let before_storage = before.map(|s| s.storage.as_slice()).unwrap_or_default();
+    for s in before_storage.iter().filter(|s| {
+        !after
+            .storage
+            .iter()
+            .any(|i| i.init_operation_hash == s.init_operation_hash)
+    }) {
+        changes.push((
+            "-",
+            "VDR entry",
+            HexStr::from(s.init_operation_hash.as_bytes()).to_string(),
+        ));
+    }
+    for s in after.storage.iter() {
+        let init_hash_hex = HexStr::from(s.init_operation_hash.as_bytes()).to_string();
+        match before_storage
+            .iter()
+            .find(|i| i.init_operation_hash == s.init_operation_hash)
+        {
+            None => changes.push(("+", "VDR entry", init_hash_hex)),
+            Some(prev) if prev != s => changes.push(("~", "VDR entry", init_hash_hex)),
+            Some(_) => (),
+        }
+    }
+
+    html! {
+        ul class="bg-base-300 font-mono text-sm text-neutral-content p-3" {
+            @if changes.is_empty() {
+                li { "No change" }
+            }
+            @for (kind, item, value) in changes {
+                li { (kind) " " (item) ": " (value) }
+            }
+        }
+    }
+}
+
+fn operation_link(signed_operation: &SignedPrismOperation) -> Markup {
+    match signed_operation.operation_hash() {
+        Some(hash) => {
+            let hash_hex = HexStr::from(hash.as_bytes()).to_string();
+            let uri = urls::ExplorerOperation::new_uri(hash_hex.clone());
+            html! { a class="link link-hover font-mono text-sm" href=(uri) { (hash_hex) } }
+        }
+        None => html! { span class="text-neutral-content" { "-" } },
+    }
+}
@@ -16,11 +16,12 @@ pub fn router() -> Router<AppState> {
 
 async fn index(Query(query): Query<DidQuery>, State(state): State<AppState>) -> Markup {
     let network = state.dlt_source.map(|i| i.network);
+    let ipfs_gateway_url = state.ipfs_gateway_url;
     match query.did.as_ref() {
         None => views::index(network),
         Some(did_str) => {
             let (state, debug) = state.did_service.resolve_did(did_str).await;
-            views::resolve(network, did_str, state, debug)
+            views::resolve(network, did_str, state, debug, &ipfs_gateway_url)
         }
     }
 }
@@ -23,17 +23,18 @@ pub fn resolve(
     did_str: &str,
     did_state: Result<(PrismDid, DidState), ResolutionError>,
     did_debug: Vec<(OperationMetadata, SignedPrismOperation, Option<ProcessError>)>,
+    ipfs_gateway_url: &str,
 ) -> Markup {
     let resolution_body = match did_state.as_ref() {
         Err(e) => resolution_error_body(e),
-        Ok((did, state)) => did_document_body(&did.to_did(), state),
+        Ok((did, state)) => did_document_body(&did.to_did(), state, ipfs_gateway_url),
     };
     let body = html! {
         (search_box(Some(did_str)))
         div class="flex flex-row w-screen justify-center" {
             div class="flex flex-col w-full max-w-4xl items-center" {
                 (resolution_body)
-                (did_debug_body(did_debug))
+                (did_debug_body(network, did_debug, ipfs_gateway_url))
             }
         }
     };
@@ -84,7 +85,7 @@ fn resolution_error_body(error: &ResolutionError) -> Markup {
     }
 }
 
-fn did_document_body(did: &Did, state: &DidState) -> Markup {
+fn did_document_body(did: &Did, state: &DidState, ipfs_gateway_url: &str) -> Markup {
     let did_doc = state.to_did_document(did);
     let contexts = state.context.as_slice();
     let public_keys = state.public_keys.as_slice();
@@ -98,7 +99,7 @@ fn did_document_body(did: &Did, state: &DidState) -> Markup {
                 (context_card(contexts))
                 (public_key_card(public_keys))
                 (service_card(&did_doc))
-                (storage_card(&storages))
+                (storage_card(&storages, ipfs_gateway_url))
             }
         }
     }
@@ -207,7 +208,7 @@ fn service_card(did_doc: &DidDocument) -> Markup {
     }
 }
 
-fn storage_card(storages: &[StorageState]) -> Markup {
+fn storage_card(storages: &[StorageState], ipfs_gateway_url: &str) -> Markup {
     let mut sorted_storages = storages.to_vec();
     sorted_storages.sort_by_key(|s| s.init_operation_hash.to_vec());
 
@@ -216,17 +217,16 @@ fn storage_card(storages: &[StorageState]) -> Markup {
         .map(|s| {
             let init_hash_hex = HexStr::from(s.init_operation_hash.as_bytes()).to_string();
             let last_hash_hex = HexStr::from(s.last_operation_hash.as_bytes()).to_string();
-            let data = format!("{:?}", s.data);
+            let history_uri = urls::ExplorerVdrEntry::new_uri(init_hash_hex.clone());
             html! {
                 li class="border p-2 rounded-md border-gray-700 wrap-anywhere" {
+                    a class="btn btn-xs btn-outline float-right" href=(history_uri) { "History" }
                     strong { "Init operation hash: " } (init_hash_hex)
                     br;
                     strong { "Last operation hash: " } (last_hash_hex)
                     br;
-                    strong { "Data: " }
-                    br;
-                    div class="bg-base-300 font-mono text-sm text-neutral-content p-3" {
-                        (data)
+                    div class="bg-base-300 text-sm p-3 mt-2" {
+                        (components::storage_data(&s.data, ipfs_gateway_url))
                     }
                 }
             }
@@ -248,12 +248,17 @@ fn storage_card(storages: &[StorageState]) -> Markup {
     }
 }
 
-fn did_debug_body(did_debug: Vec<(OperationMetadata, SignedPrismOperation, Option<ProcessError>)>) -> Markup {
+fn did_debug_body(
+    network: Option<NetworkIdentifier>,
+    did_debug: Vec<(OperationMetadata, SignedPrismOperation, Option<ProcessError>)>,
+    ipfs_gateway_url: &str,
+) -> Markup {
     let op_elems = did_debug
         .iter()
         .map(|(metadata, signed_op, error)| {
-            let block_time = metadata.block_metadata.cbt.to_rfc3339();
-            let operation_payload = format!("{signed_op:?}");
+            let operation_uri = signed_op
+                .operation_hash()
+                .map(|h| urls::ExplorerOperation::new_uri(HexStr::from(h.as_bytes()).to_string()));
             let error_lines = error
                 .as_ref()
                 .map(|e| Report::new(e).pretty(true).to_string())
@@ -263,21 +268,14 @@ fn did_debug_body(did_debug: Vec<(OperationMetadata, SignedPrismOperation, Optio
                 .collect::<Vec<_>>();
             html! {
                 li class="border p-2 rounded-md bg-base-200 border-gray-700 wrap-anywhere" {
-                    strong { "Block time: " } (block_time)
-                    br;
-                    strong { "Slot no: " } (metadata.block_metadata.slot_number)
-                    br;
-                    strong { "Block no: " } (metadata.block_metadata.block_number)
-                    br;
-                    strong { "Block seq no: " } (metadata.block_metadata.absn)
-                    br;
-                    strong { "Operation seq no: " } (metadata.osn)
+                    @if let Some(uri) = operation_uri {
+                        a class="btn btn-xs btn-outline float-right" href=(uri) { "Details" }
+                    }
+                    (components::operation_metadata(network, metadata))
                     br;
-                    strong { "Operation payload: " }
+                    strong { "Operation: " }
                     br;
-                    div class="bg-base-300 font-mono text-sm text-neutral-content p-3" {
-                        (operation_payload)
-                    }
+                    (components::signed_operation(signed_op, ipfs_gateway_url))
                     strong { "Error: " }
                     br;
                     div class="bg-base-300 font-mono text-sm text-neutral-content p-3" {
@@ -29,7 +29,7 @@ pub fn router(assets_dir: &Path, mode: RunMode) -> Router<AppState> {
                 get(Redirect::temporary(&urls::Swagger::new_uri())),
             )
             .merge(api_router),
-        RunMode::Indexer | RunMode::Standalone => Router::new()
+        RunMode::Indexer | RunMode::Standalone | RunMode::Resolver => Router::new()
             .route(
                 urls::Home::AXUM_PATH,
                 get(Redirect::temporary(&urls::Resolver::new_uri(None))),
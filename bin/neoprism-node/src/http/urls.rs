@@ -1,6 +1,6 @@
 use lazybe::macros::typed_uri;
 
-use crate::http::ui_explorer::models::PageQuery;
+use crate::http::ui_explorer::models::{PageQuery, SearchQuery};
 use crate::http::ui_resolver::models::DidQuery;
 
 // assets
@@ -18,6 +18,10 @@ typed_uri!(Resolver, "resolver" ? Option<DidQuery>);
 typed_uri!(Explorer, "explorer" ? Option<PageQuery>);
 typed_uri!(ExplorerDltCursor, "explorer" / "dlt-cursor");
 typed_uri!(ExplorerDidList, "explorer" / "did-list" ? Option<PageQuery>);
+typed_uri!(ExplorerSearch, "explorer" / "search" ? Option<SearchQuery>);
+typed_uri!(ExplorerOperationList, "explorer" / "operations" ? Option<PageQuery>);
+typed_uri!(ExplorerOperation, "explorer" / "operations" / (operation_hash: String));
+typed_uri!(ExplorerVdrEntry, "explorer" / "vdr-entries" / (init_operation_hash: String));
 
 // API system
 typed_uri!(ApiHealth, "api" / "_system" / "health");
@@ -29,4 +33,15 @@ typed_uri!(ApiSignedOpSubmissions, "api" / "signed-operation-submissions");
 // API indexer
 typed_uri!(ApiDid, "api" / "dids" / (did: String));
 typed_uri!(ApiDidData, "api" / "did-data" / (did: String));
+typed_uri!(ApiDidOperations, "api" / "did-operations" / (did: String));
 typed_uri!(ApiIndexerStats, "api" / "indexer-stats");
+typed_uri!(ApiVerifyJws, "api" / "verify-jws");
+typed_uri!(ApiSearch, "api" / "search");
+typed_uri!(ApiDidReferences, "api" / "did-references");
+
+// API VDR
+typed_uri!(ApiVdrEntry, "api" / "vdr-entries" / (init_operation_hash: String));
+typed_uri!(ApiVdrEntryData, "api" / "vdr-entries" / (init_operation_hash: String) / "data");
+typed_uri!(ApiVdrEntryHistory, "api" / "vdr-entries" / (init_operation_hash: String) / "history");
+typed_uri!(ApiVdrStatusList, "api" / "vdr-entries" / (init_operation_hash: String) / "status-list");
+typed_uri!(ApiDidVdrEntries, "api" / "did-vdr-entries" / (did: String));
@@ -1,47 +1,58 @@
 #![allow(non_snake_case)]
 #![feature(error_reporter)]
 
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use app::service::DidService;
+use app::service::cache::ResolutionCache;
 use clap::Parser;
 use cli::Cli;
+use identus_did_prism::did::CanonicalPrismDid;
 use identus_did_prism::dlt::{DltCursor, NetworkIdentifier};
 use identus_did_prism_indexer::dlt::dbsync::DbSyncSource;
 use identus_did_prism_indexer::dlt::oura::OuraN2NSource;
 use identus_did_prism_submitter::DltSink;
 use identus_did_prism_submitter::dlt::cardano_wallet::CardanoWalletSink;
-use lazybe::db::postgres::PostgresDbCtx;
-use lazybe::router::RouteConfig;
-use node_storage::PostgresDb;
-use sqlx::{PgPool, Postgres};
+use node_storage::NodeDb;
+use tokio::sync::broadcast;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 
 use crate::app::worker::{DltIndexWorker, DltSyncWorker};
-use crate::cli::{DbArgs, DltSinkArgs, DltSourceArgs, IndexerArgs, ServerArgs, StandaloneArgs, SubmitterArgs};
+use crate::cli::{
+    DbArgs, DltSinkArgs, DltSourceArgs, IndexerArgs, ResolverArgs, ServerArgs, StandaloneArgs, SubmitterArgs,
+};
 
 mod app;
 mod cli;
+mod grpc;
 mod http;
+#[cfg(test)]
+mod test_utils;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
+const INDEXED_DID_CHANNEL_SIZE: usize = 1024;
 
 #[derive(Clone, Copy)]
 enum RunMode {
     Indexer,
     Submitter,
     Standalone,
+    Resolver,
 }
 
 #[derive(Clone)]
 struct AppState {
-    pg_pool: PgPool,
     did_service: DidService,
     dlt_source: Option<DltSourceState>,
     dlt_sink: Option<Arc<dyn DltSink>>,
     run_mode: RunMode,
+    /// The base URL of the node HTTP server without trailing slash
+    external_url: String,
+    ipfs_gateway_url: String,
 }
 
 #[derive(Clone)]
@@ -50,68 +61,101 @@ struct DltSourceState {
     network: NetworkIdentifier,
 }
 
-impl RouteConfig for AppState {
-    type Ctx = PostgresDbCtx;
-    type Db = Postgres;
-
-    fn db_ctx(&self) -> (Self::Ctx, PgPool) {
-        (PostgresDbCtx, self.pg_pool.clone())
-    }
-}
-
 pub async fn run_command() -> anyhow::Result<()> {
     let cli = Cli::parse();
     match cli.command {
         cli::Command::Indexer(args) => run_indexer_command(args).await?,
         cli::Command::Submitter(args) => run_submitter_command(args).await?,
         cli::Command::Standalone(args) => run_standalone_command(args).await?,
+        cli::Command::Resolver(args) => run_resolver_command(args).await?,
     };
     Ok(())
 }
 
 async fn run_indexer_command(args: IndexerArgs) -> anyhow::Result<()> {
     let db = init_database(&args.db).await;
+    let read_db = init_read_database(&args.db, &db).await;
     let network = args.dlt_source.cardano_network.clone().into();
-    let cursor_rx = init_dlt_source(&args.dlt_source, &network, &db).await;
+    let (indexed_did_tx, _) = broadcast::channel(INDEXED_DID_CHANNEL_SIZE);
+    let cursor_rx = init_dlt_source(&args.dlt_source, &network, &db, &indexed_did_tx).await;
+    let did_service = init_did_service(
+        &args.db,
+        &args.dlt_source,
+        &read_db,
+        cursor_rx.as_ref().map(|_| &indexed_did_tx),
+    );
     let app_state = AppState {
-        pg_pool: db.pool.clone(),
         run_mode: RunMode::Indexer,
-        did_service: DidService::new(&db),
+        did_service,
         dlt_source: cursor_rx.map(|cursor_rx| DltSourceState { cursor_rx, network }),
         dlt_sink: None,
+        external_url: args.server.external_url(),
+        ipfs_gateway_url: args.server.ipfs_gateway_url.clone(),
     };
     run_server(app_state, &args.server).await
 }
 
 async fn run_submitter_command(args: SubmitterArgs) -> anyhow::Result<()> {
     let db = init_database(&args.db).await;
+    let read_db = init_read_database(&args.db, &db).await;
     let dlt_sink = init_dlt_sink(&args.dlt_sink);
     let app_state = AppState {
-        pg_pool: db.pool.clone(),
         run_mode: RunMode::Submitter,
-        did_service: DidService::new(&db),
+        did_service: DidService::new(&read_db),
         dlt_source: None,
         dlt_sink: Some(dlt_sink),
+        external_url: args.server.external_url(),
+        ipfs_gateway_url: args.server.ipfs_gateway_url.clone(),
     };
     run_server(app_state, &args.server).await
 }
 
 async fn run_standalone_command(args: StandaloneArgs) -> anyhow::Result<()> {
     let db = init_database(&args.db).await;
+    let read_db = init_read_database(&args.db, &db).await;
     let network = args.dlt_source.cardano_network.clone().into();
-    let cursor_rx = init_dlt_source(&args.dlt_source, &network, &db).await;
+    let (indexed_did_tx, _) = broadcast::channel(INDEXED_DID_CHANNEL_SIZE);
+    let cursor_rx = init_dlt_source(&args.dlt_source, &network, &db, &indexed_did_tx).await;
+    let did_service = init_did_service(
+        &args.db,
+        &args.dlt_source,
+        &read_db,
+        cursor_rx.as_ref().map(|_| &indexed_did_tx),
+    );
     let dlt_sink = init_dlt_sink(&args.dlt_sink);
     let app_state = AppState {
-        pg_pool: db.pool.clone(),
         run_mode: RunMode::Standalone,
-        did_service: DidService::new(&db),
+        did_service,
         dlt_source: cursor_rx.map(|cursor_rx| DltSourceState { cursor_rx, network }),
         dlt_sink: Some(dlt_sink),
+        external_url: args.server.external_url(),
+        ipfs_gateway_url: args.server.ipfs_gateway_url.clone(),
+    };
+    run_server(app_state, &args.server).await
+}
+
+async fn run_resolver_command(args: ResolverArgs) -> anyhow::Result<()> {
+    tracing::info!("Connecting to database in read-only resolver mode");
+    let db = NodeDb::connect_read_only(&args.db_url)
+        .await
+        .expect("Unable to connect to database");
+    let app_state = AppState {
+        run_mode: RunMode::Resolver,
+        did_service: DidService::new(&db),
+        dlt_source: None,
+        dlt_sink: None,
+        external_url: args.server.external_url(),
+        ipfs_gateway_url: args.server.ipfs_gateway_url.clone(),
     };
     run_server(app_state, &args.server).await
 }
 
 async fn run_server(app_state: AppState, server_args: &ServerArgs) -> anyhow::Result<()> {
+    let grpc_state = app_state.clone();
+    let grpc_addr = server_args
+        .grpc_port
+        .map(|port| SocketAddr::from((server_args.address, port)));
+
     let layer = ServiceBuilder::new()
         .layer(TraceLayer::new_for_http())
         .option_layer(Some(CorsLayer::permissive()).filter(|_| server_args.cors_enabled));
@@ -121,12 +165,19 @@ async fn run_server(app_state: AppState, server_args: &ServerArgs) -> anyhow::Re
     let bind_addr = format!("{}:{}", server_args.address, server_args.port);
     let listener = tokio::net::TcpListener::bind(&bind_addr).await?;
     tracing::info!("Server is listening on {}", bind_addr);
-    axum::serve(listener, router).await?;
+    let http_server = async { axum::serve(listener, router).await.map_err(anyhow::Error::from) };
+    let grpc_server = async move {
+        match grpc_addr {
+            Some(addr) => grpc::serve(grpc_state, addr).await,
+            None => Ok(()),
+        }
+    };
+    tokio::try_join!(http_server, grpc_server)?;
     Ok(())
 }
 
-async fn init_database(db_args: &DbArgs) -> PostgresDb {
-    let db = PostgresDb::connect(&db_args.db_url)
+async fn init_database(db_args: &DbArgs) -> NodeDb {
+    let db = NodeDb::connect(&db_args.db_url)
         .await
         .expect("Unable to connect to database");
 
@@ -141,10 +192,46 @@ async fn init_database(db_args: &DbArgs) -> PostgresDb {
     db
 }
 
+/// Connect to the read replica if configured, otherwise reads go to the primary database.
+/// Migrations are never applied to the read replica.
+async fn init_read_database(db_args: &DbArgs, primary_db: &NodeDb) -> NodeDb {
+    match &db_args.db_read_url {
+        Some(db_read_url) => {
+            tracing::info!("Using a separate database for read queries");
+            NodeDb::connect_read_only(db_read_url)
+                .await
+                .expect("Unable to connect to read database")
+        }
+        None => primary_db.clone(),
+    }
+}
+
+fn init_did_service(
+    db_args: &DbArgs,
+    dlt_args: &DltSourceArgs,
+    read_db: &NodeDb,
+    indexed_did_tx: Option<&broadcast::Sender<CanonicalPrismDid>>,
+) -> DidService {
+    let did_service = DidService::new(read_db);
+    match (NonZeroUsize::new(dlt_args.resolution_cache_size), indexed_did_tx) {
+        // the replica may lag behind the indexer, so an invalidation cannot guarantee freshness
+        (Some(_), Some(_)) if db_args.db_read_url.is_some() => {
+            tracing::info!("Resolution cache is disabled when a read database is configured");
+            did_service
+        }
+        (Some(capacity), Some(tx)) => {
+            tracing::info!("Resolution cache is enabled with capacity {}", capacity);
+            did_service.with_cache(ResolutionCache::new(capacity, tx.subscribe()))
+        }
+        _ => did_service,
+    }
+}
+
 async fn init_dlt_source(
     dlt_args: &DltSourceArgs,
     network: &NetworkIdentifier,
-    db: &PostgresDb,
+    db: &NodeDb,
+    indexed_did_tx: &broadcast::Sender<CanonicalPrismDid>,
 ) -> Option<tokio::sync::watch::Receiver<Option<DltCursor>>> {
     if let Some(address) = &dlt_args.cardano_relay_addr {
         tracing::info!(
@@ -162,7 +249,7 @@ async fn init_dlt_source(
         .expect("Failed to create DLT source");
 
         let sync_worker = DltSyncWorker::new(db.clone(), source);
-        let index_worker = DltIndexWorker::new(db.clone(), dlt_args.index_interval);
+        let index_worker = DltIndexWorker::new(db.clone(), dlt_args.index_interval, indexed_did_tx.clone());
         let cursor_rx = sync_worker.sync_cursor();
         tokio::spawn(sync_worker.run());
         tokio::spawn(index_worker.run());
@@ -179,7 +266,7 @@ async fn init_dlt_source(
         .expect("Failed to create DLT source");
 
         let sync_worker = DltSyncWorker::new(db.clone(), source);
-        let index_worker = DltIndexWorker::new(db.clone(), dlt_args.index_interval);
+        let index_worker = DltIndexWorker::new(db.clone(), dlt_args.index_interval, indexed_did_tx.clone());
         let cursor_rx = sync_worker.sync_cursor();
         tokio::spawn(sync_worker.run());
         tokio::spawn(index_worker.run());
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use identus_did_prism_submitter::DltSink;
+use node_storage::NodeDb;
+
+use crate::app::service::DidService;
+use crate::{AppState, RunMode};
+
+/// Create an application state backed by a fresh in-memory SQLite database.
+pub async fn new_app_state(run_mode: RunMode, dlt_sink: Option<Arc<dyn DltSink>>) -> (NodeDb, AppState) {
+    let db = NodeDb::connect("sqlite::memory:").await.unwrap();
+    db.migrate().await.unwrap();
+    let state = AppState {
+        did_service: DidService::new(&db),
+        dlt_source: None,
+        dlt_sink,
+        run_mode,
+        external_url: "http://localhost:8080".to_string(),
+        ipfs_gateway_url: "https://ipfs.io/ipfs".to_string(),
+    };
+    (db, state)
+}
@@ -1,10 +1,15 @@
-use super::{EncodeArray, EncodeVec, Error, Verifiable};
+use super::{EncodeArray, EncodeVec, Error, Signer, Verifiable};
 use crate::base64::Base64UrlStrNoPad;
-use crate::jwk::{EncodeJwk, Jwk};
+use crate::jwk::{DecodeJwk, EncodeJwk, Jwk};
+use crate::multibase::MultibaseStr;
+use crate::multikey::{EncodeMultikey, Multicodec, encode_multikey};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Ed25519PublicKey(ed25519_dalek::VerifyingKey);
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ed25519PrivateKey(ed25519_dalek::SigningKey);
+
 impl Ed25519PublicKey {
     pub fn from_slice(slice: &[u8]) -> Result<Ed25519PublicKey, Error> {
         let Some((key, _)) = slice.split_first_chunk::<32>() else {
@@ -19,6 +24,24 @@ impl Ed25519PublicKey {
     }
 }
 
+impl Ed25519PrivateKey {
+    pub fn from_slice(slice: &[u8]) -> Result<Ed25519PrivateKey, Error> {
+        let Some((key, _)) = slice.split_first_chunk::<32>() else {
+            Err(Error::InvalidKeySize {
+                expected: 32,
+                actual: slice.len(),
+                key_type: std::any::type_name::<Ed25519PrivateKey>(),
+            })?
+        };
+        let key = ed25519_dalek::SigningKey::from_bytes(key);
+        Ok(Ed25519PrivateKey(key))
+    }
+
+    pub fn to_public_key(&self) -> Ed25519PublicKey {
+        Ed25519PublicKey(self.0.verifying_key())
+    }
+}
+
 impl EncodeVec for Ed25519PublicKey {
     fn encode_vec(&self) -> Vec<u8> {
         self.0.to_bytes().to_vec()
@@ -40,6 +63,12 @@ impl Verifiable for Ed25519PublicKey {
     }
 }
 
+impl Signer for Ed25519PrivateKey {
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        ed25519_dalek::Signer::sign(&self.0, message).to_vec()
+    }
+}
+
 impl EncodeJwk for Ed25519PublicKey {
     fn encode_jwk(&self) -> Jwk {
         let x = self.encode_array();
@@ -48,6 +77,22 @@ impl EncodeJwk for Ed25519PublicKey {
             crv: "Ed25519".to_string(),
             x: Some(Base64UrlStrNoPad::from(x)),
             y: None,
+            kid: None,
+            alg: None,
+            r#use: None,
         }
     }
 }
+
+impl DecodeJwk for Ed25519PublicKey {
+    fn decode_jwk(jwk: &Jwk) -> Result<Self, crate::jwk::Error> {
+        jwk.ensure_key_type("OKP", "Ed25519")?;
+        Self::from_slice(&jwk.x_bytes()?).map_err(|e| crate::jwk::Error::InvalidKey { source: e })
+    }
+}
+
+impl EncodeMultikey for Ed25519PublicKey {
+    fn encode_multikey(&self) -> MultibaseStr {
+        encode_multikey(Multicodec::Ed25519Pub, &self.encode_vec())
+    }
+}
@@ -0,0 +1,126 @@
+//! Hierarchical deterministic key derivation using [BIP32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki)
+//! and [BIP39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki).
+//!
+//! Keys of a `did:prism` are derived using the same derivation path as the Identus SDKs
+//! `m/<wallet-purpose>'/<did-method>'/<did-index>'/<key-purpose>'/<key-index>'`.
+//!
+//! # Example
+//!
+//! ```
+//! use identus_apollo::crypto::hdkey::{PrismDerivationPath, Seed};
+//!
+//! let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+//! let seed = Seed::from_mnemonic(mnemonic, "").unwrap();
+//! let master_key_path = PrismDerivationPath::new(0, 1, 0);
+//! assert_eq!(master_key_path.to_string(), "m/29'/29'/0'/1'/0'");
+//!
+//! let master_sk = seed.derive_secp256k1(&master_key_path).unwrap();
+//! assert_eq!(master_sk, seed.derive_secp256k1_from_str("m/29'/29'/0'/1'/0'").unwrap());
+//! ```
+
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use super::Error;
+use super::secp256k1::Secp256k1PrivateKey;
+
+/// Wallet purpose used by the Identus SDKs.
+pub const PRISM_WALLET_PURPOSE: u32 = 29;
+/// DID method index of `did:prism` used by the Identus SDKs.
+pub const PRISM_DID_METHOD: u32 = 29;
+
+/// BIP32 seed derived from a BIP39 mnemonic or provided directly.
+///
+/// A valid seed is between 16 and 64 bytes, otherwise key derivation fails.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Seed(Vec<u8>);
+
+impl Debug for Seed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Seed(..)")
+    }
+}
+
+impl Seed {
+    pub fn from_slice(slice: &[u8]) -> Self {
+        Self(slice.to_vec())
+    }
+
+    /// Parse the English BIP39 mnemonic and derive a seed using the given passphrase.
+    pub fn from_mnemonic(mnemonic: &str, passphrase: &str) -> Result<Self, Error> {
+        let mnemonic = bip39::Mnemonic::parse_in(bip39::Language::English, mnemonic)?;
+        Ok(Self(mnemonic.to_seed(passphrase).to_vec()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn derive_secp256k1(&self, path: &PrismDerivationPath) -> Result<Secp256k1PrivateKey, Error> {
+        self.derive_secp256k1_from_path(&path.bip32_path()?)
+    }
+
+    /// Derive a key using an arbitrary derivation path such as `m/0'/1/2'`.
+    pub fn derive_secp256k1_from_str(&self, path: &str) -> Result<Secp256k1PrivateKey, Error> {
+        let path = bip32::DerivationPath::from_str(path)?;
+        self.derive_secp256k1_from_path(&path)
+    }
+
+    fn derive_secp256k1_from_path(&self, path: &bip32::DerivationPath) -> Result<Secp256k1PrivateKey, Error> {
+        let xprv = bip32::XPrv::derive_from_path(&self.0, path)?;
+        Secp256k1PrivateKey::from_slice(&xprv.private_key().to_bytes())
+    }
+}
+
+/// Derivation path of a `did:prism` key where every level is hardened.
+///
+/// The `key_purpose` follows the numbering of the PRISM key usage
+/// (e.g. `1` for master key, `2` for issuing key, `4` for authentication key and `8` for VDR key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrismDerivationPath {
+    pub wallet_purpose: u32,
+    pub did_method: u32,
+    pub did_index: u32,
+    pub key_purpose: u32,
+    pub key_index: u32,
+}
+
+impl PrismDerivationPath {
+    pub fn new(did_index: u32, key_purpose: u32, key_index: u32) -> Self {
+        Self {
+            wallet_purpose: PRISM_WALLET_PURPOSE,
+            did_method: PRISM_DID_METHOD,
+            did_index,
+            key_purpose,
+            key_index,
+        }
+    }
+
+    fn bip32_path(&self) -> Result<bip32::DerivationPath, Error> {
+        let mut path = bip32::DerivationPath::default();
+        for index in self.indices() {
+            path.push(bip32::ChildNumber::new(index, true)?);
+        }
+        Ok(path)
+    }
+
+    fn indices(&self) -> [u32; 5] {
+        [
+            self.wallet_purpose,
+            self.did_method,
+            self.did_index,
+            self.key_purpose,
+            self.key_index,
+        ]
+    }
+}
+
+impl Display for PrismDerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "m")?;
+        for index in self.indices() {
+            write!(f, "/{index}'")?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,13 @@
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
+#[cfg(feature = "hdkey")]
+pub mod hdkey;
+#[cfg(any(feature = "p256", feature = "p384"))]
+mod nist;
+#[cfg(feature = "p256")]
+pub mod p256;
+#[cfg(feature = "p384")]
+pub mod p384;
 #[cfg(feature = "secp256k1")]
 pub mod secp256k1;
 #[cfg(feature = "x25519")]
@@ -21,6 +29,22 @@ pub enum Error {
     #[from]
     #[display("unable to parse secp256k1 key")]
     Secp256k1KeyParsing { source: ::k256::elliptic_curve::Error },
+    #[cfg(feature = "p256")]
+    #[from(skip)]
+    #[display("unable to parse P-256 key")]
+    P256KeyParsing { source: ::p256::elliptic_curve::Error },
+    #[cfg(feature = "p384")]
+    #[from(skip)]
+    #[display("unable to parse P-384 key")]
+    P384KeyParsing { source: ::p384::elliptic_curve::Error },
+    #[cfg(feature = "hdkey")]
+    #[from]
+    #[display("unable to parse mnemonic")]
+    Mnemonic { source: bip39::Error },
+    #[cfg(feature = "hdkey")]
+    #[from]
+    #[display("unable to derive HD key")]
+    HdKeyDerivation { source: bip32::Error },
 }
 
 pub trait EncodeVec {
@@ -34,3 +58,7 @@ pub trait EncodeArray<const N: usize> {
 pub trait Verifiable {
     fn verify(&self, message: &[u8], signature: &[u8]) -> bool;
 }
+
+pub trait Signer {
+    fn sign(&self, message: &[u8]) -> Vec<u8>;
+}
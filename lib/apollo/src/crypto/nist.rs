@@ -0,0 +1,139 @@
+/// Define the public and private key types of a NIST curve from its RustCrypto crate.
+///
+/// P-256 and P-384 only differ in their crate, names and encoded sizes,
+/// where `$field_size` is the size of a single coordinate in bytes.
+macro_rules! nist_curve_keys {
+    (
+        curve: $curve:ident,
+        crv: $crv:literal,
+        public_key: $public_key:ident,
+        private_key: $private_key:ident,
+        parsing_error: $parsing_error:ident,
+        multicodec: $multicodec:ident,
+        field_size: $field_size:literal $(,)?
+    ) => {
+        use ::$curve::ecdsa::signature::{Signer as _, Verifier};
+        use ::$curve::elliptic_curve::sec1::ToEncodedPoint;
+        use $crate::base64::Base64UrlStrNoPad;
+        use $crate::crypto::{EncodeArray, EncodeVec, Error, Signer, Verifiable};
+        use $crate::jwk::{DecodeJwk, EncodeJwk, Jwk};
+        use $crate::multibase::MultibaseStr;
+        use $crate::multikey::{EncodeMultikey, Multicodec, encode_multikey};
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $public_key(::$curve::PublicKey);
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $private_key(::$curve::SecretKey);
+
+        impl EncodeVec for $public_key {
+            fn encode_vec(&self) -> Vec<u8> {
+                self.encode_compressed().into()
+            }
+        }
+
+        impl EncodeArray<{ 1 + $field_size }> for $public_key {
+            fn encode_array(&self) -> [u8; 1 + $field_size] {
+                self.encode_compressed()
+            }
+        }
+
+        impl EncodeArray<{ 1 + 2 * $field_size }> for $public_key {
+            fn encode_array(&self) -> [u8; 1 + 2 * $field_size] {
+                self.encode_uncompressed()
+            }
+        }
+
+        impl Verifiable for $public_key {
+            /// Verify the signature in either the fixed-size `r || s` format used by JWS
+            /// or the DER format.
+            fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+                let verifying_key: ::$curve::ecdsa::VerifyingKey = self.0.into();
+                let signature = ::$curve::ecdsa::Signature::from_slice(signature)
+                    .or_else(|_| ::$curve::ecdsa::Signature::from_der(signature));
+                let Ok(signature) = signature else {
+                    return false;
+                };
+                verifying_key.verify(message, &signature).is_ok()
+            }
+        }
+
+        impl $public_key {
+            pub fn from_slice(slice: &[u8]) -> Result<$public_key, Error> {
+                let pk =
+                    ::$curve::PublicKey::from_sec1_bytes(slice).map_err(|e| Error::$parsing_error { source: e })?;
+                Ok($public_key(pk))
+            }
+
+            pub fn encode_uncompressed(&self) -> [u8; 1 + 2 * $field_size] {
+                let bytes = self.0.to_encoded_point(false);
+                let Some((chunk, _)) = bytes.as_bytes().split_first_chunk::<{ 1 + 2 * $field_size }>() else {
+                    unreachable!("EncodedPoint::as_bytes() must return a single chunk");
+                };
+                chunk.to_owned()
+            }
+
+            pub fn encode_compressed(&self) -> [u8; 1 + $field_size] {
+                let bytes = self.0.to_encoded_point(true);
+                let Some((chunk, _)) = bytes.as_bytes().split_first_chunk::<{ 1 + $field_size }>() else {
+                    unreachable!("EncodedPoint::as_bytes() must return a single chunk");
+                };
+                chunk.to_owned()
+            }
+        }
+
+        impl $private_key {
+            pub fn from_slice(slice: &[u8]) -> Result<Self, Error> {
+                let sk = ::$curve::SecretKey::from_slice(slice).map_err(|e| Error::$parsing_error { source: e })?;
+                Ok(Self(sk))
+            }
+
+            pub fn to_public_key(&self) -> $public_key {
+                $public_key(self.0.public_key())
+            }
+        }
+
+        impl Signer for $private_key {
+            /// Sign the message and return the DER-encoded signature.
+            fn sign(&self, message: &[u8]) -> Vec<u8> {
+                let signing_key = ::$curve::ecdsa::SigningKey::from(&self.0);
+                let signature: ::$curve::ecdsa::Signature = signing_key.sign(message);
+                signature.to_der().to_bytes().to_vec()
+            }
+        }
+
+        impl EncodeJwk for $public_key {
+            fn encode_jwk(&self) -> Jwk {
+                let uncompressed = self.encode_uncompressed();
+                let (x, y) = uncompressed[1..].split_at($field_size);
+                Jwk {
+                    kty: "EC".to_string(),
+                    crv: $crv.to_string(),
+                    x: Some(Base64UrlStrNoPad::from(x)),
+                    y: Some(Base64UrlStrNoPad::from(y)),
+                    kid: None,
+                    alg: None,
+                    r#use: None,
+                }
+            }
+        }
+
+        impl DecodeJwk for $public_key {
+            fn decode_jwk(jwk: &Jwk) -> Result<Self, $crate::jwk::Error> {
+                jwk.ensure_key_type("EC", $crv)?;
+                let mut bytes = vec![0x04];
+                bytes.extend(jwk.x_bytes()?);
+                bytes.extend(jwk.y_bytes()?);
+                Self::from_slice(&bytes).map_err(|e| $crate::jwk::Error::InvalidKey { source: e })
+            }
+        }
+
+        impl EncodeMultikey for $public_key {
+            fn encode_multikey(&self) -> MultibaseStr {
+                encode_multikey(Multicodec::$multicodec, &self.encode_compressed())
+            }
+        }
+    };
+}
+
+pub(crate) use nist_curve_keys;
@@ -0,0 +1,9 @@
+super::nist::nist_curve_keys! {
+    curve: p256,
+    crv: "P-256",
+    public_key: P256PublicKey,
+    private_key: P256PrivateKey,
+    parsing_error: P256KeyParsing,
+    multicodec: P256Pub,
+    field_size: 32,
+}
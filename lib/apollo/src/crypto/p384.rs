@@ -0,0 +1,9 @@
+super::nist::nist_curve_keys! {
+    curve: p384,
+    crv: "P-384",
+    public_key: P384PublicKey,
+    private_key: P384PrivateKey,
+    parsing_error: P384KeyParsing,
+    multicodec: P384Pub,
+    field_size: 48,
+}
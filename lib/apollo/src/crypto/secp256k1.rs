@@ -4,9 +4,11 @@ use k256::Secp256k1;
 use k256::ecdsa::signature::{SignerMut, Verifier};
 use k256::elliptic_curve::sec1::{EncodedPoint, ToEncodedPoint};
 
-use super::{EncodeArray, EncodeVec, Error, Verifiable};
+use super::{EncodeArray, EncodeVec, Error, Signer, Verifiable};
 use crate::base64::Base64UrlStrNoPad;
-use crate::jwk::{EncodeJwk, Jwk};
+use crate::jwk::{DecodeJwk, EncodeJwk, Jwk};
+use crate::multibase::MultibaseStr;
+use crate::multikey::{EncodeMultikey, Multicodec, encode_multikey};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Secp256k1PublicKey(k256::PublicKey);
@@ -96,6 +98,18 @@ impl Secp256k1PublicKey {
         chunk.to_owned()
     }
 
+    /// Verify a fixed-size `r || s` signature as used by `ES256K` in JWS.
+    ///
+    /// Unlike [`Verifiable::verify`], the DER encoding and the legacy JVM signature formats are not accepted.
+    pub fn verify_compact(&self, message: &[u8], signature: &[u8]) -> bool {
+        let verifying_key: k256::ecdsa::VerifyingKey = self.0.into();
+        let Ok(signature) = k256::ecdsa::Signature::from_slice(signature) else {
+            return false;
+        };
+        let signature = signature.normalize_s().unwrap_or(signature);
+        verifying_key.verify(message, &signature).is_ok()
+    }
+
     pub fn curve_point(&self) -> CurvePoint {
         let uncompressed = self.encode_uncompressed();
         let (_, xy) = uncompressed.rsplit_array_ref::<64>();
@@ -125,6 +139,13 @@ impl Secp256k1PrivateKey {
     }
 }
 
+impl Signer for Secp256k1PrivateKey {
+    /// Sign the message and return the DER-encoded signature.
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        Secp256k1PrivateKey::sign(self, message)
+    }
+}
+
 /// https://github.com/hyperledger/identus-apollo/blob/6b331d9ea1432ada4c1124af95a671d0c38bd9e2/apollo/src/jvmMain/kotlin/org/hyperledger/identus/apollo/secp256k1/Secp256k1Lib.kt#L80
 fn transcode_signature_to_bitcoin(sig: &[u8]) -> Vec<u8> {
     let raw_len = sig.len() / 2;
@@ -142,6 +163,25 @@ impl EncodeJwk for Secp256k1PublicKey {
             crv: "secp256k1".to_string(),
             x: Some(Base64UrlStrNoPad::from(point.x)),
             y: Some(Base64UrlStrNoPad::from(point.y)),
+            kid: None,
+            alg: None,
+            r#use: None,
         }
     }
 }
+
+impl DecodeJwk for Secp256k1PublicKey {
+    fn decode_jwk(jwk: &Jwk) -> Result<Self, crate::jwk::Error> {
+        jwk.ensure_key_type("EC", "secp256k1")?;
+        let mut bytes = vec![0x04];
+        bytes.extend(jwk.x_bytes()?);
+        bytes.extend(jwk.y_bytes()?);
+        Self::from_slice(&bytes).map_err(|e| crate::jwk::Error::InvalidKey { source: e })
+    }
+}
+
+impl EncodeMultikey for Secp256k1PublicKey {
+    fn encode_multikey(&self) -> MultibaseStr {
+        encode_multikey(Multicodec::Secp256k1Pub, &self.encode_compressed())
+    }
+}
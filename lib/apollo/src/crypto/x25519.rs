@@ -1,10 +1,23 @@
+use std::fmt::Debug;
+
 use super::{EncodeArray, EncodeVec, Error};
 use crate::base64::Base64UrlStrNoPad;
-use crate::jwk::{EncodeJwk, Jwk};
+use crate::jwk::{DecodeJwk, EncodeJwk, Jwk};
+use crate::multibase::MultibaseStr;
+use crate::multikey::{EncodeMultikey, Multicodec, encode_multikey};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct X25519PublicKey(x25519_dalek::PublicKey);
 
+#[derive(Clone)]
+pub struct X25519PrivateKey(x25519_dalek::StaticSecret);
+
+impl Debug for X25519PrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("X25519PrivateKey").field(&self.to_public_key()).finish()
+    }
+}
+
 impl X25519PublicKey {
     pub fn from_slice(slice: &[u8]) -> Result<X25519PublicKey, Error> {
         let Some((key, _)) = slice.split_first_chunk::<32>() else {
@@ -19,6 +32,32 @@ impl X25519PublicKey {
     }
 }
 
+impl X25519PrivateKey {
+    pub fn from_slice(slice: &[u8]) -> Result<X25519PrivateKey, Error> {
+        let Some((key, _)) = slice.split_first_chunk::<32>() else {
+            Err(Error::InvalidKeySize {
+                expected: 32,
+                actual: slice.len(),
+                key_type: std::any::type_name::<X25519PrivateKey>(),
+            })?
+        };
+        let key = x25519_dalek::StaticSecret::from(key.to_owned());
+        Ok(X25519PrivateKey(key))
+    }
+
+    pub fn to_public_key(&self) -> X25519PublicKey {
+        X25519PublicKey(x25519_dalek::PublicKey::from(&self.0))
+    }
+
+    /// Perform a Diffie-Hellman key agreement and return the raw shared secret.
+    ///
+    /// The shared secret must not be used directly as a symmetric key,
+    /// it should be passed through a key derivation function first.
+    pub fn diffie_hellman(&self, public_key: &X25519PublicKey) -> [u8; 32] {
+        self.0.diffie_hellman(&public_key.0).to_bytes()
+    }
+}
+
 impl EncodeVec for X25519PublicKey {
     fn encode_vec(&self) -> Vec<u8> {
         self.0.as_bytes().to_vec()
@@ -39,6 +78,22 @@ impl EncodeJwk for X25519PublicKey {
             crv: "X25519".to_string(),
             x: Some(Base64UrlStrNoPad::from(x)),
             y: None,
+            kid: None,
+            alg: None,
+            r#use: None,
         }
     }
 }
+
+impl DecodeJwk for X25519PublicKey {
+    fn decode_jwk(jwk: &Jwk) -> Result<Self, crate::jwk::Error> {
+        jwk.ensure_key_type("OKP", "X25519")?;
+        Self::from_slice(&jwk.x_bytes()?).map_err(|e| crate::jwk::Error::InvalidKey { source: e })
+    }
+}
+
+impl EncodeMultikey for X25519PublicKey {
+    fn encode_multikey(&self) -> MultibaseStr {
+        encode_multikey(Multicodec::X25519Pub, &self.encode_vec())
+    }
+}
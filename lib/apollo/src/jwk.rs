@@ -1,5 +1,15 @@
 use crate::base64::Base64UrlStrNoPad;
 
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum Error {
+    #[display("unsupported JWK with key type {kty} and curve {crv}")]
+    UnsupportedKeyType { kty: String, crv: String },
+    #[display("JWK parameter {parameter} is missing")]
+    MissingParameter { parameter: &'static str },
+    #[display("JWK does not contain a valid key")]
+    InvalidKey { source: crate::crypto::Error },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -8,8 +18,124 @@ pub struct Jwk {
     pub crv: String,
     pub x: Option<Base64UrlStrNoPad>,
     pub y: Option<Base64UrlStrNoPad>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub kid: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub alg: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, rename = "use", skip_serializing_if = "Option::is_none")
+    )]
+    pub r#use: Option<String>,
+}
+
+impl Jwk {
+    pub fn ensure_key_type(&self, kty: &str, crv: &str) -> Result<(), Error> {
+        if self.kty != kty || self.crv != crv {
+            Err(Error::UnsupportedKeyType {
+                kty: self.kty.clone(),
+                crv: self.crv.clone(),
+            })?
+        }
+        Ok(())
+    }
+
+    pub fn x_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.x
+            .as_ref()
+            .map(|x| x.to_bytes())
+            .ok_or(Error::MissingParameter { parameter: "x" })
+    }
+
+    pub fn y_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.y
+            .as_ref()
+            .map(|y| y.to_bytes())
+            .ok_or(Error::MissingParameter { parameter: "y" })
+    }
+
+    /// Compute the [RFC 7638](https://datatracker.ietf.org/doc/html/rfc7638) thumbprint using SHA-256.
+    ///
+    /// Only the required members of `EC` and `OKP` keys are part of the thumbprint,
+    /// so `kid`, `alg` and `use` do not affect the result.
+    ///
+    /// # Example
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use identus_apollo::base64::Base64UrlStrNoPad;
+    /// use identus_apollo::jwk::Jwk;
+    ///
+    /// // test vector from https://datatracker.ietf.org/doc/html/rfc8037#appendix-A.3
+    /// let jwk = Jwk {
+    ///     kty: "OKP".to_string(),
+    ///     crv: "Ed25519".to_string(),
+    ///     x: Some(
+    ///         Base64UrlStrNoPad::from_str("11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo").unwrap(),
+    ///     ),
+    ///     y: None,
+    ///     kid: None,
+    ///     alg: None,
+    ///     r#use: None,
+    /// };
+    /// let thumbprint = jwk.thumbprint().unwrap();
+    /// assert_eq!(
+    ///     thumbprint.to_string(),
+    ///     "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+    /// );
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn thumbprint(&self) -> Result<Base64UrlStrNoPad, Error> {
+        let x = self.x.as_ref().ok_or(Error::MissingParameter { parameter: "x" })?;
+        // required members in lexicographic order
+        let members = match self.kty.as_str() {
+            "EC" => {
+                let y = self.y.as_ref().ok_or(Error::MissingParameter { parameter: "y" })?;
+                vec![
+                    ("crv", self.crv.as_str()),
+                    ("kty", self.kty.as_str()),
+                    ("x", x.as_ref()),
+                    ("y", y.as_ref()),
+                ]
+            }
+            "OKP" => vec![
+                ("crv", self.crv.as_str()),
+                ("kty", self.kty.as_str()),
+                ("x", x.as_ref()),
+            ],
+            _ => Err(Error::UnsupportedKeyType {
+                kty: self.kty.clone(),
+                crv: self.crv.clone(),
+            })?,
+        };
+        let members = members
+            .into_iter()
+            .map(|(k, v)| format!("\"{}\":\"{}\"", k, escape_json_str(v)))
+            .collect::<Vec<_>>();
+        let canonical_json = format!("{{{}}}", members.join(","));
+        let digest = crate::hash::sha256(canonical_json);
+        Ok(Base64UrlStrNoPad::from(digest.as_bytes()))
+    }
+}
+
+#[cfg(feature = "hash")]
+fn escape_json_str(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c < '\u{20}' => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 pub trait EncodeJwk {
     fn encode_jwk(&self) -> Jwk;
 }
+
+pub trait DecodeJwk: Sized {
+    fn decode_jwk(jwk: &Jwk) -> Result<Self, Error>;
+}
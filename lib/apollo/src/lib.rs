@@ -13,3 +13,9 @@ pub mod base64;
 
 #[cfg(feature = "jwk")]
 pub mod jwk;
+
+#[cfg(feature = "multibase")]
+pub mod multibase;
+
+#[cfg(feature = "multikey")]
+pub mod multikey;
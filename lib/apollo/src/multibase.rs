@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+const BASE58BTC_PREFIX: char = 'z';
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum Error {
+    #[display("unsupported multibase encoding of '{value}', only base58btc is supported")]
+    UnsupportedEncoding { value: String },
+    #[display("unable to base58btc decode '{value}'")]
+    Base58Decode { source: bs58::decode::Error, value: String },
+}
+
+/// A [multibase](https://datatracker.ietf.org/doc/html/draft-multiformats-multibase) string
+/// using the base58btc encoding (`z` prefix).
+///
+/// # Example
+/// ```
+/// use identus_apollo::multibase::MultibaseStr;
+///
+/// let b = b"hello world";
+/// let multibase = MultibaseStr::from(b);
+/// assert!(multibase.to_string() == "zStV1DL6CwTryKyV");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::Display, derive_more::Into, derive_more::AsRef)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MultibaseStr(
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "serde_impl::deserialize_multibase"))] String,
+);
+
+impl MultibaseStr {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bs58::decode(&self.0[BASE58BTC_PREFIX.len_utf8()..])
+            .into_vec()
+            .unwrap_or_else(|_| unreachable!("{} should be a valid multibase string", std::any::type_name::<Self>()))
+    }
+}
+
+impl<B: AsRef<[u8]>> From<B> for MultibaseStr {
+    fn from(value: B) -> Self {
+        let encoded = bs58::encode(value.as_ref()).into_string();
+        Self(format!("{BASE58BTC_PREFIX}{encoded}"))
+    }
+}
+
+/// # Example
+/// ```
+/// use std::str::FromStr;
+///
+/// use identus_apollo::multibase::MultibaseStr;
+///
+/// let multibase = MultibaseStr::from_str("zStV1DL6CwTryKyV").unwrap();
+/// assert_eq!(multibase, MultibaseStr::from(b"hello world"));
+///
+/// let multibase = MultibaseStr::from_str("uaGVsbG8gd29ybGQ");
+/// assert!(multibase.is_err());
+/// ```
+impl FromStr for MultibaseStr {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(encoded) = s.strip_prefix(BASE58BTC_PREFIX) else {
+            Err(Error::UnsupportedEncoding { value: s.to_string() })?
+        };
+        let bytes = bs58::decode(encoded).into_vec().map_err(|e| Error::Base58Decode {
+            source: e,
+            value: s.to_string(),
+        })?;
+        Ok(bytes.as_slice().into())
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Deserializer};
+
+    use super::MultibaseStr;
+
+    pub fn deserialize_multibase<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        MultibaseStr::from_str(&raw)
+            .map(|i| i.to_string())
+            .map_err(|e| serde::de::Error::custom(e.to_string()))
+    }
+}
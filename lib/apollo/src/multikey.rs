@@ -0,0 +1,72 @@
+use crate::multibase::MultibaseStr;
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("unknown multicodec prefix in multikey '{value}'")]
+pub struct Error {
+    value: MultibaseStr,
+}
+
+/// Public key types from the [multicodec table](https://github.com/multiformats/multicodec/blob/master/table.csv).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Multicodec {
+    Secp256k1Pub,
+    Ed25519Pub,
+    X25519Pub,
+    P256Pub,
+    P384Pub,
+}
+
+impl Multicodec {
+    const ALL: [Multicodec; 5] = [
+        Self::Secp256k1Pub,
+        Self::Ed25519Pub,
+        Self::X25519Pub,
+        Self::P256Pub,
+        Self::P384Pub,
+    ];
+
+    /// The unsigned varint encoding of the multicodec code.
+    pub fn prefix(&self) -> &'static [u8] {
+        match self {
+            Self::Secp256k1Pub => &[0xe7, 0x01],
+            Self::Ed25519Pub => &[0xed, 0x01],
+            Self::X25519Pub => &[0xec, 0x01],
+            Self::P256Pub => &[0x80, 0x24],
+            Self::P384Pub => &[0x81, 0x24],
+        }
+    }
+}
+
+/// A public key encoded as multibase of the multicodec prefix and the key bytes,
+/// as used by `publicKeyMultibase` in a [Multikey](https://www.w3.org/TR/cid-1.0/#Multikey) verification method.
+///
+/// # Example
+/// ```
+/// use identus_apollo::multikey::{Multicodec, decode_multikey, encode_multikey};
+///
+/// let multikey = encode_multikey(Multicodec::Ed25519Pub, &[0; 32]);
+/// assert!(multikey.to_string().starts_with("z6Mk"));
+///
+/// let (codec, key) = decode_multikey(&multikey).unwrap();
+/// assert_eq!(codec, Multicodec::Ed25519Pub);
+/// assert_eq!(key, vec![0; 32]);
+/// ```
+pub fn encode_multikey(codec: Multicodec, key: &[u8]) -> MultibaseStr {
+    let mut bytes = codec.prefix().to_vec();
+    bytes.extend_from_slice(key);
+    MultibaseStr::from(bytes)
+}
+
+pub fn decode_multikey(multikey: &MultibaseStr) -> Result<(Multicodec, Vec<u8>), Error> {
+    let bytes = multikey.to_bytes();
+    Multicodec::ALL
+        .into_iter()
+        .find_map(|codec| bytes.strip_prefix(codec.prefix()).map(|key| (codec, key.to_vec())))
+        .ok_or_else(|| Error {
+            value: multikey.clone(),
+        })
+}
+
+pub trait EncodeMultikey {
+    fn encode_multikey(&self) -> MultibaseStr;
+}
@@ -0,0 +1,75 @@
+#![cfg(all(feature = "hdkey", feature = "hex"))]
+
+use std::str::FromStr;
+
+use identus_apollo::crypto::EncodeArray;
+use identus_apollo::crypto::hdkey::{PrismDerivationPath, Seed};
+use identus_apollo::hex::HexStr;
+
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+fn assert_public_key(seed: &Seed, path: &str, expected: &str) {
+    let sk = seed.derive_secp256k1_from_str(path).unwrap();
+    let pk: [u8; 33] = sk.to_public_key().encode_array();
+    assert_eq!(
+        HexStr::from(pk).to_string(),
+        expected,
+        "public key mismatch at path {path}"
+    );
+}
+
+/// test vector 1 from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vectors
+#[test]
+fn derive_bip32_test_vector() {
+    let seed = Seed::from_slice(&HexStr::from_str("000102030405060708090a0b0c0d0e0f").unwrap().to_bytes());
+    assert_public_key(
+        &seed,
+        "m",
+        "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2",
+    );
+    assert_public_key(
+        &seed,
+        "m/0'",
+        "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56",
+    );
+    assert_public_key(
+        &seed,
+        "m/0'/1",
+        "03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c",
+    );
+    assert_public_key(
+        &seed,
+        "m/0'/1/2'/2/1000000000",
+        "022a471424da5e657499d1ff51cb43c47481a03b1e77f951fe64cec9f5a48f7011",
+    );
+}
+
+/// test vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+#[test]
+fn seed_from_mnemonic() {
+    let seed = Seed::from_mnemonic(MNEMONIC, "TREZOR").unwrap();
+    assert_eq!(
+        HexStr::from(seed.as_bytes()).to_string(),
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+}
+
+#[test]
+fn seed_from_invalid_mnemonic() {
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+    assert!(Seed::from_mnemonic(mnemonic, "").is_err());
+}
+
+#[test]
+fn prism_derivation_path() {
+    let path = PrismDerivationPath::new(3, 8, 1);
+    assert_eq!(path.to_string(), "m/29'/29'/3'/8'/1'");
+
+    let seed = Seed::from_mnemonic(MNEMONIC, "").unwrap();
+    let sk = seed.derive_secp256k1(&path).unwrap();
+    assert_eq!(sk, seed.derive_secp256k1_from_str("m/29'/29'/3'/8'/1'").unwrap());
+    assert_ne!(sk, seed.derive_secp256k1(&PrismDerivationPath::new(3, 8, 0)).unwrap());
+
+    let invalid_path = PrismDerivationPath::new(u32::MAX, 1, 0);
+    assert!(seed.derive_secp256k1(&invalid_path).is_err());
+}
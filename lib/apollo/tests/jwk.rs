@@ -0,0 +1,73 @@
+#![cfg(all(feature = "ed25519", feature = "secp256k1", feature = "x25519", feature = "hash"))]
+
+use identus_apollo::crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use identus_apollo::crypto::secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey};
+use identus_apollo::crypto::x25519::{X25519PrivateKey, X25519PublicKey};
+use identus_apollo::jwk::{DecodeJwk, EncodeJwk, Error};
+
+#[test]
+fn decode_encoded_jwk() {
+    let secp256k1_pk = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap().to_public_key();
+    let ed25519_pk = Ed25519PrivateKey::from_slice(&[1; 32]).unwrap().to_public_key();
+    let x25519_pk = X25519PrivateKey::from_slice(&[1; 32]).unwrap().to_public_key();
+
+    assert_eq!(
+        Secp256k1PublicKey::decode_jwk(&secp256k1_pk.encode_jwk()).unwrap(),
+        secp256k1_pk
+    );
+    assert_eq!(
+        Ed25519PublicKey::decode_jwk(&ed25519_pk.encode_jwk()).unwrap(),
+        ed25519_pk
+    );
+    assert_eq!(X25519PublicKey::decode_jwk(&x25519_pk.encode_jwk()).unwrap(), x25519_pk);
+}
+
+#[test]
+fn decode_jwk_with_mismatched_key_type() {
+    let ed25519_jwk = Ed25519PrivateKey::from_slice(&[1; 32])
+        .unwrap()
+        .to_public_key()
+        .encode_jwk();
+
+    let result = X25519PublicKey::decode_jwk(&ed25519_jwk);
+    assert!(matches!(result, Err(Error::UnsupportedKeyType { .. })));
+
+    let result = Secp256k1PublicKey::decode_jwk(&ed25519_jwk);
+    assert!(matches!(result, Err(Error::UnsupportedKeyType { .. })));
+}
+
+#[test]
+fn decode_jwk_with_missing_or_invalid_parameter() {
+    let mut jwk = Secp256k1PrivateKey::from_slice(&[1; 32])
+        .unwrap()
+        .to_public_key()
+        .encode_jwk();
+    jwk.y = None;
+    let result = Secp256k1PublicKey::decode_jwk(&jwk);
+    assert!(matches!(result, Err(Error::MissingParameter { parameter: "y" })));
+
+    jwk.y = jwk.x.clone();
+    let result = Secp256k1PublicKey::decode_jwk(&jwk);
+    assert!(matches!(result, Err(Error::InvalidKey { .. })));
+}
+
+#[test]
+fn thumbprint_ignores_optional_parameters() {
+    let jwk = Secp256k1PrivateKey::from_slice(&[1; 32])
+        .unwrap()
+        .to_public_key()
+        .encode_jwk();
+    let jwk_with_params = identus_apollo::jwk::Jwk {
+        kid: Some("did:prism:123#key-1".to_string()),
+        alg: Some("ES256K".to_string()),
+        r#use: Some("sig".to_string()),
+        ..jwk.clone()
+    };
+    assert_eq!(jwk.thumbprint().unwrap(), jwk_with_params.thumbprint().unwrap());
+
+    let other_jwk = Secp256k1PrivateKey::from_slice(&[2; 32])
+        .unwrap()
+        .to_public_key()
+        .encode_jwk();
+    assert_ne!(jwk.thumbprint().unwrap(), other_jwk.thumbprint().unwrap());
+}
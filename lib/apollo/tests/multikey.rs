@@ -0,0 +1,47 @@
+#![cfg(all(feature = "ed25519", feature = "secp256k1", feature = "x25519"))]
+
+use std::str::FromStr;
+
+use identus_apollo::crypto::EncodeVec;
+use identus_apollo::crypto::ed25519::Ed25519PrivateKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_apollo::crypto::x25519::X25519PrivateKey;
+use identus_apollo::multibase::MultibaseStr;
+use identus_apollo::multikey::{EncodeMultikey, Multicodec, decode_multikey};
+
+#[test]
+fn encode_multikey_prefix() {
+    let secp256k1_pk = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap().to_public_key();
+    let ed25519_pk = Ed25519PrivateKey::from_slice(&[1; 32]).unwrap().to_public_key();
+    let x25519_pk = X25519PrivateKey::from_slice(&[1; 32]).unwrap().to_public_key();
+
+    // well-known prefixes of base58btc encoded multikey
+    assert!(secp256k1_pk.encode_multikey().to_string().starts_with("zQ3s"));
+    assert!(ed25519_pk.encode_multikey().to_string().starts_with("z6Mk"));
+    assert!(x25519_pk.encode_multikey().to_string().starts_with("z6LS"));
+
+    let (codec, key) = decode_multikey(&secp256k1_pk.encode_multikey()).unwrap();
+    assert_eq!(codec, Multicodec::Secp256k1Pub);
+    assert_eq!(key, secp256k1_pk.encode_vec());
+
+    let (codec, key) = decode_multikey(&ed25519_pk.encode_multikey()).unwrap();
+    assert_eq!(codec, Multicodec::Ed25519Pub);
+    assert_eq!(key, ed25519_pk.encode_vec());
+
+    let (codec, key) = decode_multikey(&x25519_pk.encode_multikey()).unwrap();
+    assert_eq!(codec, Multicodec::X25519Pub);
+    assert_eq!(key, x25519_pk.encode_vec());
+}
+
+#[test]
+fn decode_multikey_unknown_codec() {
+    let multibase = MultibaseStr::from([0x00, 0x01, 0x02]);
+    assert!(decode_multikey(&multibase).is_err());
+}
+
+#[test]
+fn parse_multibase() {
+    assert!(MultibaseStr::from_str("z").unwrap().to_bytes().is_empty());
+    assert!(MultibaseStr::from_str("z0OIl").is_err());
+    assert!(MultibaseStr::from_str("fabcd").is_err());
+}
@@ -0,0 +1,89 @@
+#![cfg(all(feature = "p256", feature = "p384"))]
+
+use identus_apollo::crypto::p256::{P256PrivateKey, P256PublicKey};
+use identus_apollo::crypto::p384::{P384PrivateKey, P384PublicKey};
+use identus_apollo::crypto::{EncodeArray, EncodeVec, Signer, Verifiable};
+use identus_apollo::jwk::{DecodeJwk, EncodeJwk};
+use identus_apollo::multikey::{EncodeMultikey, Multicodec, decode_multikey};
+
+/// Convert a DER signature to the fixed-size `r || s` format.
+fn der_to_fixed(der: &[u8], scalar_size: usize) -> Vec<u8> {
+    // SEQUENCE { INTEGER r, INTEGER s }
+    let r_len = der[3] as usize;
+    let r = &der[4..4 + r_len];
+    let s_len = der[5 + r_len] as usize;
+    let s = &der[6 + r_len..6 + r_len + s_len];
+    let pad = |i: &[u8]| {
+        let i = &i[i.len().saturating_sub(scalar_size)..];
+        let mut padded = vec![0; scalar_size - i.len()];
+        padded.extend_from_slice(i);
+        padded
+    };
+    [pad(r), pad(s)].concat()
+}
+
+#[test]
+fn p256_sign_and_verify() {
+    let sk = P256PrivateKey::from_slice(&[1; 32]).unwrap();
+    let pk = sk.to_public_key();
+
+    let signature = sk.sign(b"hello world");
+    assert!(pk.verify(b"hello world", &signature));
+    assert!(pk.verify(b"hello world", &der_to_fixed(&signature, 32)));
+    assert!(!pk.verify(b"hello", &signature));
+    assert!(!pk.verify(b"hello world", &[0; 64]));
+}
+
+#[test]
+fn p384_sign_and_verify() {
+    let sk = P384PrivateKey::from_slice(&[1; 48]).unwrap();
+    let pk = sk.to_public_key();
+
+    let signature = sk.sign(b"hello world");
+    assert!(pk.verify(b"hello world", &signature));
+    assert!(pk.verify(b"hello world", &der_to_fixed(&signature, 48)));
+    assert!(!pk.verify(b"hello", &signature));
+}
+
+#[test]
+fn p256_encoding() {
+    let pk = P256PrivateKey::from_slice(&[1; 32]).unwrap().to_public_key();
+
+    let compressed: [u8; 33] = pk.encode_array();
+    let uncompressed: [u8; 65] = pk.encode_array();
+    assert_eq!(P256PublicKey::from_slice(&compressed).unwrap(), pk);
+    assert_eq!(P256PublicKey::from_slice(&uncompressed).unwrap(), pk);
+    assert_eq!(pk.encode_vec(), compressed.to_vec());
+
+    let jwk = pk.encode_jwk();
+    assert_eq!(jwk.kty, "EC");
+    assert_eq!(jwk.crv, "P-256");
+    assert_eq!(P256PublicKey::decode_jwk(&jwk).unwrap(), pk);
+    assert!(P384PublicKey::decode_jwk(&jwk).is_err());
+
+    let multikey = pk.encode_multikey();
+    assert!(multikey.to_string().starts_with("zDn"));
+    assert_eq!(
+        decode_multikey(&multikey).unwrap(),
+        (Multicodec::P256Pub, compressed.to_vec())
+    );
+}
+
+#[test]
+fn p384_encoding() {
+    let pk = P384PrivateKey::from_slice(&[1; 48]).unwrap().to_public_key();
+
+    let compressed: [u8; 49] = pk.encode_array();
+    assert_eq!(P384PublicKey::from_slice(&compressed).unwrap(), pk);
+
+    let jwk = pk.encode_jwk();
+    assert_eq!(jwk.crv, "P-384");
+    assert_eq!(P384PublicKey::decode_jwk(&jwk).unwrap(), pk);
+
+    let multikey = pk.encode_multikey();
+    assert!(multikey.to_string().starts_with("z82"));
+    assert_eq!(
+        decode_multikey(&multikey).unwrap(),
+        (Multicodec::P384Pub, compressed.to_vec())
+    );
+}
@@ -0,0 +1,85 @@
+#![cfg(all(feature = "ed25519", feature = "secp256k1", feature = "x25519", feature = "hex"))]
+
+use std::str::FromStr;
+
+use identus_apollo::crypto::ed25519::Ed25519PrivateKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_apollo::crypto::x25519::{X25519PrivateKey, X25519PublicKey};
+use identus_apollo::crypto::{EncodeVec, Signer, Verifiable};
+use identus_apollo::hex::HexStr;
+
+fn hex_bytes(hex: &str) -> Vec<u8> {
+    HexStr::from_str(hex).unwrap().to_bytes()
+}
+
+/// test vector 1 from https://datatracker.ietf.org/doc/html/rfc8032#section-7.1
+#[test]
+fn ed25519_sign_test_vector() {
+    let sk = Ed25519PrivateKey::from_slice(&hex_bytes(
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+    ))
+    .unwrap();
+    let pk = sk.to_public_key();
+    assert_eq!(
+        pk.encode_vec(),
+        hex_bytes("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+    );
+
+    let signature = sk.sign(b"");
+    assert_eq!(
+        signature,
+        hex_bytes(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        )
+    );
+    assert!(pk.verify(b"", &signature));
+    assert!(!pk.verify(b"hello", &signature));
+}
+
+#[test]
+fn sign_and_verify_with_signer() {
+    fn sign_and_verify(signer: &dyn Signer, verifier: &dyn Verifiable) {
+        let signature = signer.sign(b"hello world");
+        assert!(verifier.verify(b"hello world", &signature));
+        assert!(!verifier.verify(b"hello", &signature));
+    }
+
+    let sk = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap();
+    sign_and_verify(&sk, &sk.to_public_key());
+
+    let sk = Ed25519PrivateKey::from_slice(&[1; 32]).unwrap();
+    sign_and_verify(&sk, &sk.to_public_key());
+}
+
+#[test]
+fn private_key_invalid_size() {
+    assert!(Ed25519PrivateKey::from_slice(&[1; 31]).is_err());
+    assert!(X25519PrivateKey::from_slice(&[1; 31]).is_err());
+}
+
+/// test vector from https://datatracker.ietf.org/doc/html/rfc7748#section-6.1
+#[test]
+fn x25519_diffie_hellman_test_vector() {
+    let alice_sk = X25519PrivateKey::from_slice(&hex_bytes(
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+    ))
+    .unwrap();
+    let bob_sk = X25519PrivateKey::from_slice(&hex_bytes(
+        "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+    ))
+    .unwrap();
+    let alice_pk = X25519PublicKey::from_slice(&hex_bytes(
+        "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a",
+    ))
+    .unwrap();
+    let bob_pk = X25519PublicKey::from_slice(&hex_bytes(
+        "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f",
+    ))
+    .unwrap();
+    assert_eq!(alice_sk.to_public_key(), alice_pk);
+    assert_eq!(bob_sk.to_public_key(), bob_pk);
+
+    let shared_secret = hex_bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(alice_sk.diffie_hellman(&bob_pk).to_vec(), shared_secret);
+    assert_eq!(bob_sk.diffie_hellman(&alice_pk).to_vec(), shared_secret);
+}
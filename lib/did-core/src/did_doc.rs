@@ -1,4 +1,5 @@
 use identus_apollo::jwk::Jwk;
+use identus_apollo::multibase::MultibaseStr;
 use serde::{Deserialize, Serialize};
 
 use crate::Did;
@@ -26,14 +27,17 @@ pub struct VerificationMethod {
     pub id: String,
     pub r#type: String,
     pub controller: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key_jwk: Option<Jwk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key_multibase: Option<MultibaseStr>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(untagged)]
 pub enum VerificationMethodOrRef {
-    Embedded(VerificationMethod),
+    Embedded(Box<VerificationMethod>),
     Ref(String),
 }
 
@@ -0,0 +1,235 @@
+use std::str::FromStr;
+
+use identus_apollo::hex::HexStr;
+use identus_did_core::DidDocument;
+use identus_did_prism::did::did_doc::{DidResolutionResult, VerificationMethodFormat};
+use identus_did_prism::did::operation::SignedPrismOperationHexStr;
+use identus_did_prism::did::resolver::LongFormResolutionError;
+use identus_did_prism::did::{CanonicalPrismDid, DidResolver, DidState, LongFormResolver, PrismDid};
+use identus_did_prism::dlt::{OperationMetadata, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::proto::node_api::DIDData;
+use identus_did_prism::protocol::resolver::resolve_published;
+use reqwest::{Client, Response, StatusCode, header};
+
+use crate::models::{DidOperation, SignedOperationSubmissionRequest, SignedOperationSubmissionResponse};
+
+/// The media type requested from the node to get a DID resolution result instead of a DID document.
+const DID_RESOLUTION_MEDIA_TYPE: &str = r#"application/ld+json;profile="https://w3id.org/did-resolution""#;
+
+#[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
+pub enum Error {
+    #[from]
+    #[display("unable to send a request to the node")]
+    Request { source: reqwest::Error },
+    #[display("did {did} is invalid according to the node")]
+    InvalidDid { did: String },
+    #[display("did {did} is not found")]
+    DidNotFound { did: String },
+    #[display("node did not return a success status (status: {status}, body: {body})")]
+    UnexpectedStatus { status: u16, body: String },
+    #[display("node returned DIDData that is not a valid hex string")]
+    InvalidDidDataHex { source: identus_apollo::hex::Error },
+    #[display("node returned DIDData that cannot be decoded")]
+    InvalidDidDataProto { source: protobuf::Error },
+    #[from]
+    #[display("unable to resolve long-form did locally")]
+    LongFormResolution { source: LongFormResolutionError },
+}
+
+mod models {
+    use identus_did_prism::did::operation::SignedPrismOperationHexStr;
+    use identus_did_prism::dlt::{OperationMetadata, TxId};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct DidOperation {
+        pub metadata: OperationMetadata,
+        pub signed_operation: SignedPrismOperationHexStr,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SignedOperationSubmissionRequest {
+        pub signed_operations: Vec<SignedPrismOperationHexStr>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SignedOperationSubmissionResponse {
+        pub tx_id: TxId,
+    }
+}
+
+/// An HTTP client for the REST API of a remote NeoPRISM node.
+///
+/// # Example
+/// ```no_run
+/// use std::str::FromStr;
+///
+/// use identus_did_prism::did::PrismDid;
+/// use identus_did_prism_client::NodeClient;
+///
+/// # async fn example() -> Result<(), identus_did_prism_client::Error> {
+/// let client = NodeClient::new("https://neoprism.patlo.dev");
+/// let did = PrismDid::from_str(
+///     "did:prism:0000000000000000000000000000000000000000000000000000000000000000",
+/// )
+/// .unwrap();
+/// let did_doc = client.resolve_did(&did).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NodeClient {
+    base_url: String,
+    client: Client,
+}
+
+impl NodeClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_client(base_url, Client::new())
+    }
+
+    pub fn with_client(base_url: impl Into<String>, client: Client) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    /// Resolve the W3C DID document using the default verification method format of the node.
+    pub async fn resolve_did(&self, did: &PrismDid) -> Result<DidDocument, Error> {
+        let resp = self
+            .client
+            .get(format!("{}/api/dids/{}", self.base_url, did))
+            .send()
+            .await?;
+        let resp = check_resolution_status(did, resp).await?;
+        Ok(resp.json().await?)
+    }
+
+    pub async fn resolve_did_with_format(
+        &self,
+        did: &PrismDid,
+        format: VerificationMethodFormat,
+    ) -> Result<DidDocument, Error> {
+        let resp = self
+            .client
+            .get(format!("{}/api/dids/{}", self.base_url, did))
+            .query(&[("verificationMethodFormat", format)])
+            .send()
+            .await?;
+        let resp = check_resolution_status(did, resp).await?;
+        Ok(resp.json().await?)
+    }
+
+    /// Resolve the W3C DID document with its metadata as a DID resolution result.
+    ///
+    /// The default verification method format of the node is used when `format` is `None`.
+    pub async fn resolve_did_resolution_result(
+        &self,
+        did: &PrismDid,
+        format: Option<VerificationMethodFormat>,
+    ) -> Result<DidResolutionResult, Error> {
+        let mut req = self
+            .client
+            .get(format!("{}/api/dids/{}", self.base_url, did))
+            .header(header::ACCEPT, DID_RESOLUTION_MEDIA_TYPE);
+        if let Some(format) = format {
+            req = req.query(&[("verificationMethodFormat", format)]);
+        }
+        let resp = req.send().await?;
+        let resp = check_resolution_status(did, resp).await?;
+        Ok(resp.json().await?)
+    }
+
+    /// Resolve the DID as the `DIDData` protobuf message.
+    pub async fn get_did_data(&self, did: &PrismDid) -> Result<DIDData, Error> {
+        let resp = self
+            .client
+            .get(format!("{}/api/did-data/{}", self.base_url, did))
+            .send()
+            .await?;
+        let resp = check_resolution_status(did, resp).await?;
+        let hex_str = HexStr::from_str(&resp.text().await?).map_err(|e| Error::InvalidDidDataHex { source: e })?;
+        let did_data = DIDData::decode(&hex_str.to_bytes()).map_err(|e| Error::InvalidDidDataProto { source: e })?;
+        Ok(did_data)
+    }
+
+    /// Get all published operations of the DID including the invalid ones.
+    pub async fn get_did_operations(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(OperationMetadata, SignedPrismOperation)>, Error> {
+        let resp = self
+            .client
+            .get(format!("{}/api/did-operations/{}", self.base_url, did))
+            .send()
+            .await?;
+        let resp = check_success_status(resp).await?;
+        let operations = resp.json::<Vec<DidOperation>>().await?;
+        Ok(operations
+            .into_iter()
+            .map(|op| (op.metadata, op.signed_operation.into()))
+            .collect())
+    }
+
+    /// Submit signed operations to the node, returning the id of the transaction that contains them.
+    pub async fn submit_signed_operations(&self, operations: Vec<SignedPrismOperation>) -> Result<TxId, Error> {
+        let req = SignedOperationSubmissionRequest {
+            signed_operations: operations.into_iter().map(SignedPrismOperationHexStr::from).collect(),
+        };
+        let resp = self
+            .client
+            .post(format!("{}/api/signed-operation-submissions", self.base_url))
+            .json(&req)
+            .send()
+            .await?;
+        let resp = check_success_status(resp).await?;
+        let submission = resp.json::<SignedOperationSubmissionResponse>().await?;
+        Ok(submission.tx_id)
+    }
+}
+
+async fn check_resolution_status(did: &PrismDid, resp: Response) -> Result<Response, Error> {
+    match resp.status() {
+        StatusCode::BAD_REQUEST => Err(Error::InvalidDid { did: did.to_string() }),
+        StatusCode::NOT_FOUND => Err(Error::DidNotFound { did: did.to_string() }),
+        _ => check_success_status(resp).await,
+    }
+}
+
+async fn check_success_status(resp: Response) -> Result<Response, Error> {
+    let status = resp.status();
+    if status.is_success() {
+        Ok(resp)
+    } else {
+        Err(Error::UnexpectedStatus {
+            status: status.as_u16(),
+            body: resp.text().await.unwrap_or_default(),
+        })
+    }
+}
+
+/// Resolve DIDs from the published operations returned by the node.
+///
+/// The operations are validated locally, so the node is only trusted to return all operations of the DID.
+#[async_trait::async_trait]
+impl DidResolver for NodeClient {
+    type Error = Error;
+
+    async fn resolve(&self, did: &PrismDid) -> Result<Option<DidState>, Self::Error> {
+        let operations = self.get_did_operations(&did.clone().into_canonical()).await?;
+        if operations.is_empty() {
+            Ok(LongFormResolver.resolve(did).await?)
+        } else {
+            Ok(resolve_published(operations).0)
+        }
+    }
+
+    async fn get_operations(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        self.get_did_operations(did).await
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Path, Query};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::DateTime;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_apollo::hash::sha256;
+use identus_apollo::hex::HexStr;
+use identus_did_prism::did::did_doc::{DidResolutionResult, VerificationMethodFormat};
+use identus_did_prism::did::operation::builder::{CreateDidOperationBuilder, sign_operation};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey, SignedPrismOperationHexStr};
+use identus_did_prism::did::{CanonicalPrismDid, DidResolver, DidState, LongFormPrismDid, PrismDid, PrismDidOps};
+use identus_did_prism::dlt::{BlockHash, BlockMetadata, OperationMetadata, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::proto::node_api::DIDData;
+use identus_did_prism::protocol::resolver::resolve_published;
+use identus_did_prism_client::{Error, NodeClient};
+use serde_json::{Value, json};
+
+fn create_did_operation() -> SignedPrismOperation {
+    let master_sk = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap();
+    let auth_sk = Secp256k1PrivateKey::from_slice(&[2; 32]).unwrap();
+    let operation = CreateDidOperationBuilder::new()
+        .add_key(
+            "master-0",
+            KeyUsage::MasterKey,
+            NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+        )
+        .add_key(
+            "auth-0",
+            KeyUsage::AuthenticationKey,
+            NonOperationPublicKey::Secp256k1(auth_sk.to_public_key()),
+        )
+        .build();
+    sign_operation(operation, "master-0", &master_sk)
+}
+
+fn metadata() -> OperationMetadata {
+    OperationMetadata {
+        block_metadata: BlockMetadata {
+            slot_number: 10.into(),
+            block_number: 1.into(),
+            block_hash: Some(BlockHash::from(sha256("block"))),
+            cbt: DateTime::UNIX_EPOCH,
+            absn: 0,
+            tx_hash: Some(TxId::from(sha256("tx"))),
+        },
+        osn: 0,
+    }
+}
+
+fn did_and_state(signed_operation: &SignedPrismOperation) -> (CanonicalPrismDid, DidState) {
+    let did = CanonicalPrismDid::from_operation(signed_operation.operation.as_ref().unwrap()).unwrap();
+    let state = resolve_published(vec![(metadata(), signed_operation.clone())])
+        .0
+        .unwrap();
+    (did, state)
+}
+
+/// The resolution result returned by the node for a DID created by the given operation.
+fn resolution_result(
+    signed_operation: &SignedPrismOperation,
+    format: VerificationMethodFormat,
+) -> (CanonicalPrismDid, DidResolutionResult) {
+    let did = CanonicalPrismDid::from_operation(signed_operation.operation.as_ref().unwrap()).unwrap();
+    let (state, debug) = resolve_published(vec![(metadata(), signed_operation.clone())]);
+    let result = state.unwrap().to_resolution_result(&did.to_did(), format, &debug);
+    (did, result)
+}
+
+/// Serve the router on a random local port and return its base URL.
+async fn serve(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    format!("http://{addr}")
+}
+
+/// Serve a node that responds to every request with the same status and body.
+async fn serve_status(status: StatusCode, body: &'static str) -> NodeClient {
+    let router = Router::new().fallback(move || async move { (status, body) });
+    NodeClient::new(serve(router).await)
+}
+
+#[tokio::test]
+async fn resolve_did_parses_did_document() {
+    let (did, state) = did_and_state(&create_did_operation());
+    let did_doc = state.to_did_document(&did.to_did());
+    let expected_did = did.to_string();
+    let router = Router::new().route(
+        "/api/dids/{did}",
+        get(move |Path(path_did): Path<String>| async move {
+            assert_eq!(path_did, expected_did);
+            Json(did_doc)
+        }),
+    );
+    // the trailing slash of the base URL must not end up in the request path
+    let client = NodeClient::new(format!("{}/", serve(router).await));
+
+    let result = client.resolve_did(&did.clone().into()).await.unwrap();
+    assert_eq!(result.id.to_string(), did.to_string());
+    assert_eq!(result.verification_method.len(), 1);
+    assert_eq!(result.verification_method[0].r#type, "JsonWebKey2020");
+}
+
+#[tokio::test]
+async fn resolve_did_with_format_sends_query() {
+    let (did, state) = did_and_state(&create_did_operation());
+    let did_doc = state.to_did_document_with_format(&did.to_did(), VerificationMethodFormat::Multikey);
+    let router = Router::new().route(
+        "/api/dids/{did}",
+        get(move |Query(query): Query<HashMap<String, String>>| async move {
+            assert_eq!(query.get("verificationMethodFormat").unwrap(), "Multikey");
+            Json(did_doc)
+        }),
+    );
+    let client = NodeClient::new(serve(router).await);
+
+    let result = client
+        .resolve_did_with_format(&did.into(), VerificationMethodFormat::Multikey)
+        .await
+        .unwrap();
+    assert_eq!(result.verification_method[0].r#type, "Multikey");
+}
+
+#[tokio::test]
+async fn resolve_did_resolution_result_sends_accept_header() {
+    let (did, resolution_result) = resolution_result(&create_did_operation(), VerificationMethodFormat::Multikey);
+    let router = Router::new().route(
+        "/api/dids/{did}",
+        get(
+            move |Query(query): Query<HashMap<String, String>>, headers: HeaderMap| async move {
+                assert_eq!(query.get("verificationMethodFormat").unwrap(), "Multikey");
+                assert_eq!(
+                    headers.get(header::ACCEPT).unwrap(),
+                    r#"application/ld+json;profile="https://w3id.org/did-resolution""#
+                );
+                Json(resolution_result)
+            },
+        ),
+    );
+    let client = NodeClient::new(serve(router).await);
+
+    let result = client
+        .resolve_did_resolution_result(&did.clone().into(), Some(VerificationMethodFormat::Multikey))
+        .await
+        .unwrap();
+    assert_eq!(result.did_document.id.to_string(), did.to_string());
+    assert_eq!(result.did_document.verification_method[0].r#type, "Multikey");
+    let metadata = result.did_document_metadata;
+    assert_eq!(metadata.created, Some(DateTime::UNIX_EPOCH));
+    assert_eq!(metadata.created_tx_hash, Some(TxId::from(sha256("tx"))));
+    assert_eq!(metadata.created_block_hash, Some(BlockHash::from(sha256("block"))));
+    assert_eq!(metadata.updated, None);
+}
+
+#[tokio::test]
+async fn resolution_status_maps_to_error() {
+    let (did, _) = did_and_state(&create_did_operation());
+    let did = PrismDid::from(did);
+
+    let client = serve_status(StatusCode::BAD_REQUEST, "").await;
+    let result = client.resolve_did(&did).await;
+    assert!(matches!(result, Err(Error::InvalidDid { did: d }) if d == did.to_string()));
+
+    let client = serve_status(StatusCode::NOT_FOUND, "").await;
+    let result = client.get_did_data(&did).await;
+    assert!(matches!(result, Err(Error::DidNotFound { did: d }) if d == did.to_string()));
+
+    let client = serve_status(StatusCode::INTERNAL_SERVER_ERROR, "database is down").await;
+    let result = client.resolve_did(&did).await;
+    assert!(matches!(
+        result,
+        Err(Error::UnexpectedStatus { status: 500, body }) if body == "database is down"
+    ));
+}
+
+#[tokio::test]
+async fn get_did_data_decodes_hex_proto() {
+    let (did, state) = did_and_state(&create_did_operation());
+    let expected: DIDData = state.into();
+    let hex_str = HexStr::from(expected.encode_to_vec()).to_string();
+    let router = Router::new().route("/api/did-data/{did}", get(move || async move { hex_str }));
+    let client = NodeClient::new(serve(router).await);
+
+    let did_data = client.get_did_data(&did.into()).await.unwrap();
+    assert_eq!(did_data, expected);
+    assert_eq!(did_data.public_keys.len(), 2);
+}
+
+#[tokio::test]
+async fn get_did_data_rejects_invalid_hex() {
+    let (did, _) = did_and_state(&create_did_operation());
+    let client = serve_status(StatusCode::OK, "not a hex string").await;
+    let result = client.get_did_data(&did.into()).await;
+    assert!(matches!(result, Err(Error::InvalidDidDataHex { .. })));
+}
+
+#[tokio::test]
+async fn resolve_did_from_node_operations() {
+    let signed_operation = create_did_operation();
+    let (did, _) = did_and_state(&signed_operation);
+    let operations = json!([{
+        "metadata": metadata(),
+        "signed_operation": SignedPrismOperationHexStr::from(signed_operation.clone()),
+    }]);
+    let router = Router::new().route(
+        "/api/did-operations/{did}",
+        get(move || async move { Json(operations) }),
+    );
+    let client = NodeClient::new(serve(router).await);
+
+    let operations = client.get_did_operations(&did).await.unwrap();
+    assert_eq!(operations, vec![(metadata(), signed_operation)]);
+
+    let state = client.resolve(&did.clone().into()).await.unwrap().unwrap();
+    assert_eq!(state.did, did);
+    assert_eq!(state.public_keys.len(), 2);
+}
+
+#[tokio::test]
+async fn resolve_unpublished_did() {
+    let signed_operation = create_did_operation();
+    let (did, _) = did_and_state(&signed_operation);
+    let long_form_did = LongFormPrismDid::from_operation(signed_operation.operation.as_ref().unwrap()).unwrap();
+    let router = Router::new().route("/api/did-operations/{did}", get(|| async { Json(json!([])) }));
+    let client = NodeClient::new(serve(router).await);
+
+    // long-form DID is resolved locally when the node has no operations
+    let state = client.resolve(&long_form_did.into()).await.unwrap().unwrap();
+    assert_eq!(state.did, did);
+    assert_eq!(state.public_keys.len(), 2);
+
+    let state = client.resolve(&did.into()).await.unwrap();
+    assert!(state.is_none());
+}
+
+#[tokio::test]
+async fn submit_signed_operations_returns_tx_id() {
+    let signed_operation = create_did_operation();
+    let tx_id = TxId::from(sha256("tx"));
+    let requests = Arc::new(Mutex::new(Vec::<Value>::new()));
+    let router = Router::new().route(
+        "/api/signed-operation-submissions",
+        post({
+            let requests = requests.clone();
+            let tx_id = tx_id.clone();
+            move |Json(body): Json<Value>| async move {
+                requests.lock().unwrap().push(body);
+                Json(json!({ "tx_id": tx_id }))
+            }
+        }),
+    );
+    let client = NodeClient::new(serve(router).await);
+
+    let result = client
+        .submit_signed_operations(vec![signed_operation.clone()])
+        .await
+        .unwrap();
+    assert_eq!(result, tx_id);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0],
+        json!({ "signed_operations": [HexStr::from(signed_operation.encode_to_vec()).to_string()] })
+    );
+}
@@ -16,8 +16,9 @@ mod models {
     use std::str::FromStr;
 
     use chrono::{DateTime, Utc};
+    use identus_apollo::hash::Sha256Digest;
     use identus_apollo::hex::HexStr;
-    use identus_did_prism::dlt::{BlockMetadata, PublishedPrismObject};
+    use identus_did_prism::dlt::{BlockHash, BlockMetadata, PublishedPrismObject, TxId};
     use identus_did_prism::proto::MessageExt;
     use identus_did_prism::proto::prism::PrismObject;
     use serde::{Deserialize, Serialize};
@@ -32,6 +33,7 @@ mod models {
         pub block_no: i32,
         pub block_hash: Vec<u8>,
         pub tx_idx: i32,
+        pub tx_hash: Vec<u8>,
         pub metadata: serde_json::Value,
     }
 
@@ -60,13 +62,25 @@ mod models {
 
     pub fn parse_metadata_projection(metadata: MetadataProjection) -> Result<PublishedPrismObject, MetadataReadError> {
         let block_hash = HexStr::from(&metadata.block_hash).to_string();
+        let tx_idx = Some(metadata.tx_idx as usize);
+        let tx_hash = Sha256Digest::from_bytes(&metadata.tx_hash).map_err(|_| MetadataReadError::InvalidTxHash {
+            block_hash: Some(block_hash.clone()),
+            tx_idx,
+            tx_hash: HexStr::from(&metadata.tx_hash).to_string(),
+        })?;
+        let block_hash_digest =
+            Sha256Digest::from_bytes(&metadata.block_hash).map_err(|_| MetadataReadError::InvalidBlockHash {
+                block_hash: block_hash.clone(),
+                tx_idx,
+            })?;
         let block_metadata = BlockMetadata {
             slot_number: (metadata.slot_no as u64).into(),
             block_number: (metadata.block_no as u64).into(),
+            block_hash: Some(BlockHash::from(block_hash_digest)),
             cbt: metadata.time,
             absn: metadata.tx_idx as u32,
+            tx_hash: Some(TxId::from(tx_hash)),
         };
-        let tx_idx = Some(metadata.tx_idx as usize);
 
         let metadata_json: MetadataMapJson =
             serde_json::from_value(metadata.metadata).map_err(|e| MetadataReadError::InvalidMetadataType {
@@ -343,6 +357,7 @@ SELECT
     b.block_no,
     b.hash AS block_hash,
     tx.block_index AS tx_idx,
+    tx.hash AS tx_hash,
     tx_meta.json AS metadata
 FROM tx_metadata AS tx_meta
 LEFT JOIN tx ON tx_meta.tx_id = tx.id
@@ -42,6 +42,14 @@ pub(crate) enum MetadataReadError {
         tx_idx: Option<usize>,
         timestamp: i64,
     },
+    #[display("tx hash {tx_hash} is invalid on block {block_hash:?} tx {tx_idx:?}")]
+    InvalidTxHash {
+        block_hash: Option<String>,
+        tx_idx: Option<usize>,
+        tx_hash: String,
+    },
+    #[display("block hash {block_hash} is invalid on tx {tx_idx:?}")]
+    InvalidBlockHash { block_hash: String, tx_idx: Option<usize> },
     #[display("block property '{name}' is missing on block {block_hash:?} tx {tx_idx:?}")]
     MissingBlockProperty {
         block_hash: Option<String>,
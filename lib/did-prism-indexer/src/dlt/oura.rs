@@ -18,8 +18,12 @@ use crate::dlt::common::CursorPersistWorker;
 use crate::repo::DltCursorRepo;
 
 mod models {
+    use std::str::FromStr;
+
     use chrono::{DateTime, Utc};
-    use identus_did_prism::dlt::{BlockMetadata, PublishedPrismObject};
+    use identus_apollo::hash::Sha256Digest;
+    use identus_apollo::hex::HexStr;
+    use identus_did_prism::dlt::{BlockHash, BlockMetadata, PublishedPrismObject, TxId};
     use identus_did_prism::prelude::*;
     use identus_did_prism::proto::prism::PrismObject;
     use oura::model::{EventContext, MetadataRecord};
@@ -52,6 +56,48 @@ mod models {
         })
     }
 
+    pub fn parse_oura_tx_hash(context: &EventContext) -> Result<TxId, MetadataReadError> {
+        let block_hash = &context.block_hash;
+        let tx_idx = context.tx_idx;
+        let tx_hash = context
+            .tx_hash
+            .as_ref()
+            .ok_or(MetadataReadError::MissingBlockProperty {
+                block_hash: block_hash.clone(),
+                tx_idx,
+                name: "tx_hash",
+            })?;
+        HexStr::from_str(tx_hash)
+            .ok()
+            .and_then(|hex_str| Sha256Digest::from_bytes(&hex_str.to_bytes()).ok())
+            .map(TxId::from)
+            .ok_or(MetadataReadError::InvalidTxHash {
+                block_hash: block_hash.clone(),
+                tx_idx,
+                tx_hash: tx_hash.clone(),
+            })
+    }
+
+    pub fn parse_oura_block_hash(context: &EventContext) -> Result<BlockHash, MetadataReadError> {
+        let tx_idx = context.tx_idx;
+        let block_hash = context
+            .block_hash
+            .as_ref()
+            .ok_or(MetadataReadError::MissingBlockProperty {
+                block_hash: None,
+                tx_idx,
+                name: "block_hash",
+            })?;
+        HexStr::from_str(block_hash)
+            .ok()
+            .and_then(|hex_str| Sha256Digest::from_bytes(&hex_str.to_bytes()).ok())
+            .map(BlockHash::from)
+            .ok_or(MetadataReadError::InvalidBlockHash {
+                block_hash: block_hash.clone(),
+                tx_idx,
+            })
+    }
+
     pub fn parse_oura_event(
         context: EventContext,
         metadata: MetadataRecord,
@@ -75,6 +121,7 @@ mod models {
                     name: "block_number",
                 })?
                 .into(),
+            block_hash: Some(parse_oura_block_hash(&context)?),
             slot_number: context
                 .slot
                 .ok_or(MetadataReadError::MissingBlockProperty {
@@ -83,6 +130,7 @@ mod models {
                     name: "slot",
                 })?
                 .into(),
+            tx_hash: Some(parse_oura_tx_hash(&context)?),
         };
 
         // parse prism_block
@@ -1,12 +1,17 @@
+use identus_apollo::crypto::EncodeVec;
 use identus_apollo::hash::Sha256Digest;
 use identus_apollo::hex::HexStr;
 use identus_did_prism::did::CanonicalPrismDid;
+use identus_did_prism::did::operation::{ServiceEndpoint, ServiceEndpointValue};
 use identus_did_prism::dlt::OperationMetadata;
 use identus_did_prism::prelude::*;
 use identus_did_prism::proto::prism::prism_operation::Operation;
+use identus_did_prism::protocol::resolver::resolve_published;
 
 use crate::DltSource;
-use crate::repo::{IndexedOperation, OperationRepo};
+use crate::repo::{DidReference, IndexedOperation, OperationRepo};
+
+const REFERENCE_BATCH_SIZE: u32 = 200;
 
 enum IntermediateIndexedOperation {
     Ssi {
@@ -22,13 +27,18 @@ enum IntermediateIndexedOperation {
     },
 }
 
-/// Run indexer loop until no more operation to index
-pub async fn run_indexer_loop<Repo>(repo: &Repo) -> anyhow::Result<()>
+/// Run indexer loop until no more operation to index.
+///
+/// `on_indexed` is invoked with the affected DID after each operation is successfully persisted.
+/// The references of the affected DIDs are refreshed after each batch.
+pub async fn run_indexer_loop<Repo, F>(repo: &Repo, mut on_indexed: F) -> anyhow::Result<()>
 where
     Repo: OperationRepo,
+    F: FnMut(&CanonicalPrismDid),
     <Repo as OperationRepo>::Error: Send + Sync + 'static,
 {
     loop {
+        refresh_did_references(repo).await?;
         let unindexed_operations = repo.get_raw_operations_unindexed().await?;
         if unindexed_operations.is_empty() {
             return Ok(());
@@ -74,11 +84,66 @@ where
                     IndexedOperation::Ignored { raw_operation_id }
                 }
             };
+            let affected_did = indexed_op.did().cloned();
             repo.insert_indexed_operations(vec![indexed_op]).await?;
+            if let Some(did) = affected_did {
+                on_indexed(&did);
+            }
+        }
+    }
+}
+
+/// Set the references of DIDs with newly indexed operations to the ones they currently declare.
+async fn refresh_did_references<Repo>(repo: &Repo) -> anyhow::Result<()>
+where
+    Repo: OperationRepo,
+    <Repo as OperationRepo>::Error: Send + Sync + 'static,
+{
+    loop {
+        let dids = repo.get_dids_with_stale_references(REFERENCE_BATCH_SIZE).await?;
+        if dids.is_empty() {
+            return Ok(());
+        }
+
+        tracing::info!("Refreshing references of {} DIDs", dids.len());
+        for did in dids {
+            let operations = repo
+                .get_raw_operations_by_did(&did)
+                .await?
+                .into_iter()
+                .map(|(_, metadata, signed_operation)| (metadata, signed_operation))
+                .collect();
+            let references = did_references(operations);
+            repo.set_did_references(&did, references).await?;
         }
     }
 }
 
+/// Resolve the DID and collect its public keys and the URLs of its service endpoints.
+fn did_references(operations: Vec<(OperationMetadata, SignedPrismOperation)>) -> Vec<DidReference> {
+    let Some(state) = resolve_published(operations).0 else {
+        return vec![];
+    };
+    let public_keys = state.public_keys.iter().map(|pk| DidReference::PublicKey {
+        key_id: pk.id.to_string(),
+        data: pk.data.encode_vec(),
+    });
+    let service_endpoints = state.services.iter().flat_map(|service| {
+        let endpoints = match &service.service_endpoint {
+            ServiceEndpoint::Value(endpoint) => std::slice::from_ref(endpoint),
+            ServiceEndpoint::List(endpoints) => endpoints.as_slice(),
+        };
+        endpoints.iter().filter_map(|endpoint| match endpoint {
+            ServiceEndpointValue::Uri(url) => Some(DidReference::ServiceEndpoint {
+                service_id: service.id.to_string(),
+                url: url.clone(),
+            }),
+            ServiceEndpointValue::Json(_) => None,
+        })
+    });
+    public_keys.chain(service_endpoints).collect()
+}
+
 /// Run sync loop until DLT source is closed
 pub async fn run_sync_loop<Repo, Src>(repo: &Repo, source: Src) -> anyhow::Result<()>
 where
@@ -122,6 +187,31 @@ where
     Ok(())
 }
 
+/// Find the DID affected by the signed operation.
+///
+/// Storage operations that update or deactivate an entry are traced back to their root
+/// through the indexed operations, so the DID is only found once the parents are indexed.
+pub async fn find_affected_did<Repo>(
+    repo: &Repo,
+    signed_operation: SignedPrismOperation,
+) -> anyhow::Result<Option<CanonicalPrismDid>>
+where
+    Repo: OperationRepo,
+    <Repo as OperationRepo>::Error: Send + Sync + 'static,
+{
+    match index_from_signed_operation(signed_operation) {
+        Ok(IntermediateIndexedOperation::Ssi { did }) => Ok(Some(did)),
+        Ok(IntermediateIndexedOperation::VdrRoot { did, .. }) => Ok(Some(did)),
+        Ok(IntermediateIndexedOperation::VdrChild {
+            prev_operation_hash, ..
+        }) => {
+            let vdr_root = recursively_find_vdr_root(repo, &prev_operation_hash).await?;
+            Ok(vdr_root.map(|(did, _)| did))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
 /// Returns DID that create a root operation and its operation hash
 async fn recursively_find_vdr_root<Repo>(
     repo: &Repo,
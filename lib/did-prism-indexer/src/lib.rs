@@ -7,7 +7,7 @@ pub mod dlt;
 mod indexing;
 pub mod repo;
 
-pub use indexing::{run_indexer_loop, run_sync_loop};
+pub use indexing::{find_affected_did, run_indexer_loop, run_sync_loop};
 
 pub trait DltSource {
     fn sync_cursor(&self) -> watch::Receiver<Option<DltCursor>>;
@@ -0,0 +1,429 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use identus_apollo::hash::Sha256Digest;
+use identus_did_prism::did::CanonicalPrismDid;
+use identus_did_prism::dlt::{BlockNo, DltCursor, OperationMetadata, SlotNo, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::utils::paging::Paginated;
+use uuid::Uuid;
+
+use crate::repo::{DidReference, DltCursorRepo, IndexedOperation, OperationRepo, RawOperationId};
+
+const UNINDEXED_BATCH_SIZE: usize = 200;
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum Error {
+    #[display("raw operation at block {block_number} (absn: {absn}, osn: {osn}) already exists")]
+    DuplicateRawOperation { block_number: u64, absn: u32, osn: u32 },
+    #[display("raw operation {id} does not exist")]
+    RawOperationNotFound { id: Uuid },
+    #[display("raw operation {id} is already indexed")]
+    RawOperationAlreadyIndexed { id: Uuid },
+}
+
+/// Ordering key of a raw operation, equivalent to [`OperationMetadata::compare_time_asc`].
+type OperationOrder = (BlockNo, u32, u32);
+
+struct RawOperationRecord {
+    id: RawOperationId,
+    metadata: OperationMetadata,
+    signed_operation: SignedPrismOperation,
+    indexed: Option<IndexedOperation>,
+}
+
+#[derive(Default)]
+struct State {
+    raw_operations: BTreeMap<OperationOrder, RawOperationRecord>,
+    raw_operation_orders: HashMap<Uuid, OperationOrder>,
+    vdr_operation_hashes: HashMap<Vec<u8>, Uuid>,
+    did_references: HashMap<CanonicalPrismDid, Vec<DidReference>>,
+    stale_reference_dids: Vec<CanonicalPrismDid>,
+    cursor: Option<DltCursor>,
+}
+
+/// An in-memory implementation of [`OperationRepo`] and [`DltCursorRepo`].
+///
+/// It follows the same ordering and indexing semantics as the database-backed repositories
+/// and is intended for tests and for embedding the indexer without a database.
+/// Cloning the repo returns a handle to the same underlying storage.
+#[derive(Clone, Default)]
+pub struct InMemoryRepo {
+    state: Arc<Mutex<State>>,
+}
+
+impl InMemoryRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl RawOperationRecord {
+    fn to_tuple(&self) -> (RawOperationId, OperationMetadata, SignedPrismOperation) {
+        (self.id, self.metadata.clone(), self.signed_operation.clone())
+    }
+
+    fn indexed_did(&self) -> Option<&CanonicalPrismDid> {
+        self.indexed.as_ref().and_then(|i| i.did())
+    }
+}
+
+impl State {
+    /// Collect the DIDs with the ids of their references matched by `f`, ordered by DID.
+    fn find_references<F>(&self, page: u32, page_size: u32, f: F) -> Paginated<(CanonicalPrismDid, String)>
+    where
+        F: Fn(&DidReference) -> Option<&String>,
+    {
+        let mut result = self
+            .did_references
+            .iter()
+            .flat_map(|(did, references)| {
+                references
+                    .iter()
+                    .filter_map(&f)
+                    .map(move |id| (did.clone(), id.clone()))
+            })
+            .collect::<Vec<_>>();
+        result.sort_by(|(a_did, a_id), (b_did, b_id)| {
+            a_did
+                .suffix
+                .as_bytes()
+                .cmp(b_did.suffix.as_bytes())
+                .then_with(|| a_id.cmp(b_id))
+        });
+
+        let total_items = result.len() as u32;
+        let items = result
+            .into_iter()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .collect();
+
+        Paginated {
+            items,
+            current_page: page,
+            page_size,
+            total_items,
+        }
+    }
+}
+
+fn operation_order(metadata: &OperationMetadata) -> OperationOrder {
+    (
+        metadata.block_metadata.block_number,
+        metadata.block_metadata.absn,
+        metadata.osn,
+    )
+}
+
+#[async_trait::async_trait]
+impl OperationRepo for InMemoryRepo {
+    type Error = Error;
+
+    async fn get_last_indexed_block(&self) -> Result<Option<(SlotNo, BlockNo)>, Self::Error> {
+        let state = self.lock();
+        let last_indexed = state
+            .raw_operations
+            .values()
+            .filter(|op| op.indexed.is_some())
+            .max_by_key(|op| op.metadata.block_metadata.slot_number)
+            .map(|op| {
+                (
+                    op.metadata.block_metadata.slot_number,
+                    op.metadata.block_metadata.block_number,
+                )
+            });
+        Ok(last_indexed)
+    }
+
+    async fn get_all_dids(&self, page: u32, page_size: u32) -> Result<Paginated<CanonicalPrismDid>, Self::Error> {
+        let state = self.lock();
+        let mut first_slots: HashMap<&CanonicalPrismDid, SlotNo> = HashMap::new();
+        for op in state.raw_operations.values() {
+            if let Some(did) = op.indexed_did() {
+                let slot = op.metadata.block_metadata.slot_number;
+                first_slots
+                    .entry(did)
+                    .and_modify(|s| *s = (*s).min(slot))
+                    .or_insert(slot);
+            }
+        }
+
+        let mut dids = first_slots.into_iter().collect::<Vec<_>>();
+        dids.sort_by(|(a_did, a_slot), (b_did, b_slot)| {
+            b_slot
+                .cmp(a_slot)
+                .then_with(|| a_did.suffix.as_bytes().cmp(b_did.suffix.as_bytes()))
+        });
+
+        let total_items = dids.len() as u32;
+        let items = dids
+            .into_iter()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .map(|(did, _)| did.clone())
+            .collect();
+
+        Ok(Paginated {
+            items,
+            current_page: page,
+            page_size,
+            total_items,
+        })
+    }
+
+    async fn get_dids_by_suffix_prefix(
+        &self,
+        suffix_prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<CanonicalPrismDid>, Self::Error> {
+        let state = self.lock();
+        let mut dids = state
+            .raw_operations
+            .values()
+            .filter_map(|op| op.indexed_did())
+            .filter(|did| did.suffix_hex().to_string().starts_with(suffix_prefix))
+            .collect::<Vec<_>>();
+        dids.sort_by(|a, b| a.suffix.as_bytes().cmp(b.suffix.as_bytes()));
+        dids.dedup();
+        Ok(dids.into_iter().take(limit as usize).cloned().collect())
+    }
+
+    async fn get_raw_operations(
+        &self,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let state = self.lock();
+        let items = state
+            .raw_operations
+            .values()
+            .rev()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .map(|op| op.to_tuple())
+            .collect();
+        Ok(Paginated {
+            items,
+            current_page: page,
+            page_size,
+            total_items: state.raw_operations.len() as u32,
+        })
+    }
+
+    async fn get_raw_operations_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let state = self.lock();
+        let result = state
+            .raw_operations
+            .values()
+            .filter(|op| op.signed_operation.operation_hash().as_ref() == Some(operation_hash))
+            .map(|op| op.to_tuple())
+            .collect();
+        Ok(result)
+    }
+
+    async fn get_raw_operations_by_tx_hash(
+        &self,
+        tx_hash: &TxId,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let state = self.lock();
+        let result = state
+            .raw_operations
+            .values()
+            .filter(|op| op.metadata.block_metadata.tx_hash.as_ref() == Some(tx_hash))
+            .map(|op| op.to_tuple())
+            .collect();
+        Ok(result)
+    }
+
+    async fn get_raw_operations_unindexed(
+        &self,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let state = self.lock();
+        let result = state
+            .raw_operations
+            .values()
+            .filter(|op| op.indexed.is_none())
+            .take(UNINDEXED_BATCH_SIZE)
+            .map(|op| op.to_tuple())
+            .collect();
+        Ok(result)
+    }
+
+    async fn get_raw_operations_by_did(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let state = self.lock();
+        let result = state
+            .raw_operations
+            .values()
+            .filter(|op| op.indexed_did() == Some(did))
+            .map(|op| op.to_tuple())
+            .collect();
+        Ok(result)
+    }
+
+    async fn get_raw_operation_vdr_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Option<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let state = self.lock();
+        let result = state
+            .vdr_operation_hashes
+            .get(operation_hash.as_bytes())
+            .and_then(|id| state.raw_operation_orders.get(id))
+            .and_then(|order| state.raw_operations.get(order))
+            .map(|op| op.to_tuple());
+        Ok(result)
+    }
+
+    async fn insert_raw_operations(
+        &self,
+        operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    ) -> Result<(), Self::Error> {
+        let mut state = self.lock();
+
+        // validate the whole batch first so the insert is atomic
+        let mut batch_orders = Vec::with_capacity(operations.len());
+        for (metadata, _) in &operations {
+            let order = operation_order(metadata);
+            if state.raw_operations.contains_key(&order) || batch_orders.contains(&order) {
+                let (block_number, absn, osn) = order;
+                Err(Error::DuplicateRawOperation {
+                    block_number: block_number.inner(),
+                    absn,
+                    osn,
+                })?
+            }
+            batch_orders.push(order);
+        }
+
+        for ((metadata, signed_operation), order) in operations.into_iter().zip(batch_orders) {
+            let id = Uuid::new_v4();
+            state.raw_operation_orders.insert(id, order);
+            state.raw_operations.insert(
+                order,
+                RawOperationRecord {
+                    id: id.into(),
+                    metadata,
+                    signed_operation,
+                    indexed: None,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    async fn insert_indexed_operations(&self, operations: Vec<IndexedOperation>) -> Result<(), Self::Error> {
+        let mut state = self.lock();
+
+        // validate the whole batch first so the insert is atomic
+        let mut batch_ids = Vec::with_capacity(operations.len());
+        for op in &operations {
+            let id = *op.raw_operation_id().as_ref();
+            let record = state
+                .raw_operation_orders
+                .get(&id)
+                .and_then(|order| state.raw_operations.get(order))
+                .ok_or(Error::RawOperationNotFound { id })?;
+            if record.indexed.is_some() || batch_ids.contains(&id) {
+                Err(Error::RawOperationAlreadyIndexed { id })?
+            }
+            batch_ids.push(id);
+        }
+
+        for op in operations {
+            let id = *op.raw_operation_id().as_ref();
+            match &op {
+                IndexedOperation::Ssi { did, .. } if !state.stale_reference_dids.contains(did) => {
+                    state.stale_reference_dids.push(did.clone());
+                }
+                IndexedOperation::Vdr { operation_hash, .. } => {
+                    state.vdr_operation_hashes.insert(operation_hash.clone(), id);
+                }
+                _ => (),
+            }
+            let order = state.raw_operation_orders[&id];
+            if let Some(record) = state.raw_operations.get_mut(&order) {
+                record.indexed = Some(op);
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_dids_with_stale_references(&self, limit: u32) -> Result<Vec<CanonicalPrismDid>, Self::Error> {
+        let state = self.lock();
+        Ok(state
+            .stale_reference_dids
+            .iter()
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn set_did_references(
+        &self,
+        did: &CanonicalPrismDid,
+        references: Vec<DidReference>,
+    ) -> Result<(), Self::Error> {
+        let mut state = self.lock();
+        state.stale_reference_dids.retain(|i| i != did);
+        state.did_references.insert(did.clone(), references);
+        Ok(())
+    }
+
+    async fn get_dids_by_public_key(
+        &self,
+        data: &[u8],
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error> {
+        let state = self.lock();
+        let result = state.find_references(page, page_size, |reference| match reference {
+            DidReference::PublicKey { key_id, data: key_data } if key_data == data => Some(key_id),
+            _ => None,
+        });
+        Ok(result)
+    }
+
+    async fn get_dids_by_service_endpoint(
+        &self,
+        url: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error> {
+        let state = self.lock();
+        let result = state.find_references(page, page_size, |reference| match reference {
+            DidReference::ServiceEndpoint {
+                service_id,
+                url: endpoint,
+            } if endpoint == url => Some(service_id),
+            _ => None,
+        });
+        Ok(result)
+    }
+}
+
+#[async_trait::async_trait]
+impl DltCursorRepo for InMemoryRepo {
+    type Error = Error;
+
+    async fn get_cursor(&self) -> Result<Option<DltCursor>, Self::Error> {
+        let state = self.lock();
+        Ok(state.cursor.clone())
+    }
+
+    async fn set_cursor(&self, cursor: DltCursor) -> Result<(), Self::Error> {
+        let mut state = self.lock();
+        state.cursor = Some(DltCursor { cbt: None, ..cursor });
+        Ok(())
+    }
+}
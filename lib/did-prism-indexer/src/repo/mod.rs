@@ -0,0 +1,148 @@
+use identus_apollo::hash::Sha256Digest;
+use identus_did_prism::did::CanonicalPrismDid;
+use identus_did_prism::dlt::{BlockNo, DltCursor, OperationMetadata, SlotNo, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::utils::paging::Paginated;
+use uuid::Uuid;
+
+#[cfg(feature = "in-memory")]
+pub mod memory;
+
+#[derive(Clone, Debug, Copy, derive_more::From, derive_more::Into, derive_more::AsRef)]
+pub struct RawOperationId(Uuid);
+
+pub enum IndexedOperation {
+    Ssi {
+        raw_operation_id: RawOperationId,
+        did: CanonicalPrismDid,
+    },
+    Vdr {
+        raw_operation_id: RawOperationId,
+        operation_hash: Vec<u8>,
+        init_operation_hash: Vec<u8>,
+        prev_operation_hash: Option<Vec<u8>>,
+        did: CanonicalPrismDid,
+    },
+    Ignored {
+        raw_operation_id: RawOperationId,
+    },
+}
+
+impl IndexedOperation {
+    pub fn raw_operation_id(&self) -> &RawOperationId {
+        match self {
+            IndexedOperation::Ssi { raw_operation_id, .. } => raw_operation_id,
+            IndexedOperation::Vdr { raw_operation_id, .. } => raw_operation_id,
+            IndexedOperation::Ignored { raw_operation_id } => raw_operation_id,
+        }
+    }
+
+    pub fn did(&self) -> Option<&CanonicalPrismDid> {
+        match self {
+            IndexedOperation::Ssi { did, .. } => Some(did),
+            IndexedOperation::Vdr { did, .. } => Some(did),
+            IndexedOperation::Ignored { .. } => None,
+        }
+    }
+}
+
+/// Material currently declared by a DID that can be looked up in reverse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DidReference {
+    /// Public key material, using the compressed point for EC keys
+    PublicKey { key_id: String, data: Vec<u8> },
+    /// URL of a service endpoint
+    ServiceEndpoint { service_id: String, url: String },
+}
+
+#[async_trait::async_trait]
+pub trait OperationRepo {
+    type Error: std::error::Error;
+
+    async fn get_last_indexed_block(&self) -> Result<Option<(SlotNo, BlockNo)>, Self::Error>;
+
+    async fn get_all_dids(&self, page: u32, page_size: u32) -> Result<Paginated<CanonicalPrismDid>, Self::Error>;
+
+    /// Get up to `limit` DIDs whose suffix starts with the given lowercase hexadecimal prefix, ordered by suffix.
+    async fn get_dids_by_suffix_prefix(
+        &self,
+        suffix_prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<CanonicalPrismDid>, Self::Error>;
+
+    /// Get a page of raw operations ordered from the latest to the oldest.
+    async fn get_raw_operations(
+        &self,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error>;
+
+    /// Get all raw operations with the given operation hash ordered from the oldest to the latest.
+    ///
+    /// The same operation may be published more than once.
+    async fn get_raw_operations_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error>;
+
+    /// Get all raw operations published in the given Cardano transaction ordered from the oldest to the latest.
+    async fn get_raw_operations_by_tx_hash(
+        &self,
+        tx_hash: &TxId,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error>;
+
+    async fn get_raw_operations_unindexed(
+        &self,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error>;
+
+    async fn get_raw_operations_by_did(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error>;
+
+    async fn get_raw_operation_vdr_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Option<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error>;
+
+    async fn insert_raw_operations(
+        &self,
+        operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    ) -> Result<(), Self::Error>;
+
+    async fn insert_indexed_operations(&self, operations: Vec<IndexedOperation>) -> Result<(), Self::Error>;
+
+    /// Get up to `limit` DIDs with SSI operations indexed after their references were last set.
+    async fn get_dids_with_stale_references(&self, limit: u32) -> Result<Vec<CanonicalPrismDid>, Self::Error>;
+
+    /// Replace the references of the DID with the ones it currently declares.
+    async fn set_did_references(
+        &self,
+        did: &CanonicalPrismDid,
+        references: Vec<DidReference>,
+    ) -> Result<(), Self::Error>;
+
+    /// Get a page of the DIDs currently declaring the public key material along with the key id, ordered by DID.
+    async fn get_dids_by_public_key(
+        &self,
+        data: &[u8],
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error>;
+
+    /// Get a page of the DIDs currently declaring the service endpoint URL along with the service id, ordered by DID.
+    async fn get_dids_by_service_endpoint(
+        &self,
+        url: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error>;
+}
+
+#[async_trait::async_trait]
+pub trait DltCursorRepo {
+    type Error: std::error::Error;
+
+    async fn set_cursor(&self, cursor: DltCursor) -> Result<(), Self::Error>;
+    async fn get_cursor(&self) -> Result<Option<DltCursor>, Self::Error>;
+}
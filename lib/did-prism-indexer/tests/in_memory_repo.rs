@@ -0,0 +1,411 @@
+#![cfg(feature = "in-memory")]
+
+use chrono::DateTime;
+use identus_apollo::crypto::EncodeVec;
+use identus_apollo::crypto::ed25519::Ed25519PrivateKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_apollo::hash::{Sha256Digest, sha256};
+use identus_did_prism::did::CanonicalPrismDid;
+use identus_did_prism::did::operation::builder::{
+    CreateDidOperationBuilder, UpdateDidOperationBuilder, sign_operation,
+};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+use identus_did_prism::dlt::{BlockHash, BlockMetadata, DltCursor, OperationMetadata, PublishedPrismObject, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::proto;
+use identus_did_prism::protocol::resolver::resolve_published;
+use identus_did_prism_indexer::repo::OperationRepo;
+use identus_did_prism_indexer::repo::memory::InMemoryRepo;
+use identus_did_prism_indexer::{DltSource, find_affected_did, run_indexer_loop, run_sync_loop};
+use tokio::sync::{mpsc, watch};
+
+struct ChannelSource {
+    rx: mpsc::Receiver<PublishedPrismObject>,
+    cursor_rx: watch::Receiver<Option<DltCursor>>,
+}
+
+impl DltSource for ChannelSource {
+    fn sync_cursor(&self) -> watch::Receiver<Option<DltCursor>> {
+        self.cursor_rx.clone()
+    }
+
+    fn into_stream(self) -> Result<mpsc::Receiver<PublishedPrismObject>, String> {
+        Ok(self.rx)
+    }
+}
+
+fn new_signed_operation(operation: proto::prism::prism_operation::Operation) -> (SignedPrismOperation, Sha256Digest) {
+    let operation = PrismOperation {
+        operation: Some(operation),
+        special_fields: Default::default(),
+    };
+    let operation_hash = operation.operation_hash();
+    let signed_operation = SignedPrismOperation {
+        signed_with: "master-0".to_string(),
+        signature: vec![],
+        operation: Some(operation).into(),
+        special_fields: Default::default(),
+    };
+    (signed_operation, operation_hash)
+}
+
+fn block_metadata(block_number: u64) -> BlockMetadata {
+    BlockMetadata {
+        slot_number: (block_number * 10).into(),
+        block_number: block_number.into(),
+        block_hash: Some(BlockHash::from(sha256(format!("block-{block_number}")))),
+        cbt: DateTime::UNIX_EPOCH,
+        absn: 0,
+        tx_hash: Some(tx_hash(block_number)),
+    }
+}
+
+fn tx_hash(block_number: u64) -> TxId {
+    sha256(block_number.to_be_bytes()).into()
+}
+
+fn metadata(block_number: u64, osn: u32) -> OperationMetadata {
+    OperationMetadata {
+        block_metadata: block_metadata(block_number),
+        osn,
+    }
+}
+
+#[tokio::test]
+async fn index_did_and_storage_operations() {
+    let repo = InMemoryRepo::new();
+
+    let (create_did_op, _) = new_signed_operation(proto::prism::prism_operation::Operation::CreateDid(
+        proto::prism_ssi::ProtoCreateDID::default(),
+    ));
+    let did = CanonicalPrismDid::from_operation(create_did_op.operation.as_ref().unwrap()).unwrap();
+    let (create_storage_op, create_storage_hash) = new_signed_operation(
+        proto::prism::prism_operation::Operation::CreateStorageEntry(proto::prism_storage::ProtoCreateStorageEntry {
+            did_prism_hash: did.suffix.to_vec(),
+            ..Default::default()
+        }),
+    );
+    let (update_storage_op, _) = new_signed_operation(proto::prism::prism_operation::Operation::UpdateStorageEntry(
+        proto::prism_storage::ProtoUpdateStorageEntry {
+            previous_event_hash: create_storage_hash.to_vec(),
+            ..Default::default()
+        },
+    ));
+
+    // insert in reverse order to check that ordering is based on the metadata
+    repo.insert_raw_operations(vec![(metadata(2, 0), update_storage_op)])
+        .await
+        .unwrap();
+    repo.insert_raw_operations(vec![
+        (metadata(1, 0), create_did_op),
+        (metadata(1, 1), create_storage_op),
+    ])
+    .await
+    .unwrap();
+
+    let mut indexed_dids = vec![];
+    run_indexer_loop(&repo, |did| indexed_dids.push(did.clone()))
+        .await
+        .unwrap();
+
+    assert_eq!(indexed_dids, vec![did.clone(), did.clone(), did.clone()]);
+    assert!(repo.get_raw_operations_unindexed().await.unwrap().is_empty());
+
+    let operations = repo.get_raw_operations_by_did(&did).await.unwrap();
+    let osns = operations
+        .iter()
+        .map(|(_, meta, _)| (meta.block_metadata.block_number.inner(), meta.osn))
+        .collect::<Vec<_>>();
+    assert_eq!(osns, vec![(1, 0), (1, 1), (2, 0)]);
+
+    let vdr_root = repo
+        .get_raw_operation_vdr_by_operation_hash(&create_storage_hash)
+        .await
+        .unwrap();
+    assert!(vdr_root.is_some());
+
+    let last_indexed = repo.get_last_indexed_block().await.unwrap().unwrap();
+    assert_eq!(last_indexed, (20.into(), 2.into()));
+
+    let dids = repo.get_all_dids(0, 10).await.unwrap();
+    assert_eq!(dids.items, vec![did]);
+    assert_eq!(dids.total_items, 1);
+}
+
+#[tokio::test]
+async fn find_affected_did_of_storage_operations() {
+    let repo = InMemoryRepo::new();
+
+    let (create_did_op, _) = new_signed_operation(proto::prism::prism_operation::Operation::CreateDid(
+        proto::prism_ssi::ProtoCreateDID::default(),
+    ));
+    let did = CanonicalPrismDid::from_operation(create_did_op.operation.as_ref().unwrap()).unwrap();
+    let (create_storage_op, create_storage_hash) = new_signed_operation(
+        proto::prism::prism_operation::Operation::CreateStorageEntry(proto::prism_storage::ProtoCreateStorageEntry {
+            did_prism_hash: did.suffix.to_vec(),
+            ..Default::default()
+        }),
+    );
+    let (update_storage_op, _) = new_signed_operation(proto::prism::prism_operation::Operation::UpdateStorageEntry(
+        proto::prism_storage::ProtoUpdateStorageEntry {
+            previous_event_hash: create_storage_hash.to_vec(),
+            ..Default::default()
+        },
+    ));
+
+    let affected_did = find_affected_did(&repo, create_did_op.clone()).await.unwrap();
+    assert_eq!(affected_did, Some(did.clone()));
+    let affected_did = find_affected_did(&repo, create_storage_op.clone()).await.unwrap();
+    assert_eq!(affected_did, Some(did.clone()));
+
+    // parent storage operation is not yet indexed
+    let affected_did = find_affected_did(&repo, update_storage_op.clone()).await.unwrap();
+    assert_eq!(affected_did, None);
+
+    repo.insert_raw_operations(vec![
+        (metadata(1, 0), create_did_op),
+        (metadata(1, 1), create_storage_op),
+    ])
+    .await
+    .unwrap();
+    run_indexer_loop(&repo, |_| {}).await.unwrap();
+
+    let affected_did = find_affected_did(&repo, update_storage_op).await.unwrap();
+    assert_eq!(affected_did, Some(did));
+}
+
+#[tokio::test]
+async fn insert_duplicate_raw_operations_is_atomic() {
+    let repo = InMemoryRepo::new();
+    let (op, _) = new_signed_operation(proto::prism::prism_operation::Operation::CreateDid(
+        proto::prism_ssi::ProtoCreateDID::default(),
+    ));
+
+    repo.insert_raw_operations(vec![(metadata(1, 0), op.clone())])
+        .await
+        .unwrap();
+    let result = repo
+        .insert_raw_operations(vec![(metadata(1, 1), op.clone()), (metadata(1, 0), op)])
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(repo.get_raw_operations_unindexed().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn get_raw_operations_latest_first() {
+    let repo = InMemoryRepo::new();
+    let (create_did_op, create_did_hash) = new_signed_operation(proto::prism::prism_operation::Operation::CreateDid(
+        proto::prism_ssi::ProtoCreateDID::default(),
+    ));
+    let (other_op, _) = new_signed_operation(proto::prism::prism_operation::Operation::CreateDid(
+        proto::prism_ssi::ProtoCreateDID {
+            did_data: Some(Default::default()).into(),
+            ..Default::default()
+        },
+    ));
+
+    // the same operation is published twice
+    repo.insert_raw_operations(vec![
+        (metadata(1, 0), create_did_op.clone()),
+        (metadata(1, 1), other_op),
+        (metadata(2, 0), create_did_op),
+    ])
+    .await
+    .unwrap();
+
+    let page = repo.get_raw_operations(0, 2).await.unwrap();
+    let orders = page
+        .items
+        .iter()
+        .map(|(_, meta, _)| (meta.block_metadata.block_number.inner(), meta.osn))
+        .collect::<Vec<_>>();
+    assert_eq!(orders, vec![(2, 0), (1, 1)]);
+    assert_eq!(page.total_items, 3);
+    assert_eq!(repo.get_raw_operations(1, 2).await.unwrap().items.len(), 1);
+
+    let operations = repo
+        .get_raw_operations_by_operation_hash(&create_did_hash)
+        .await
+        .unwrap();
+    let orders = operations
+        .iter()
+        .map(|(_, meta, _)| meta.block_metadata.block_number.inner())
+        .collect::<Vec<_>>();
+    assert_eq!(orders, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn search_dids_and_raw_operations() {
+    let repo = InMemoryRepo::new();
+    let (create_did_op, _) = new_signed_operation(proto::prism::prism_operation::Operation::CreateDid(
+        proto::prism_ssi::ProtoCreateDID::default(),
+    ));
+    let did = CanonicalPrismDid::from_operation(create_did_op.operation.as_ref().unwrap()).unwrap();
+    let (update_did_op, _) = new_signed_operation(proto::prism::prism_operation::Operation::UpdateDid(
+        proto::prism_ssi::ProtoUpdateDID {
+            id: did.suffix_hex().to_string(),
+            previous_operation_hash: vec![0xca, 0xfe, 0xba, 0xbe],
+            ..Default::default()
+        },
+    ));
+
+    repo.insert_raw_operations(vec![(metadata(1, 0), create_did_op), (metadata(2, 0), update_did_op)])
+        .await
+        .unwrap();
+    run_indexer_loop(&repo, |_| {}).await.unwrap();
+
+    let suffix = did.suffix_hex().to_string();
+    for prefix in ["", &suffix[..1], &suffix[..5], &suffix] {
+        let dids = repo.get_dids_by_suffix_prefix(prefix, 10).await.unwrap();
+        assert_eq!(dids, vec![did.clone()]);
+    }
+    let other_prefix = if suffix.starts_with('0') { "1" } else { "0" };
+    assert!(
+        repo.get_dids_by_suffix_prefix(other_prefix, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let operations = repo.get_raw_operations_by_tx_hash(&tx_hash(2)).await.unwrap();
+    assert_eq!(operations.len(), 1);
+    assert_eq!(operations[0].1, metadata(2, 0));
+    assert!(
+        repo.get_raw_operations_by_tx_hash(&tx_hash(3))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn index_did_references() {
+    let repo = InMemoryRepo::new();
+    let master_sk = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap();
+    let shared_pk = Ed25519PrivateKey::from_slice(&[2; 32]).unwrap().to_public_key();
+    let create_did = |seed: &str| {
+        let operation = CreateDidOperationBuilder::new()
+            .add_key(
+                "master-0",
+                KeyUsage::MasterKey,
+                NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+            )
+            .add_key(
+                "auth-0",
+                KeyUsage::AuthenticationKey,
+                NonOperationPublicKey::Ed25519(shared_pk.clone()),
+            )
+            .add_service("service-0", "LinkedDomains", "https://example.com")
+            .add_context(seed)
+            .build();
+        sign_operation(operation, "master-0", &master_sk)
+    };
+    let create_did_op_1 = create_did("https://example.com/1");
+    let create_did_op_2 = create_did("https://example.com/2");
+    let did_1 = CanonicalPrismDid::from_operation(create_did_op_1.operation.as_ref().unwrap()).unwrap();
+    let did_2 = CanonicalPrismDid::from_operation(create_did_op_2.operation.as_ref().unwrap()).unwrap();
+
+    repo.insert_raw_operations(vec![
+        (metadata(1, 0), create_did_op_1.clone()),
+        (metadata(1, 1), create_did_op_2),
+    ])
+    .await
+    .unwrap();
+    run_indexer_loop(&repo, |_| {}).await.unwrap();
+
+    let mut expected = vec![
+        (did_1.clone(), "auth-0".to_string()),
+        (did_2.clone(), "auth-0".to_string()),
+    ];
+    expected.sort_by(|a, b| a.0.suffix.as_bytes().cmp(b.0.suffix.as_bytes()));
+    let dids = repo
+        .get_dids_by_public_key(&shared_pk.encode_vec(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items, expected);
+    assert_eq!(dids.total_items, 2);
+    let dids = repo
+        .get_dids_by_public_key(&master_sk.to_public_key().encode_compressed(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items.len(), 2);
+    let dids = repo
+        .get_dids_by_service_endpoint("https://example.com", 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items.len(), 2);
+    let dids = repo
+        .get_dids_by_public_key(&shared_pk.encode_vec(), 1, 1)
+        .await
+        .unwrap();
+    assert_eq!(dids.items, expected[1..]);
+    assert_eq!(dids.total_pages(), 2);
+    assert!(repo.get_dids_with_stale_references(10).await.unwrap().is_empty());
+
+    // references removed by an update are no longer found
+    let state = resolve_published(vec![(metadata(1, 0), create_did_op_1)]).0.unwrap();
+    let update_did_op = UpdateDidOperationBuilder::new(&state)
+        .remove_key("auth-0")
+        .remove_service("service-0")
+        .build();
+    repo.insert_raw_operations(vec![(
+        metadata(2, 0),
+        sign_operation(update_did_op, "master-0", &master_sk),
+    )])
+    .await
+    .unwrap();
+    run_indexer_loop(&repo, |_| {}).await.unwrap();
+
+    let dids = repo
+        .get_dids_by_public_key(&shared_pk.encode_vec(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items, vec![(did_2.clone(), "auth-0".to_string())]);
+    let dids = repo
+        .get_dids_by_service_endpoint("https://example.com", 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items, vec![(did_2, "service-0".to_string())]);
+    assert!(
+        repo.get_dids_by_service_endpoint("https://example.org", 0, 10)
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn sync_loop_inserts_operations_with_sequence_number() {
+    let repo = InMemoryRepo::new();
+    let (tx, rx) = mpsc::channel(8);
+    let (_cursor_tx, cursor_rx) = watch::channel(None);
+    let source = ChannelSource { rx, cursor_rx };
+
+    let (op, _) = new_signed_operation(proto::prism::prism_operation::Operation::CreateDid(
+        proto::prism_ssi::ProtoCreateDID::default(),
+    ));
+    let empty_op = SignedPrismOperation::default();
+    let prism_object = proto::prism::PrismObject {
+        block_content: Some(proto::prism::PrismBlock {
+            operations: vec![empty_op, op],
+            ..Default::default()
+        })
+        .into(),
+        ..Default::default()
+    };
+    tx.send(PublishedPrismObject {
+        block_metadata: block_metadata(1),
+        prism_object,
+    })
+    .await
+    .unwrap();
+    drop(tx);
+
+    run_sync_loop(&repo, source).await.unwrap();
+
+    let operations = repo.get_raw_operations_unindexed().await.unwrap();
+    assert_eq!(operations.len(), 1);
+    assert_eq!(operations[0].1, metadata(1, 1));
+}
@@ -1,16 +1,132 @@
+use chrono::{DateTime, Utc};
+use identus_apollo::hex::HexStr;
 use identus_apollo::jwk::EncodeJwk;
+use identus_apollo::multikey::EncodeMultikey;
 use identus_did_core::{
     Did, DidDocument, Service, ServiceEndpoint, ServiceType, StringOrMap, VerificationMethod, VerificationMethodOrRef,
 };
 
-use crate::did::operation::KeyUsage;
+use crate::did::operation::{KeyUsage, NonOperationPublicKey};
 use crate::did::{DidState, operation};
+use crate::dlt::{BlockHash, TxId};
+use crate::protocol::resolver::ResolutionDebug;
+
+const DID_RESOLUTION_CONTEXT: &str = "https://w3id.org/did-resolution/v1";
+const DID_LD_JSON_CONTENT_TYPE: &str = "application/did+ld+json";
+
+/// The type and public key representation of the verification methods in a DID document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum VerificationMethodFormat {
+    /// `JsonWebKey2020` with `publicKeyJwk`
+    #[default]
+    JsonWebKey2020,
+    /// `Multikey` with `publicKeyMultibase`
+    Multikey,
+    /// `EcdsaSecp256k1VerificationKey2019` with `publicKeyJwk` for secp256k1 keys,
+    /// `Ed25519VerificationKey2020` with `publicKeyMultibase` for Ed25519 keys,
+    /// `X25519KeyAgreementKey2020` with `publicKeyMultibase` for X25519 keys and
+    /// `Multikey` with `publicKeyMultibase` for NIST curve keys
+    KeyTypeSpecific,
+}
+
+/// The result of resolving a DID as defined by [DID Resolution](https://w3c.github.io/did-resolution/).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DidResolutionResult {
+    #[serde(rename = "@context")]
+    pub context: String,
+    pub did_document: DidDocument,
+    pub did_document_metadata: DidDocumentMetadata,
+    pub did_resolution_metadata: DidResolutionMetadata,
+}
+
+/// The metadata of a resolved DID document.
+///
+/// Timestamps and Cardano hashes are only available for published DIDs,
+/// and the hashes are not available for operations indexed before they were recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocumentMetadata {
+    /// Hexadecimal hash of the last operation applied to the DID
+    pub version_id: String,
+    /// Block timestamp of the operation that created the DID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<DateTime<Utc>>,
+    /// Block timestamp of the last operation that updated the DID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<DateTime<Utc>>,
+    /// Cardano transaction hash of the operation that created the DID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_tx_hash: Option<TxId>,
+    /// Cardano block hash of the operation that created the DID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_block_hash: Option<BlockHash>,
+    /// Cardano transaction hash of the last operation that updated the DID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_tx_hash: Option<TxId>,
+    /// Cardano block hash of the last operation that updated the DID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_block_hash: Option<BlockHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DidResolutionMetadata {
+    pub content_type: String,
+}
 
 impl DidState {
+    /// Build the DID resolution result from the resolved state and the operations it is resolved from.
+    ///
+    /// The operations are empty for an unpublished DID.
+    pub fn to_resolution_result(
+        &self,
+        did: &Did,
+        format: VerificationMethodFormat,
+        operations: &ResolutionDebug,
+    ) -> DidResolutionResult {
+        DidResolutionResult {
+            context: DID_RESOLUTION_CONTEXT.to_string(),
+            did_document: self.to_did_document_with_format(did, format),
+            did_document_metadata: self.to_did_document_metadata(operations),
+            did_resolution_metadata: DidResolutionMetadata {
+                content_type: DID_LD_JSON_CONTENT_TYPE.to_string(),
+            },
+        }
+    }
+
+    fn to_did_document_metadata(&self, operations: &ResolutionDebug) -> DidDocumentMetadata {
+        let mut applied = operations
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, error))| error.is_none());
+        // the first applied operation is the one that created the DID
+        let created = applied.clone().next();
+        let updated = applied
+            .rfind(|(_, (_, op, _))| op.operation_hash().as_ref() == Some(&*self.last_operation_hash))
+            .filter(|(i, _)| Some(*i) != created.map(|(i, _)| i));
+        let created = created.map(|(_, (metadata, _, _))| &metadata.block_metadata);
+        let updated = updated.map(|(_, (metadata, _, _))| &metadata.block_metadata);
+        DidDocumentMetadata {
+            version_id: HexStr::from(self.last_operation_hash.as_bytes()).to_string(),
+            created: created.map(|i| i.cbt),
+            updated: updated.map(|i| i.cbt),
+            created_tx_hash: created.and_then(|i| i.tx_hash.clone()),
+            created_block_hash: created.and_then(|i| i.block_hash.clone()),
+            updated_tx_hash: updated.and_then(|i| i.tx_hash.clone()),
+            updated_block_hash: updated.and_then(|i| i.block_hash.clone()),
+        }
+    }
+
     pub fn to_did_document(&self, did: &Did) -> DidDocument {
-        let mut context = vec!["https://www.w3.org/ns/did/v1".to_string()];
-        context.extend(self.context.clone());
+        self.to_did_document_with_format(did, VerificationMethodFormat::default())
+    }
 
+    pub fn to_did_document_with_format(&self, did: &Did, format: VerificationMethodFormat) -> DidDocument {
         let get_relationship = |usage: KeyUsage| -> Vec<VerificationMethodOrRef> {
             self.public_keys
                 .iter()
@@ -31,8 +147,19 @@ impl DidState {
                 ];
                 W3C_KEY_TYPES.iter().any(|usage| usage == &k.data.usage())
             })
-            .flat_map(|k| transform_key_jwk(did, k))
-            .collect();
+            .flat_map(|k| transform_key(did, k, format))
+            .collect::<Vec<_>>();
+
+        let mut context = vec!["https://www.w3.org/ns/did/v1".to_string()];
+        for vm in &verification_method {
+            if let Some(vm_context) = verification_method_context(&vm.r#type)
+                && !context.iter().any(|c| c == vm_context)
+            {
+                context.push(vm_context.to_string());
+            }
+        }
+        context.extend(self.context.clone());
+
         DidDocument {
             context,
             id: did.clone(),
@@ -47,19 +174,50 @@ impl DidState {
     }
 }
 
-fn transform_key_jwk(did: &Did, key: &operation::PublicKey) -> Option<VerificationMethod> {
-    match &key.data {
+fn transform_key(
+    did: &Did,
+    key: &operation::PublicKey,
+    format: VerificationMethodFormat,
+) -> Option<VerificationMethod> {
+    let data = match &key.data {
         operation::PublicKeyData::Master { .. } => None,
         operation::PublicKeyData::Vdr { .. } => None,
-        operation::PublicKeyData::Other { data, .. } => {
-            let jwk = data.encode_jwk();
-            Some(VerificationMethod {
-                id: format!("{}#{}", did, key.id),
-                r#type: "JsonWebKey2020".to_string(),
-                controller: did.to_string(),
-                public_key_jwk: Some(jwk),
-            })
+        operation::PublicKeyData::Other { data, .. } => Some(data),
+    }?;
+    let (r#type, public_key_jwk, public_key_multibase) = match (format, data) {
+        (VerificationMethodFormat::JsonWebKey2020, _) => ("JsonWebKey2020", Some(data.encode_jwk()), None),
+        (VerificationMethodFormat::Multikey, _) => ("Multikey", None, Some(data.encode_multikey())),
+        (VerificationMethodFormat::KeyTypeSpecific, NonOperationPublicKey::Secp256k1(_)) => {
+            ("EcdsaSecp256k1VerificationKey2019", Some(data.encode_jwk()), None)
+        }
+        (VerificationMethodFormat::KeyTypeSpecific, NonOperationPublicKey::Ed25519(_)) => {
+            ("Ed25519VerificationKey2020", None, Some(data.encode_multikey()))
+        }
+        (VerificationMethodFormat::KeyTypeSpecific, NonOperationPublicKey::X25519(_)) => {
+            ("X25519KeyAgreementKey2020", None, Some(data.encode_multikey()))
         }
+        (
+            VerificationMethodFormat::KeyTypeSpecific,
+            NonOperationPublicKey::P256(_) | NonOperationPublicKey::P384(_),
+        ) => ("Multikey", None, Some(data.encode_multikey())),
+    };
+    Some(VerificationMethod {
+        id: format!("{}#{}", did, key.id),
+        r#type: r#type.to_string(),
+        controller: did.to_string(),
+        public_key_jwk,
+        public_key_multibase,
+    })
+}
+
+/// The JSON-LD context defining the verification method type, if it is not part of the DID core context.
+fn verification_method_context(r#type: &str) -> Option<&'static str> {
+    match r#type {
+        "Multikey" => Some("https://w3id.org/security/multikey/v1"),
+        "EcdsaSecp256k1VerificationKey2019" => Some("https://w3id.org/security/suites/secp256k1-2019/v1"),
+        "Ed25519VerificationKey2020" => Some("https://w3id.org/security/suites/ed25519-2020/v1"),
+        "X25519KeyAgreementKey2020" => Some("https://w3id.org/security/suites/x25519-2020/v1"),
+        _ => None,
     }
 }
 
@@ -20,8 +20,10 @@ use crate::proto::prism::prism_operation::Operation;
 pub mod did_doc;
 pub mod error;
 pub mod operation;
+pub mod resolver;
 
 pub use error::Error;
+pub use resolver::{DidResolver, LongFormResolver};
 
 static CANONICAL_SUFFIX_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^([0-9a-f]{64}$)").expect("CANONICAL_SUFFIX_RE regex is invalid"));
@@ -239,24 +241,26 @@ impl From<DidState> for node_api::DIDData {
             public_keys: value.public_keys.into_iter().map(|i| i.orig).collect(),
             services: value.services.into_iter().map(|i| i.orig).collect(),
             context: value.context,
-            storage_data: value.storage.into_iter().flat_map(|i| i.try_into().ok()).collect(),
+            storage_data: value.storage.into_iter().map(|i| i.into()).collect(),
             special_fields: Default::default(),
         }
     }
 }
 
-impl TryFrom<StorageState> for node_api::StorageData {
-    type Error = &'static str;
-
-    fn try_from(value: StorageState) -> Result<Self, Self::Error> {
-        match value.data.as_ref() {
-            StorageData::Bytes(items) => Ok(Self {
-                init_operation_hash: value.init_operation_hash.to_vec(),
-                prev_operation_hash: value.last_operation_hash.to_vec(),
-                data: Some(node_api::storage_data::Data::Bytes(items.clone())),
-                special_fields: Default::default(),
-            }),
-            _ => Err("only bytes is supported in DIDData"),
+impl From<StorageState> for node_api::StorageData {
+    fn from(value: StorageState) -> Self {
+        let data = match value.data.as_ref() {
+            StorageData::Bytes(bytes) => node_api::storage_data::Data::Bytes(bytes.clone()),
+            StorageData::Ipfs(cid) => node_api::storage_data::Data::Ipfs(cid.clone()),
+            StorageData::StatusList(status_list) => {
+                node_api::storage_data::Data::StatusListEntry(status_list.clone().into())
+            }
+        };
+        Self {
+            init_operation_hash: value.init_operation_hash.to_vec(),
+            prev_operation_hash: value.last_operation_hash.to_vec(),
+            data: Some(data),
+            special_fields: Default::default(),
         }
     }
 }
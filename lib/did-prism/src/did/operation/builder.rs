@@ -0,0 +1,373 @@
+//! Builders for constructing and signing PRISM operations.
+//!
+//! # Example
+//!
+//! ```
+//! use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+//! use identus_did_prism::did::CanonicalPrismDid;
+//! use identus_did_prism::did::operation::builder::{CreateDidOperationBuilder, sign_operation};
+//! use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+//!
+//! let master_sk = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap();
+//! let operation = CreateDidOperationBuilder::new()
+//!     .add_key(
+//!         "master-0",
+//!         KeyUsage::MasterKey,
+//!         NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+//!     )
+//!     .add_service("service-0", "LinkedDomains", "https://example.com")
+//!     .build();
+//! let did = CanonicalPrismDid::from_operation(&operation).unwrap();
+//! let signed_operation = sign_operation(operation, "master-0", &master_sk);
+//! assert_eq!(signed_operation.signed_with, "master-0");
+//! ```
+
+use identus_apollo::crypto::EncodeVec;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+
+use crate::did::operation::{
+    KeyUsage, NonOperationPublicKey, StatusListData, StatusListUpdate, StorageData, StorageUpdate,
+};
+use crate::did::{CanonicalPrismDid, DidState, PrismDidOps, StorageState};
+use crate::prelude::*;
+use crate::proto::prism::prism_operation::Operation;
+use crate::proto::prism_ssi::proto_create_did::DIDCreationData;
+use crate::proto::prism_ssi::public_key::Key_data;
+use crate::proto::prism_ssi::update_didaction::Action;
+use crate::proto::prism_ssi::{
+    AddKeyAction, AddServiceAction, CompressedECKeyData, PatchContextAction, ProtoCreateDID, ProtoDeactivateDID,
+    ProtoUpdateDID, RemoveKeyAction, RemoveServiceAction, UpdateDIDAction, UpdateServiceAction,
+};
+use crate::proto::prism_storage::{ProtoCreateStorageEntry, ProtoDeactivateStorageEntry, ProtoUpdateStorageEntry};
+use crate::proto::{self, MessageExt};
+
+/// Sign the operation with the given key and wrap it in a [`SignedPrismOperation`].
+///
+/// The `key_id` must refer to a master key for DID operations or a VDR key for storage operations.
+pub fn sign_operation(
+    operation: PrismOperation,
+    key_id: &str,
+    signing_key: &Secp256k1PrivateKey,
+) -> SignedPrismOperation {
+    let signature = signing_key.sign(&operation.encode_to_vec());
+    SignedPrismOperation {
+        signed_with: key_id.to_string(),
+        signature,
+        operation: Some(operation).into(),
+        special_fields: Default::default(),
+    }
+}
+
+fn new_operation(operation: Operation) -> PrismOperation {
+    PrismOperation {
+        operation: Some(operation),
+        special_fields: Default::default(),
+    }
+}
+
+fn new_public_key(id: &str, usage: KeyUsage, public_key: &NonOperationPublicKey) -> proto::prism_ssi::PublicKey {
+    let (curve, data) = match public_key {
+        NonOperationPublicKey::Secp256k1(pk) => ("secp256k1", pk.encode_compressed().to_vec()),
+        NonOperationPublicKey::Ed25519(pk) => ("Ed25519", pk.encode_vec()),
+        NonOperationPublicKey::X25519(pk) => ("X25519", pk.encode_vec()),
+        NonOperationPublicKey::P256(pk) => ("P-256", pk.encode_vec()),
+        NonOperationPublicKey::P384(pk) => ("P-384", pk.encode_vec()),
+    };
+    proto::prism_ssi::PublicKey {
+        id: id.to_string(),
+        usage: proto_key_usage(usage).into(),
+        key_data: Some(Key_data::CompressedEcKeyData(CompressedECKeyData {
+            curve: curve.to_string(),
+            data,
+            special_fields: Default::default(),
+        })),
+        special_fields: Default::default(),
+    }
+}
+
+fn new_service(id: &str, r#type: &str, service_endpoint: &str) -> proto::prism_ssi::Service {
+    proto::prism_ssi::Service {
+        id: id.to_string(),
+        type_: r#type.to_string(),
+        service_endpoint: service_endpoint.to_string(),
+        special_fields: Default::default(),
+    }
+}
+
+fn proto_key_usage(usage: KeyUsage) -> proto::prism_ssi::KeyUsage {
+    match usage {
+        KeyUsage::MasterKey => proto::prism_ssi::KeyUsage::MASTER_KEY,
+        KeyUsage::IssuingKey => proto::prism_ssi::KeyUsage::ISSUING_KEY,
+        KeyUsage::KeyAgreementKey => proto::prism_ssi::KeyUsage::KEY_AGREEMENT_KEY,
+        KeyUsage::AuthenticationKey => proto::prism_ssi::KeyUsage::AUTHENTICATION_KEY,
+        KeyUsage::RevocationKey => proto::prism_ssi::KeyUsage::REVOCATION_KEY,
+        KeyUsage::CapabilityInvocationKey => proto::prism_ssi::KeyUsage::CAPABILITY_INVOCATION_KEY,
+        KeyUsage::CapabilityDelegationKey => proto::prism_ssi::KeyUsage::CAPABILITY_DELEGATION_KEY,
+        KeyUsage::VdrKey => proto::prism_ssi::KeyUsage::VDR_KEY,
+    }
+}
+
+/// Builder for a `CreateDID` operation.
+///
+/// Service endpoints are given in their serialized form (a URI, a JSON array or a JSON object).
+#[derive(Debug, Clone, Default)]
+pub struct CreateDidOperationBuilder {
+    public_keys: Vec<proto::prism_ssi::PublicKey>,
+    services: Vec<proto::prism_ssi::Service>,
+    context: Vec<String>,
+}
+
+impl CreateDidOperationBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_key(mut self, id: &str, usage: KeyUsage, public_key: NonOperationPublicKey) -> Self {
+        self.public_keys.push(new_public_key(id, usage, &public_key));
+        self
+    }
+
+    pub fn add_service(mut self, id: &str, r#type: &str, service_endpoint: &str) -> Self {
+        self.services.push(new_service(id, r#type, service_endpoint));
+        self
+    }
+
+    pub fn add_context(mut self, context: &str) -> Self {
+        self.context.push(context.to_string());
+        self
+    }
+
+    pub fn build(self) -> PrismOperation {
+        new_operation(Operation::CreateDid(ProtoCreateDID {
+            did_data: Some(DIDCreationData {
+                public_keys: self.public_keys,
+                services: self.services,
+                context: self.context,
+                special_fields: Default::default(),
+            })
+            .into(),
+            special_fields: Default::default(),
+        }))
+    }
+}
+
+/// Builder for an `UpdateDID` operation.
+///
+/// The `previous_operation_hash` is taken from the latest [`DidState`].
+#[derive(Debug, Clone)]
+pub struct UpdateDidOperationBuilder {
+    id: String,
+    previous_operation_hash: Vec<u8>,
+    actions: Vec<Action>,
+}
+
+impl UpdateDidOperationBuilder {
+    pub fn new(did_state: &DidState) -> Self {
+        Self {
+            id: did_state.did.suffix_hex().to_string(),
+            previous_operation_hash: did_state.last_operation_hash.to_vec(),
+            actions: vec![],
+        }
+    }
+
+    pub fn add_key(mut self, id: &str, usage: KeyUsage, public_key: NonOperationPublicKey) -> Self {
+        self.actions.push(Action::AddKey(AddKeyAction {
+            key: Some(new_public_key(id, usage, &public_key)).into(),
+            special_fields: Default::default(),
+        }));
+        self
+    }
+
+    pub fn remove_key(mut self, id: &str) -> Self {
+        self.actions.push(Action::RemoveKey(RemoveKeyAction {
+            keyId: id.to_string(),
+            special_fields: Default::default(),
+        }));
+        self
+    }
+
+    pub fn add_service(mut self, id: &str, r#type: &str, service_endpoint: &str) -> Self {
+        self.actions.push(Action::AddService(AddServiceAction {
+            service: Some(new_service(id, r#type, service_endpoint)).into(),
+            special_fields: Default::default(),
+        }));
+        self
+    }
+
+    pub fn remove_service(mut self, id: &str) -> Self {
+        self.actions.push(Action::RemoveService(RemoveServiceAction {
+            serviceId: id.to_string(),
+            special_fields: Default::default(),
+        }));
+        self
+    }
+
+    /// Update the type and/or the endpoints of an existing service.
+    /// At least one of `r#type` or `service_endpoint` must be provided for the operation to be valid.
+    pub fn update_service(mut self, id: &str, r#type: Option<&str>, service_endpoint: Option<&str>) -> Self {
+        self.actions.push(Action::UpdateService(UpdateServiceAction {
+            serviceId: id.to_string(),
+            type_: r#type.unwrap_or_default().to_string(),
+            service_endpoints: service_endpoint.unwrap_or_default().to_string(),
+            special_fields: Default::default(),
+        }));
+        self
+    }
+
+    /// Replace the `@context` of the DID document. An empty list removes all contexts.
+    pub fn patch_context(mut self, context: Vec<String>) -> Self {
+        self.actions.push(Action::PatchContext(PatchContextAction {
+            context,
+            special_fields: Default::default(),
+        }));
+        self
+    }
+
+    pub fn build(self) -> PrismOperation {
+        new_operation(Operation::UpdateDid(ProtoUpdateDID {
+            previous_operation_hash: self.previous_operation_hash,
+            id: self.id,
+            actions: self
+                .actions
+                .into_iter()
+                .map(|action| UpdateDIDAction {
+                    action: Some(action),
+                    special_fields: Default::default(),
+                })
+                .collect(),
+            special_fields: Default::default(),
+        }))
+    }
+}
+
+/// Builder for a `DeactivateDID` operation.
+#[derive(Debug, Clone)]
+pub struct DeactivateDidOperationBuilder {
+    id: String,
+    previous_operation_hash: Vec<u8>,
+}
+
+impl DeactivateDidOperationBuilder {
+    pub fn new(did_state: &DidState) -> Self {
+        Self {
+            id: did_state.did.suffix_hex().to_string(),
+            previous_operation_hash: did_state.last_operation_hash.to_vec(),
+        }
+    }
+
+    pub fn build(self) -> PrismOperation {
+        new_operation(Operation::DeactivateDid(ProtoDeactivateDID {
+            previous_operation_hash: self.previous_operation_hash,
+            id: self.id,
+            special_fields: Default::default(),
+        }))
+    }
+}
+
+/// Builder for a `CreateStorageEntry` operation.
+///
+/// The `nonce` can be set to create multiple entries with the same initial data.
+#[derive(Debug, Clone)]
+pub struct CreateStorageOperationBuilder {
+    did: CanonicalPrismDid,
+    nonce: Vec<u8>,
+    data: StorageData,
+}
+
+impl CreateStorageOperationBuilder {
+    pub fn new(did: &CanonicalPrismDid, data: StorageData) -> Self {
+        Self {
+            did: did.clone(),
+            nonce: vec![],
+            data,
+        }
+    }
+
+    pub fn nonce(mut self, nonce: &[u8]) -> Self {
+        self.nonce = nonce.to_vec();
+        self
+    }
+
+    pub fn build(self) -> PrismOperation {
+        new_operation(Operation::CreateStorageEntry(ProtoCreateStorageEntry {
+            did_prism_hash: self.did.suffix.to_vec(),
+            nonce: self.nonce,
+            data: Some(self.data.into()),
+            special_fields: Default::default(),
+        }))
+    }
+}
+
+/// Builder for an `UpdateStorageEntry` operation.
+///
+/// The `previous_event_hash` is taken from the latest [`StorageState`] of the entry.
+/// Since a status list entry is published as a diff, the builder computes the
+/// [`StatusListUpdate`] from the current status list when a whole status list is given.
+#[derive(Debug, Clone)]
+pub struct UpdateStorageOperationBuilder {
+    previous_event_hash: Vec<u8>,
+    data: StorageUpdate,
+}
+
+impl UpdateStorageOperationBuilder {
+    pub fn new(storage_state: &StorageState, data: StorageData) -> Self {
+        let data = match (storage_state.data.as_ref(), data) {
+            (_, StorageData::Bytes(bytes)) => StorageUpdate::Bytes(bytes),
+            (_, StorageData::Ipfs(cid)) => StorageUpdate::Ipfs(cid),
+            (StorageData::StatusList(current), StorageData::StatusList(status_list)) => {
+                StorageUpdate::StatusList(StatusListUpdate::between(current, &status_list))
+            }
+            (_, StorageData::StatusList(status_list)) => {
+                let empty = StatusListData {
+                    status_size: status_list.status_size,
+                    ..Default::default()
+                };
+                StorageUpdate::StatusList(StatusListUpdate {
+                    status_size: Some(status_list.status_size),
+                    ..StatusListUpdate::between(&empty, &status_list)
+                })
+            }
+        };
+        Self {
+            previous_event_hash: storage_state.last_operation_hash.to_vec(),
+            data,
+        }
+    }
+
+    /// Update a status list entry with the given change only.
+    pub fn status_list(storage_state: &StorageState, update: StatusListUpdate) -> Self {
+        Self {
+            previous_event_hash: storage_state.last_operation_hash.to_vec(),
+            data: StorageUpdate::StatusList(update),
+        }
+    }
+
+    pub fn build(self) -> PrismOperation {
+        new_operation(Operation::UpdateStorageEntry(ProtoUpdateStorageEntry {
+            previous_event_hash: self.previous_event_hash,
+            data: Some(self.data.into()),
+            special_fields: Default::default(),
+        }))
+    }
+}
+
+/// Builder for a `DeactivateStorageEntry` operation.
+#[derive(Debug, Clone)]
+pub struct DeactivateStorageOperationBuilder {
+    previous_event_hash: Vec<u8>,
+}
+
+impl DeactivateStorageOperationBuilder {
+    pub fn new(storage_state: &StorageState) -> Self {
+        Self {
+            previous_event_hash: storage_state.last_operation_hash.to_vec(),
+        }
+    }
+
+    pub fn build(self) -> PrismOperation {
+        new_operation(Operation::DeactivateStorageEntry(ProtoDeactivateStorageEntry {
+            previous_event_hash: self.previous_event_hash,
+            special_fields: Default::default(),
+        }))
+    }
+}
@@ -1,3 +1,4 @@
+pub mod builder;
 mod ssi;
 mod storage;
 
@@ -18,9 +19,16 @@ pub struct OperationParameters {
     pub max_id_size: usize,
     pub max_type_size: usize,
     pub max_service_endpoint_size: usize,
+    /// Whether P-256 and P-384 public keys are accepted as non-master keys
+    pub allow_nist_curves: bool,
 }
 
 impl OperationParameters {
+    /// Parameters of protocol version 1.
+    ///
+    /// NIST curves are accepted as an extension of the specification, which only lists secp256k1,
+    /// Ed25519 and X25519. DIDs using P-256 or P-384 keys are resolved by NeoPRISM but may be
+    /// rejected by nodes that follow the specification strictly.
     pub fn v1() -> Self {
         Self {
             max_services: 50,
@@ -28,6 +36,7 @@ impl OperationParameters {
             max_id_size: 50,
             max_type_size: 100,
             max_service_endpoint_size: 300,
+            allow_nist_curves: true,
         }
     }
 }
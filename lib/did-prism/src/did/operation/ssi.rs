@@ -2,12 +2,16 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 
 use enum_dispatch::enum_dispatch;
-use identus_apollo::crypto::Error as CryptoError;
 use identus_apollo::crypto::ed25519::Ed25519PublicKey;
+use identus_apollo::crypto::p256::P256PublicKey;
+use identus_apollo::crypto::p384::P384PublicKey;
 use identus_apollo::crypto::secp256k1::Secp256k1PublicKey;
 use identus_apollo::crypto::x25519::X25519PublicKey;
+use identus_apollo::crypto::{EncodeVec, Error as CryptoError};
 use identus_apollo::hash::Sha256Digest;
-use identus_apollo::jwk::EncodeJwk;
+use identus_apollo::jwk::{DecodeJwk, EncodeJwk, Error as JwkError, Jwk};
+use identus_apollo::multibase::MultibaseStr;
+use identus_apollo::multikey::EncodeMultikey;
 use regex::Regex;
 
 use crate::did::CanonicalPrismDid;
@@ -368,6 +372,9 @@ impl PublicKey {
                 id: id.clone(),
             })?
             .ok_or_else(|| PublicKeyError::UnsupportedCurve { id: id.clone() })?;
+        if !param.allow_nist_curves && matches!(pk, NonOperationPublicKey::P256(_) | NonOperationPublicKey::P384(_)) {
+            Err(PublicKeyError::UnsupportedCurve { id: id.clone() })?
+        }
         let data = match (usage, pk) {
             (KeyUsage::MasterKey, NonOperationPublicKey::Secp256k1(pk)) => PublicKeyData::Master { data: pk },
             (KeyUsage::MasterKey, _) => Err(PublicKeyError::MasterKeyNotSecp256k1 { id: id.clone() })?,
@@ -390,6 +397,8 @@ pub enum NonOperationPublicKey {
     Secp256k1(Secp256k1PublicKey),
     Ed25519(Ed25519PublicKey),
     X25519(X25519PublicKey),
+    P256(P256PublicKey),
+    P384(P384PublicKey),
 }
 
 impl NonOperationPublicKey {
@@ -403,6 +412,8 @@ impl NonOperationPublicKey {
             "secp256k1" => Ok(Some(Self::Secp256k1(Self::convert_secp256k1(key_data)?))),
             "Ed25519" => Ok(Some(Self::Ed25519(Self::convert_ed25519(key_data)?))),
             "X25519" => Ok(Some(Self::X25519(Self::convert_x25519(key_data)?))),
+            "P-256" => Ok(Some(Self::P256(Self::convert_p256(key_data)?))),
+            "P-384" => Ok(Some(Self::P384(Self::convert_p384(key_data)?))),
             _ => Ok(None),
         }
     }
@@ -421,6 +432,34 @@ impl NonOperationPublicKey {
         Ok(pk)
     }
 
+    fn convert_p256(key_data: &Key_data) -> Result<P256PublicKey, CryptoError> {
+        let pk = match key_data {
+            Key_data::EcKeyData(k) => {
+                let mut data = Vec::with_capacity(65);
+                data.push(0x04);
+                data.extend_from_slice(k.x.as_ref());
+                data.extend_from_slice(k.y.as_ref());
+                P256PublicKey::from_slice(&data)?
+            }
+            Key_data::CompressedEcKeyData(k) => P256PublicKey::from_slice(&k.data)?,
+        };
+        Ok(pk)
+    }
+
+    fn convert_p384(key_data: &Key_data) -> Result<P384PublicKey, CryptoError> {
+        let pk = match key_data {
+            Key_data::EcKeyData(k) => {
+                let mut data = Vec::with_capacity(97);
+                data.push(0x04);
+                data.extend_from_slice(k.x.as_ref());
+                data.extend_from_slice(k.y.as_ref());
+                P384PublicKey::from_slice(&data)?
+            }
+            Key_data::CompressedEcKeyData(k) => P384PublicKey::from_slice(&k.data)?,
+        };
+        Ok(pk)
+    }
+
     fn convert_ed25519(key_data: &Key_data) -> Result<Ed25519PublicKey, CryptoError> {
         let pk = match key_data {
             Key_data::EcKeyData(k) => Ed25519PublicKey::from_slice(&k.x)?,
@@ -438,12 +477,54 @@ impl NonOperationPublicKey {
     }
 }
 
+impl EncodeVec for NonOperationPublicKey {
+    fn encode_vec(&self) -> Vec<u8> {
+        match self {
+            NonOperationPublicKey::Secp256k1(pk) => pk.encode_vec(),
+            NonOperationPublicKey::Ed25519(pk) => pk.encode_vec(),
+            NonOperationPublicKey::X25519(pk) => pk.encode_vec(),
+            NonOperationPublicKey::P256(pk) => pk.encode_vec(),
+            NonOperationPublicKey::P384(pk) => pk.encode_vec(),
+        }
+    }
+}
+
 impl EncodeJwk for NonOperationPublicKey {
-    fn encode_jwk(&self) -> identus_apollo::jwk::Jwk {
+    fn encode_jwk(&self) -> Jwk {
         match self {
             NonOperationPublicKey::Secp256k1(pk) => pk.encode_jwk(),
             NonOperationPublicKey::Ed25519(pk) => pk.encode_jwk(),
             NonOperationPublicKey::X25519(pk) => pk.encode_jwk(),
+            NonOperationPublicKey::P256(pk) => pk.encode_jwk(),
+            NonOperationPublicKey::P384(pk) => pk.encode_jwk(),
+        }
+    }
+}
+
+impl DecodeJwk for NonOperationPublicKey {
+    fn decode_jwk(jwk: &Jwk) -> Result<Self, JwkError> {
+        match jwk.crv.as_str() {
+            "secp256k1" => Ok(Self::Secp256k1(Secp256k1PublicKey::decode_jwk(jwk)?)),
+            "Ed25519" => Ok(Self::Ed25519(Ed25519PublicKey::decode_jwk(jwk)?)),
+            "X25519" => Ok(Self::X25519(X25519PublicKey::decode_jwk(jwk)?)),
+            "P-256" => Ok(Self::P256(P256PublicKey::decode_jwk(jwk)?)),
+            "P-384" => Ok(Self::P384(P384PublicKey::decode_jwk(jwk)?)),
+            _ => Err(JwkError::UnsupportedKeyType {
+                kty: jwk.kty.clone(),
+                crv: jwk.crv.clone(),
+            }),
+        }
+    }
+}
+
+impl EncodeMultikey for NonOperationPublicKey {
+    fn encode_multikey(&self) -> MultibaseStr {
+        match self {
+            NonOperationPublicKey::Secp256k1(pk) => pk.encode_multikey(),
+            NonOperationPublicKey::Ed25519(pk) => pk.encode_multikey(),
+            NonOperationPublicKey::X25519(pk) => pk.encode_multikey(),
+            NonOperationPublicKey::P256(pk) => pk.encode_multikey(),
+            NonOperationPublicKey::P384(pk) => pk.encode_multikey(),
         }
     }
 }
@@ -472,6 +553,16 @@ impl PublicKeyData {
     }
 }
 
+impl EncodeVec for PublicKeyData {
+    /// Encode the key material, using the compressed point for EC keys.
+    fn encode_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Master { data } | Self::Vdr { data } => data.encode_vec(),
+            Self::Other { data, .. } => data.encode_vec(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyUsage {
     MasterKey,
@@ -498,6 +589,20 @@ impl KeyUsage {
             proto::prism_ssi::KeyUsage::UNKNOWN_KEY => None,
         }
     }
+
+    /// The key purpose index used in the HD derivation path of the key.
+    pub fn derivation_index(&self) -> u32 {
+        match self {
+            Self::MasterKey => 1,
+            Self::IssuingKey => 2,
+            Self::KeyAgreementKey => 3,
+            Self::AuthenticationKey => 4,
+            Self::RevocationKey => 5,
+            Self::CapabilityInvocationKey => 6,
+            Self::CapabilityDelegationKey => 7,
+            Self::VdrKey => 8,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -5,13 +5,132 @@ use crate::did::CanonicalPrismDid;
 use crate::did::error::{CreateStorageOperationError, DeactivateStorageOperationError, UpdateStorageOperationError};
 use crate::proto::prism_storage::proto_create_storage_entry::Data as ProtoCreateStorageData;
 use crate::proto::prism_storage::proto_update_storage_entry::Data as ProtoUpdateStorageData;
-use crate::proto::prism_storage::{ProtoCreateStorageEntry, ProtoDeactivateStorageEntry, ProtoUpdateStorageEntry};
+use crate::proto::prism_storage::{
+    ProtoCreateStorageEntry, ProtoDeactivateStorageEntry, ProtoUpdateStorageEntry, StatusList, StatusListEntry,
+};
+use crate::status_list::MAX_BITSTRING_LENGTH;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct StatusListData {
     pub state: i64,
     pub name: String,
     pub detail: String,
+    pub list_size: u32,
+    pub status_size: u32,
+    pub int_status: Vec<u8>,
+}
+
+impl StatusListData {
+    /// The number of bytes needed for `list_size` statuses of `status_size` bits,
+    /// or `None` if the list is longer than [`MAX_BITSTRING_LENGTH`] bits.
+    pub fn bitstring_len(&self) -> Option<usize> {
+        (self.list_size as usize)
+            .checked_mul(self.status_size.max(1) as usize)
+            .filter(|bits| *bits <= MAX_BITSTRING_LENGTH)
+            .map(|bits| bits.div_ceil(8))
+    }
+
+    /// The statuses as a bitstring of exactly `list_size` statuses,
+    /// or `None` if the list is longer than [`MAX_BITSTRING_LENGTH`] bits.
+    pub fn bitstring(&self) -> Option<Vec<u8>> {
+        self.bitstring_len().map(|len| self.padded_statuses(len))
+    }
+
+    /// The statuses as a bitstring of `len` bytes, where the bits beyond the list are cleared.
+    fn padded_statuses(&self, len: usize) -> Vec<u8> {
+        let bits = (self.list_size as usize)
+            .saturating_mul(self.status_size.max(1) as usize)
+            .min(len * 8);
+        let mut statuses = self.int_status.clone();
+        statuses.resize(bits.div_ceil(8), 0);
+        if let Some(last) = statuses.last_mut() {
+            *last &= 0xff << ((8 - bits % 8) % 8);
+        }
+        statuses.resize(len, 0);
+        statuses
+    }
+
+    /// Apply the change of an update operation to this status list.
+    fn apply(&self, update: &StatusListUpdate) -> StatusListData {
+        let mut result = self.clone();
+        if let Some(state) = update.state {
+            result.state = state;
+        }
+        if let Some(name) = &update.name {
+            result.name = name.clone();
+        }
+        if let Some(detail) = &update.detail {
+            result.detail = detail.clone();
+        }
+        let grown = StatusListData {
+            list_size: self.list_size.max(update.list_size.unwrap_or_default()),
+            ..self.clone()
+        };
+        if grown.bitstring_len().is_some() {
+            result.list_size = grown.list_size;
+        }
+        // statuses of a list longer than the maximum length are left unchanged
+        if let Some(len) = result.bitstring_len() {
+            result.int_status = self.padded_statuses(len);
+            for (status, mask) in result.int_status.iter_mut().zip(&update.int_status) {
+                *status ^= mask;
+            }
+            // bits of the mask beyond the list are ignored
+            result.int_status = result.padded_statuses(len);
+        }
+        result
+    }
+}
+
+/// The change of a status list entry published by an update operation.
+///
+/// Fields that are set replace the current values and the statuses are changed bit by bit:
+/// - `list_size` grows the list up to [`MAX_BITSTRING_LENGTH`] bits, a smaller size is ignored so statuses are never dropped.
+/// - Every set bit of `int_status` flips the corresponding bit of the statuses. Bits beyond the list are ignored.
+///
+/// The status size is fixed once the entry is a status list.
+/// `status_size` is only used when an entry of another type is updated to a status list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusListUpdate {
+    pub state: Option<i64>,
+    pub name: Option<String>,
+    pub detail: Option<String>,
+    pub list_size: Option<u32>,
+    pub status_size: Option<u32>,
+    pub int_status: Vec<u8>,
+}
+
+impl StatusListUpdate {
+    /// The change that updates the status list `from` to the status list `to`.
+    ///
+    /// Since a list never shrinks, statuses of `from` beyond the list size of `to` are cleared instead.
+    pub fn between(from: &StatusListData, to: &StatusListData) -> Self {
+        let grown = StatusListData {
+            list_size: from.list_size.max(to.list_size),
+            ..from.clone()
+        };
+        let (list_size, len) = match grown.bitstring_len() {
+            Some(len) => (grown.list_size, len),
+            None => (from.list_size, from.bitstring_len().unwrap_or_default()),
+        };
+        let mut int_status = from
+            .padded_statuses(len)
+            .into_iter()
+            .zip(to.padded_statuses(len))
+            .map(|(from, to)| from ^ to)
+            .collect::<Vec<_>>();
+        while int_status.last() == Some(&0) {
+            int_status.pop();
+        }
+        Self {
+            state: (from.state != to.state).then_some(to.state),
+            name: (from.name != to.name).then(|| to.name.clone()),
+            detail: (from.detail != to.detail).then(|| to.detail.clone()),
+            list_size: (from.list_size != list_size).then_some(list_size),
+            status_size: None,
+            int_status,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,25 +145,109 @@ impl From<ProtoCreateStorageData> for StorageData {
         match value {
             ProtoCreateStorageData::Bytes(bytes) => StorageData::Bytes(bytes),
             ProtoCreateStorageData::Ipfs(cid) => StorageData::Ipfs(cid),
-            ProtoCreateStorageData::StatusListEntry(sle) => StorageData::StatusList(StatusListData {
-                state: sle.state,
-                name: sle.name,
-                detail: sle.details,
-            }),
+            ProtoCreateStorageData::StatusListEntry(sle) => StorageData::StatusList(sle.into()),
+        }
+    }
+}
+
+impl From<StorageData> for ProtoCreateStorageData {
+    fn from(value: StorageData) -> Self {
+        match value {
+            StorageData::Bytes(bytes) => ProtoCreateStorageData::Bytes(bytes),
+            StorageData::Ipfs(cid) => ProtoCreateStorageData::Ipfs(cid),
+            StorageData::StatusList(sl) => ProtoCreateStorageData::StatusListEntry(sl.into()),
+        }
+    }
+}
+
+impl From<StatusListEntry> for StatusListData {
+    fn from(value: StatusListEntry) -> Self {
+        let status_list = value.status_list.into_option().unwrap_or_default();
+        StatusListData {
+            state: value.state.unwrap_or_default(),
+            name: value.name.unwrap_or_default(),
+            detail: value.details.unwrap_or_default(),
+            list_size: status_list.list_size,
+            status_size: status_list.status_size,
+            int_status: status_list.int_status,
+        }
+    }
+}
+
+impl From<StatusListData> for StatusListEntry {
+    fn from(value: StatusListData) -> Self {
+        StatusListEntry {
+            state: Some(value.state),
+            name: Some(value.name),
+            details: Some(value.detail),
+            status_list: Some(StatusList {
+                list_size: value.list_size,
+                status_size: value.status_size,
+                int_status: value.int_status,
+                special_fields: Default::default(),
+            })
+            .into(),
+            special_fields: Default::default(),
         }
     }
 }
 
-impl From<ProtoUpdateStorageData> for StorageData {
+/// The data of an update operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageUpdate {
+    Bytes(Vec<u8>),
+    Ipfs(String),
+    StatusList(StatusListUpdate),
+}
+
+impl From<ProtoUpdateStorageData> for StorageUpdate {
     fn from(value: ProtoUpdateStorageData) -> Self {
         match value {
-            ProtoUpdateStorageData::Bytes(bytes) => StorageData::Bytes(bytes),
-            ProtoUpdateStorageData::Ipfs(cid) => StorageData::Ipfs(cid),
-            ProtoUpdateStorageData::StatusListEntry(sle) => StorageData::StatusList(StatusListData {
-                state: sle.state,
-                name: sle.name,
-                detail: sle.details,
-            }),
+            ProtoUpdateStorageData::Bytes(bytes) => StorageUpdate::Bytes(bytes),
+            ProtoUpdateStorageData::Ipfs(cid) => StorageUpdate::Ipfs(cid),
+            ProtoUpdateStorageData::StatusListEntry(sle) => StorageUpdate::StatusList(sle.into()),
+        }
+    }
+}
+
+impl From<StorageUpdate> for ProtoUpdateStorageData {
+    fn from(value: StorageUpdate) -> Self {
+        match value {
+            StorageUpdate::Bytes(bytes) => ProtoUpdateStorageData::Bytes(bytes),
+            StorageUpdate::Ipfs(cid) => ProtoUpdateStorageData::Ipfs(cid),
+            StorageUpdate::StatusList(update) => ProtoUpdateStorageData::StatusListEntry(update.into()),
+        }
+    }
+}
+
+impl From<StatusListEntry> for StatusListUpdate {
+    fn from(value: StatusListEntry) -> Self {
+        let status_list = value.status_list.into_option().unwrap_or_default();
+        StatusListUpdate {
+            state: value.state,
+            name: value.name,
+            detail: value.details,
+            list_size: Some(status_list.list_size).filter(|i| *i > 0),
+            status_size: Some(status_list.status_size).filter(|i| *i > 0),
+            int_status: status_list.int_status,
+        }
+    }
+}
+
+impl From<StatusListUpdate> for StatusListEntry {
+    fn from(value: StatusListUpdate) -> Self {
+        let status_list = StatusList {
+            list_size: value.list_size.unwrap_or_default(),
+            status_size: value.status_size.unwrap_or_default(),
+            int_status: value.int_status,
+            special_fields: Default::default(),
+        };
+        StatusListEntry {
+            state: value.state,
+            name: value.name,
+            details: value.detail,
+            status_list: Some(status_list).filter(|i| *i != StatusList::default()).into(),
+            special_fields: Default::default(),
         }
     }
 }
@@ -77,7 +280,7 @@ impl CreateStorageOperation {
 #[derive(Debug, Clone)]
 pub struct UpdateStorageOperation {
     pub prev_event_hash: Sha256Digest,
-    pub data: StorageData,
+    pub data: StorageUpdate,
 }
 
 impl UpdateStorageOperation {
@@ -92,6 +295,27 @@ impl UpdateStorageOperation {
 
         Ok(Self { prev_event_hash, data })
     }
+
+    /// Compute the data of the entry after this update.
+    ///
+    /// Bytes and IPFS data replace the current data while a status list update
+    /// is applied as a diff to the current status list.
+    pub fn apply(&self, current: &StorageData) -> StorageData {
+        match (&self.data, current) {
+            (StorageUpdate::Bytes(bytes), _) => StorageData::Bytes(bytes.clone()),
+            (StorageUpdate::Ipfs(cid), _) => StorageData::Ipfs(cid.clone()),
+            (StorageUpdate::StatusList(update), StorageData::StatusList(current)) => {
+                StorageData::StatusList(current.apply(update))
+            }
+            (StorageUpdate::StatusList(update), _) => {
+                let empty = StatusListData {
+                    status_size: update.status_size.unwrap_or_default(),
+                    ..Default::default()
+                };
+                StorageData::StatusList(empty.apply(update))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
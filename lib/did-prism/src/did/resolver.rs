@@ -0,0 +1,80 @@
+use identus_apollo::hash::Sha256Digest;
+
+use crate::did::{CanonicalPrismDid, DidState, PrismDid, PrismDidOps};
+use crate::dlt::OperationMetadata;
+use crate::prelude::SignedPrismOperation;
+use crate::protocol::error::ProcessError;
+use crate::protocol::resolver::{resolve_published_at_version, resolve_unpublished};
+
+/// A source of resolved DID states such as a node database or a remote node.
+#[async_trait::async_trait]
+pub trait DidResolver {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Resolve the latest state of the DID, or `None` if the DID is not found.
+    async fn resolve(&self, did: &PrismDid) -> Result<Option<DidState>, Self::Error>;
+
+    /// Resolve the state of the DID right after the operation with the given hash is processed.
+    async fn resolve_at_version(
+        &self,
+        did: &PrismDid,
+        version: &Sha256Digest,
+    ) -> Result<Option<DidState>, Self::Error> {
+        let operations = self.get_operations(&did.clone().into_canonical()).await?;
+        Ok(resolve_published_at_version(operations, version).0)
+    }
+
+    /// Get all published operations of the DID including the invalid ones.
+    async fn get_operations(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(OperationMetadata, SignedPrismOperation)>, Self::Error>;
+}
+
+#[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
+pub enum LongFormResolutionError {
+    #[from]
+    #[display("cannot decode the encoded state of long-form did")]
+    InvalidDid { source: crate::did::Error },
+    #[from]
+    #[display("cannot process did state from long-form did")]
+    ProcessFail { source: ProcessError },
+}
+
+/// Resolve long-form DIDs from their encoded state without looking up any published operation.
+///
+/// This is useful for verifying signatures of DIDs that are not yet published when no node is available.
+/// Canonical DIDs are never found.
+#[derive(Debug, Clone, Default)]
+pub struct LongFormResolver;
+
+#[async_trait::async_trait]
+impl DidResolver for LongFormResolver {
+    type Error = LongFormResolutionError;
+
+    async fn resolve(&self, did: &PrismDid) -> Result<Option<DidState>, Self::Error> {
+        match did {
+            PrismDid::Canonical(_) => Ok(None),
+            PrismDid::LongForm(long_form_did) => {
+                let operation = long_form_did.operation()?;
+                Ok(Some(resolve_unpublished(operation)?))
+            }
+        }
+    }
+
+    async fn resolve_at_version(
+        &self,
+        did: &PrismDid,
+        version: &Sha256Digest,
+    ) -> Result<Option<DidState>, Self::Error> {
+        let state = self.resolve(did).await?;
+        Ok(state.filter(|s| s.last_operation_hash.as_ref() == version))
+    }
+
+    async fn get_operations(
+        &self,
+        _: &CanonicalPrismDid,
+    ) -> Result<Vec<(OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        Ok(vec![])
+    }
+}
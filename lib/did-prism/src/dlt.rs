@@ -15,21 +15,31 @@ pub struct DltCursor {
     pub cbt: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BlockMetadata {
     /// Cardano slot number
     pub slot_number: SlotNo,
     /// Cardano block number
     pub block_number: BlockNo,
+    /// Cardano block hash
+    ///
+    /// This is not available for operations indexed before it was recorded
+    pub block_hash: Option<BlockHash>,
     /// Cardano block timestamp
     pub cbt: DateTime<Utc>,
     /// PrismBlock seqeuence number
     ///
     /// This is used to order PrismBlock within the same Cardano block
     pub absn: u32,
+    /// Cardano transaction hash
+    ///
+    /// This is not available for operations indexed before it was recorded
+    pub tx_hash: Option<TxId>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct OperationMetadata {
     /// PrismBlock metadata
     pub block_metadata: BlockMetadata,
@@ -118,30 +128,49 @@ impl BlockNo {
 #[debug("{}", identus_apollo::hex::HexStr::from(self.0.as_bytes()))]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[cfg_attr(feature = "openapi", schema(value_type = String, example = "5ab0cf7e4c7cd4b63ba84a4fe299409be12ba85607cb6d1a149e80bc2eac070d"))]
-pub struct TxId(#[serde(serialize_with = "TxId::serialize", deserialize_with = "TxId::deserialize")] Sha256Digest);
+pub struct TxId(#[serde(serialize_with = "serialize_hash", deserialize_with = "deserialize_hash")] Sha256Digest);
 
 impl TxId {
-    fn serialize<S>(bytes: &Sha256Digest, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let hex_str = HexStr::from(bytes.as_bytes());
-        serializer.serialize_str(&hex_str.to_string())
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
     }
+}
+
+#[derive(
+    Clone, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::Debug, derive_more::Display, derive_more::From,
+)]
+#[display("{}", identus_apollo::hex::HexStr::from(self.0.as_bytes()))]
+#[debug("{}", identus_apollo::hex::HexStr::from(self.0.as_bytes()))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "openapi", schema(value_type = String, example = "4b3e4ac4cc4fb7b0c0c4ee8c4ac8d0e6ea5b9bd3cf3c4cf12b7a8cd1a5fd0a2e"))]
+pub struct BlockHash(#[serde(serialize_with = "serialize_hash", deserialize_with = "deserialize_hash")] Sha256Digest);
 
-    fn deserialize<'de, D>(deserializer: D) -> Result<Sha256Digest, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let hex_str = String::deserialize(deserializer)?;
-        let bytes = HexStr::from_str(&hex_str)
-            .map_err(|e| serde::de::Error::custom(format!("Value is not a valid hex: {e}")))?;
-        let digest = Sha256Digest::from_bytes(&bytes.to_bytes())
-            .map_err(|e| serde::de::Error::custom(format!("Value is not a valid digest: {e}")))?;
-        Ok(digest)
+impl BlockHash {
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
     }
 }
 
+fn serialize_hash<S>(bytes: &Sha256Digest, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let hex_str = HexStr::from(bytes.as_bytes());
+    serializer.serialize_str(&hex_str.to_string())
+}
+
+fn deserialize_hash<'de, D>(deserializer: D) -> Result<Sha256Digest, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hex_str = String::deserialize(deserializer)?;
+    let bytes =
+        HexStr::from_str(&hex_str).map_err(|e| serde::de::Error::custom(format!("Value is not a valid hex: {e}")))?;
+    let digest = Sha256Digest::from_bytes(&bytes.to_bytes())
+        .map_err(|e| serde::de::Error::custom(format!("Value is not a valid digest: {e}")))?;
+    Ok(digest)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, strum::VariantArray)]
 pub enum NetworkIdentifier {
     #[strum(serialize = "mainnet")]
@@ -0,0 +1,260 @@
+use std::str::FromStr;
+
+use identus_apollo::base64::Base64UrlStrNoPad;
+use identus_apollo::crypto::Verifiable;
+
+use crate::did::operation::{KeyUsage, NonOperationPublicKey, PublicKeyData};
+use crate::did::{DidResolver, DidState, PrismDid};
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum Error {
+    #[display("JWS is not in the compact serialization format")]
+    InvalidFormat,
+    #[display("unable to decode JWS {part}")]
+    InvalidEncoding {
+        source: identus_apollo::base64::Error,
+        part: &'static str,
+    },
+    #[display("unable to parse JWS header")]
+    InvalidHeader { source: serde_json::Error },
+    #[display("unsupported JWS algorithm {alg}")]
+    UnsupportedAlgorithm { alg: String },
+    #[display("unsupported critical JWS header parameters {crit:?}")]
+    UnsupportedCriticalHeader { crit: Vec<String> },
+    #[display("JWS header does not contain a kid")]
+    MissingKid,
+    #[display("JWS kid {kid} is not a DID URL with a fragment")]
+    InvalidKid { kid: String },
+    #[display("JWS kid {kid} does not contain a valid PRISM DID")]
+    InvalidDid {
+        source: Box<crate::did::Error>,
+        kid: String,
+    },
+    #[display("unable to resolve did {did}")]
+    Resolution {
+        source: Box<dyn std::error::Error + Send + Sync>,
+        did: PrismDid,
+    },
+    #[display("did {did} is not found")]
+    DidNotFound { did: PrismDid },
+    #[display("key {kid} is not found in the did document")]
+    KeyNotFound { kid: String },
+    #[display("key {kid} is not part of the {relationship} verification relationship")]
+    KeyRelationshipMismatch {
+        kid: String,
+        relationship: VerificationRelationship,
+    },
+    #[display("JWS algorithm {alg} cannot be used with key {kid}")]
+    AlgorithmKeyMismatch { alg: JwsAlgorithm, kid: String },
+    #[display("JWS signature is invalid")]
+    InvalidSignature,
+}
+
+/// The verification relationship in the DID document that the signing key must belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum VerificationRelationship {
+    #[display("authentication")]
+    Authentication,
+    #[display("assertionMethod")]
+    AssertionMethod,
+    #[display("capabilityInvocation")]
+    CapabilityInvocation,
+    #[display("capabilityDelegation")]
+    CapabilityDelegation,
+}
+
+impl VerificationRelationship {
+    pub fn key_usage(&self) -> KeyUsage {
+        match self {
+            Self::Authentication => KeyUsage::AuthenticationKey,
+            Self::AssertionMethod => KeyUsage::IssuingKey,
+            Self::CapabilityInvocation => KeyUsage::CapabilityInvocationKey,
+            Self::CapabilityDelegation => KeyUsage::CapabilityDelegationKey,
+        }
+    }
+}
+
+/// JWS algorithms that can be verified with the key types supported in PRISM DIDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum JwsAlgorithm {
+    #[display("ES256K")]
+    ES256K,
+    #[display("EdDSA")]
+    EdDSA,
+    #[display("ES256")]
+    ES256,
+    #[display("ES384")]
+    ES384,
+}
+
+impl FromStr for JwsAlgorithm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ES256K" => Ok(Self::ES256K),
+            "EdDSA" => Ok(Self::EdDSA),
+            "ES256" => Ok(Self::ES256),
+            "ES384" => Ok(Self::ES384),
+            _ => Err(Error::UnsupportedAlgorithm { alg: s.to_string() }),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct JwsHeader {
+    alg: String,
+    kid: Option<String>,
+    crit: Option<Vec<String>>,
+}
+
+/// A parsed JWS in the compact serialization format whose signature is not yet verified.
+#[derive(Debug, Clone)]
+pub struct CompactJws {
+    pub algorithm: JwsAlgorithm,
+    pub did: PrismDid,
+    pub kid: String,
+    pub key_id: String,
+    pub header: serde_json::Map<String, serde_json::Value>,
+    pub payload: Vec<u8>,
+    signing_input: String,
+    signature: Vec<u8>,
+}
+
+/// A JWS whose signature is verified against a key of the signer DID.
+#[derive(Debug, Clone)]
+pub struct VerifiedJws {
+    pub algorithm: JwsAlgorithm,
+    pub did: PrismDid,
+    pub kid: String,
+    pub header: serde_json::Map<String, serde_json::Value>,
+    pub payload: Vec<u8>,
+}
+
+impl VerifiedJws {
+    /// Parse the payload as a JSON value, such as the claims of a JWT.
+    pub fn claims(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::from_slice(&self.payload)
+    }
+}
+
+impl FromStr for CompactJws {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('.');
+        let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            Err(Error::InvalidFormat)?
+        };
+
+        let decode = |part: &'static str, value: &str| {
+            Base64UrlStrNoPad::from_str(value)
+                .map(|b| b.to_bytes())
+                .map_err(|e| Error::InvalidEncoding { source: e, part })
+        };
+        let header_bytes = decode("header", header_b64)?;
+        let payload = decode("payload", payload_b64)?;
+        let signature = decode("signature", signature_b64)?;
+
+        let parsed_header: JwsHeader =
+            serde_json::from_slice(&header_bytes).map_err(|e| Error::InvalidHeader { source: e })?;
+        let header = serde_json::from_slice(&header_bytes).map_err(|e| Error::InvalidHeader { source: e })?;
+        if let Some(crit) = parsed_header.crit {
+            Err(Error::UnsupportedCriticalHeader { crit })?
+        }
+        let algorithm = JwsAlgorithm::from_str(&parsed_header.alg)?;
+        let kid = parsed_header.kid.ok_or(Error::MissingKid)?;
+        let Some((did, key_id)) = kid.split_once('#') else {
+            Err(Error::InvalidKid { kid: kid.clone() })?
+        };
+        let did = PrismDid::from_str(did).map_err(|e| Error::InvalidDid {
+            source: Box::new(e),
+            kid: kid.clone(),
+        })?;
+        let key_id = key_id.to_string();
+
+        Ok(Self {
+            algorithm,
+            did,
+            kid,
+            key_id,
+            header,
+            payload,
+            signing_input: format!("{}.{}", header_b64, payload_b64),
+            signature,
+        })
+    }
+}
+
+impl CompactJws {
+    /// Verify the signature using the key referenced by `kid` in the given DID state.
+    ///
+    /// The key must belong to the given verification relationship and match the JWS algorithm.
+    pub fn verify(self, state: &DidState, relationship: VerificationRelationship) -> Result<VerifiedJws, Error> {
+        let public_key = state
+            .public_keys
+            .iter()
+            .find(|k| k.id.as_str() == self.key_id)
+            .ok_or_else(|| Error::KeyNotFound { kid: self.kid.clone() })?;
+        let key = match &public_key.data {
+            PublicKeyData::Other { data, usage } if *usage == relationship.key_usage() => data,
+            _ => Err(Error::KeyRelationshipMismatch {
+                kid: self.kid.clone(),
+                relationship,
+            })?,
+        };
+
+        let message = self.signing_input.as_bytes();
+        let signature = self.signature.as_slice();
+        let is_valid = match (self.algorithm, key) {
+            (JwsAlgorithm::ES256K, NonOperationPublicKey::Secp256k1(pk)) => pk.verify_compact(message, signature),
+            (JwsAlgorithm::EdDSA, NonOperationPublicKey::Ed25519(pk)) => pk.verify(message, signature),
+            (JwsAlgorithm::ES256, NonOperationPublicKey::P256(pk)) => {
+                signature.len() == 64 && pk.verify(message, signature)
+            }
+            (JwsAlgorithm::ES384, NonOperationPublicKey::P384(pk)) => {
+                signature.len() == 96 && pk.verify(message, signature)
+            }
+            _ => Err(Error::AlgorithmKeyMismatch {
+                alg: self.algorithm,
+                kid: self.kid.clone(),
+            })?,
+        };
+        if !is_valid {
+            Err(Error::InvalidSignature)?
+        }
+
+        Ok(VerifiedJws {
+            algorithm: self.algorithm,
+            did: self.did,
+            kid: self.kid,
+            header: self.header,
+            payload: self.payload,
+        })
+    }
+}
+
+/// Verify a compact JWS or JWT against the DID referenced by the `kid` header.
+///
+/// The DID is resolved using the given resolver and the signing key must belong to
+/// the given verification relationship, e.g. `assertionMethod` for credentials.
+pub async fn verify_jws<R: DidResolver + Sync>(
+    resolver: &R,
+    jws: &str,
+    relationship: VerificationRelationship,
+) -> Result<VerifiedJws, Error> {
+    let jws = CompactJws::from_str(jws)?;
+    let state = resolver
+        .resolve(&jws.did)
+        .await
+        .map_err(|e| Error::Resolution {
+            source: Box::new(e),
+            did: jws.did.clone(),
+        })?
+        .ok_or_else(|| Error::DidNotFound { did: jws.did.clone() })?;
+    jws.verify(&state, relationship)
+}
@@ -1,9 +1,11 @@
 pub mod did;
 pub mod dlt;
 pub mod error;
+pub mod jws;
 mod macros;
 pub mod prelude;
 pub mod protocol;
+pub mod status_list;
 pub mod utils;
 
 #[allow(clippy::doc_lazy_continuation)]
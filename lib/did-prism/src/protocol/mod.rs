@@ -8,6 +8,7 @@ use error::{DidStateConflictError, ProcessError};
 use identus_apollo::hash::Sha256Digest;
 use protobuf::SpecialFields;
 
+use self::resolver::StorageVersion;
 use self::v1::V1Processor;
 use crate::did::operation::{PublicKey, PublicKeyId, Service, ServiceEndpoint, ServiceId, ServiceType, StorageData};
 use crate::did::{CanonicalPrismDid, DidState, StorageState};
@@ -78,6 +79,8 @@ struct DidStateRc {
 struct StorageStateRc {
     prev_operation_hash: Rc<Sha256Digest>,
     data: Rc<StorageData>,
+    /// Every version of the entry including the one that deactivated it
+    history: im_rc::Vector<StorageVersion>,
 }
 
 impl DidStateRc {
@@ -206,6 +209,11 @@ impl DidStateRc {
             Revocable::new(
                 StorageStateRc {
                     prev_operation_hash: operation_hash.clone().into(),
+                    history: im_rc::Vector::unit(StorageVersion {
+                        operation_hash: operation_hash.clone(),
+                        metadata: added_at.clone(),
+                        data: Some(data.clone()),
+                    }),
                     data: data.into(),
                 },
                 added_at,
@@ -238,7 +246,13 @@ impl DidStateRc {
         }
 
         storage.revoke(revoke_at);
-        storage.get_mut().prev_operation_hash = operation_hash.clone().into();
+        let storage_inner = storage.get_mut();
+        storage_inner.prev_operation_hash = operation_hash.clone().into();
+        storage_inner.history.push_back(StorageVersion {
+            operation_hash: operation_hash.clone(),
+            metadata: revoke_at.clone(),
+            data: None,
+        });
         Ok(())
     }
 
@@ -246,7 +260,8 @@ impl DidStateRc {
         &mut self,
         prev_operation_hash: &Sha256Digest,
         operation_hash: &Sha256Digest,
-        data: StorageData,
+        update: impl FnOnce(&StorageData) -> StorageData,
+        updated_at: &OperationMetadata,
     ) -> Result<(), DidStateConflictError> {
         let Some(storage) = self
             .storage
@@ -266,10 +281,22 @@ impl DidStateRc {
 
         let storage_inner = storage.get_mut();
         storage_inner.prev_operation_hash = operation_hash.clone().into();
-        storage_inner.data = data.into();
+        storage_inner.data = update(&storage_inner.data).into();
+        storage_inner.history.push_back(StorageVersion {
+            operation_hash: operation_hash.clone(),
+            metadata: updated_at.clone(),
+            data: Some(storage_inner.data.as_ref().clone()),
+        });
         Ok(())
     }
 
+    fn storage_history(&self, init_operation_hash: &Sha256Digest) -> Vec<StorageVersion> {
+        self.storage
+            .get(init_operation_hash)
+            .map(|s| s.get().history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     fn finalize(self) -> DidState {
         let did: CanonicalPrismDid = (*self.did).clone();
         let context: Vec<String> = self.context.iter().map(|s| s.as_str().to_string()).collect();
@@ -353,8 +380,10 @@ fn init_unpublished_context(
         block_metadata: BlockMetadata {
             slot_number: 0.into(),
             block_number: 0.into(),
+            block_hash: None,
             cbt: DateTime::UNIX_EPOCH,
             absn: 0,
+            tx_hash: None,
         },
         osn: 0,
     };
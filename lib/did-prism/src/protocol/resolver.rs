@@ -1,7 +1,10 @@
 use std::collections::VecDeque;
 
+use identus_apollo::hash::Sha256Digest;
+
 use super::{OperationProcessingContext, ProcessError, Published, init_published_context};
 use crate::did::DidState;
+use crate::did::operation::StorageData;
 use crate::dlt::OperationMetadata;
 use crate::prelude::*;
 use crate::protocol::init_unpublished_context;
@@ -15,8 +18,29 @@ pub fn resolve_unpublished(operation: PrismOperation) -> Result<DidState, Proces
 }
 
 pub fn resolve_published(
-    mut operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    operations: Vec<(OperationMetadata, SignedPrismOperation)>,
 ) -> (Option<DidState>, ResolutionDebug) {
+    let (state_ctx, debug) = process_published(operations);
+    (state_ctx.map(|ctx| ctx.finalize()), debug)
+}
+
+/// Resolve every version of a VDR entry from the operation that created it to the latest operation.
+///
+/// The versions are recorded while processing the operations of the DID that owns the entry,
+/// so an entry revoked by deactivating the DID ends with the `DeactivateDID` operation.
+pub fn resolve_storage_history(
+    operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    init_operation_hash: &Sha256Digest,
+) -> Vec<StorageVersion> {
+    let (state_ctx, _) = process_published(operations);
+    state_ctx
+        .map(|ctx| ctx.state.storage_history(init_operation_hash))
+        .unwrap_or_default()
+}
+
+fn process_published(
+    mut operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+) -> (Option<OperationProcessingContext<Published>>, ResolutionDebug) {
     tracing::debug!("resolving published DID data from {} operations", operations.len());
     operations.sort_by(|a, b| OperationMetadata::compare_time_asc(&a.0, &b.0));
     let mut operations: OperationList = operations.into();
@@ -34,7 +58,53 @@ pub fn resolve_published(
         debug.push((metadata, operation, error));
     }
 
-    (Some(state_ctx.finalize()), debug)
+    (Some(state_ctx), debug)
+}
+
+/// Resolve the DID state after processing the operations up to and including the operation with the given hash.
+///
+/// Returns `None` if the operation is not part of the given operations
+/// or the DID is not yet created at that point.
+pub fn resolve_published_at_version(
+    mut operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    version: &Sha256Digest,
+) -> (Option<DidState>, ResolutionDebug) {
+    operations.sort_by(|a, b| OperationMetadata::compare_time_asc(&a.0, &b.0));
+    let Some(position) = operations
+        .iter()
+        .position(|(_, op)| op.operation_hash().as_ref() == Some(version))
+    else {
+        return (None, vec![]);
+    };
+    operations.truncate(position + 1);
+    resolve_published(operations)
+}
+
+/// Resolve the DID state before and after processing the last of the given operations.
+///
+/// The state before is `None` if the DID is not yet created before the last operation.
+/// The last entry of the debug list tells whether the last operation is rejected.
+pub fn resolve_published_transition(
+    mut operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+) -> (Option<DidState>, Option<DidState>, ResolutionDebug) {
+    operations.sort_by(|a, b| OperationMetadata::compare_time_asc(&a.0, &b.0));
+    let Some((metadata, operation)) = operations.pop() else {
+        return (None, None, vec![]);
+    };
+    let (state_ctx, mut debug) = process_published(operations);
+    let before = state_ctx.as_ref().map(|ctx| ctx.state.clone().finalize());
+    let (state_ctx, error) = match state_ctx {
+        Some(ctx) => {
+            let (ctx, error) = ctx.process(operation.clone(), metadata.clone());
+            (Some(ctx), error)
+        }
+        None => match init_published_context(operation.clone(), metadata.clone()) {
+            Ok(ctx) => (Some(ctx), None),
+            Err(e) => (None, Some(e)),
+        },
+    };
+    debug.push((metadata, operation, error));
+    (before, state_ctx.map(|ctx| ctx.finalize()), debug)
 }
 
 fn init_state_ops(operations: &mut OperationList) -> (Option<OperationProcessingContext<Published>>, ResolutionDebug) {
@@ -54,3 +124,12 @@ fn init_state_ops(operations: &mut OperationList) -> (Option<OperationProcessing
     }
     (None, debug)
 }
+
+/// A version of a VDR entry produced by an operation that changed the entry.
+#[derive(Debug, Clone)]
+pub struct StorageVersion {
+    pub operation_hash: Sha256Digest,
+    pub metadata: OperationMetadata,
+    /// The data after the operation is applied or `None` if the operation deactivated the entry or the DID.
+    pub data: Option<StorageData>,
+}
@@ -206,7 +206,7 @@ impl OperationProcessorOps for V1Processor {
     fn update_storage(
         &self,
         state: &DidStateRc,
-        _metadata: OperationMetadata,
+        metadata: OperationMetadata,
         operation: ProtoUpdateStorageEntry,
         prism_operation_special_fields: SpecialFields,
     ) -> Result<DidStateRc, ProcessError> {
@@ -222,7 +222,8 @@ impl OperationProcessorOps for V1Processor {
         candidate_state.update_storage(
             &parsed_operation.prev_event_hash,
             &operation_hash,
-            parsed_operation.data,
+            |current| parsed_operation.apply(current),
+            &metadata,
         )?;
         candidate_state.with_last_operation_hash(operation_hash);
 
@@ -0,0 +1,121 @@
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use identus_apollo::base64::Base64UrlStrNoPad;
+
+use crate::did::operation::StatusListData;
+
+/// The minimum length in bits of an uncompressed status list required by the specifications for herd privacy.
+pub const MIN_BITSTRING_LENGTH: usize = 131_072;
+/// The maximum length in bits of a status list, which bounds the memory needed to apply and encode its statuses.
+pub const MAX_BITSTRING_LENGTH: usize = 1 << 27;
+
+/// The status purposes defined by [Bitstring Status List v1.0](https://www.w3.org/TR/vc-bitstring-status-list/).
+const BITSTRING_STATUS_PURPOSES: [&str; 4] = ["revocation", "suspension", "refresh", "message"];
+/// The status purposes defined by [Status List 2021](https://www.w3.org/TR/2023/WD-vc-status-list-20230427/).
+const STATUS_LIST_2021_STATUS_PURPOSES: [&str; 2] = ["revocation", "suspension"];
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum Error {
+    #[display("status size {status_size} is not supported by {format}")]
+    UnsupportedStatusSize { status_size: u32, format: StatusListFormat },
+    #[display("status list of {list_size} statuses of {status_size} bits is longer than {MAX_BITSTRING_LENGTH} bits")]
+    UnsupportedListSize { list_size: u32, status_size: u32 },
+    #[display("status purpose '{status_purpose}' is not supported by {format}")]
+    UnsupportedStatusPurpose {
+        status_purpose: String,
+        format: StatusListFormat,
+    },
+    #[display("unable to compress the status list")]
+    Compression { source: std::io::Error },
+}
+
+/// The status list credential formats that can be derived from a status list entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, derive_more::Display, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum StatusListFormat {
+    /// [Bitstring Status List v1.0](https://www.w3.org/TR/vc-bitstring-status-list/)
+    #[default]
+    #[display("BitstringStatusList")]
+    BitstringStatusList,
+    /// [Status List 2021](https://www.w3.org/TR/2023/WD-vc-status-list-20230427/)
+    #[display("StatusList2021")]
+    StatusList2021,
+}
+
+/// The `credentialSubject` of a status list credential.
+///
+/// The issuer is expected to wrap it in a credential and sign it,
+/// since the node does not hold the keys of the DID.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct StatusListCredentialSubject {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub r#type: StatusListFormat,
+    pub status_purpose: String,
+    /// The GZIP-compressed bitstring encoded as required by the format.
+    pub encoded_list: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_size: Option<u32>,
+}
+
+impl StatusListCredentialSubject {
+    /// Derive the credential subject from a status list entry.
+    ///
+    /// The status purpose is taken from the entry name and must be one of the purposes defined by the format.
+    pub fn new(id: String, status_list: &StatusListData, format: StatusListFormat) -> Result<Self, Error> {
+        let status_size = match (format, status_list.status_size) {
+            (_, 0 | 1) => None,
+            (StatusListFormat::BitstringStatusList, status_size) => Some(status_size),
+            (StatusListFormat::StatusList2021, status_size) => {
+                Err(Error::UnsupportedStatusSize { status_size, format })?
+            }
+        };
+        let status_purposes = match format {
+            StatusListFormat::BitstringStatusList => BITSTRING_STATUS_PURPOSES.as_slice(),
+            StatusListFormat::StatusList2021 => STATUS_LIST_2021_STATUS_PURPOSES.as_slice(),
+        };
+        if !status_purposes.contains(&status_list.name.as_str()) {
+            Err(Error::UnsupportedStatusPurpose {
+                status_purpose: status_list.name.clone(),
+                format,
+            })?
+        }
+        Ok(Self {
+            id,
+            r#type: format,
+            status_purpose: status_list.name.clone(),
+            encoded_list: encode_list(status_list, format)?,
+            status_size,
+        })
+    }
+}
+
+/// Compress the statuses of the entry as the `encodedList` of the given format.
+///
+/// The statuses are cut or zero-padded to exactly `list_size` statuses,
+/// then the bitstring is zero-padded to at least [`MIN_BITSTRING_LENGTH`] bits.
+/// Lists longer than [`MAX_BITSTRING_LENGTH`] bits are not supported.
+pub fn encode_list(status_list: &StatusListData, format: StatusListFormat) -> Result<String, Error> {
+    let mut bitstring = status_list.bitstring().ok_or(Error::UnsupportedListSize {
+        list_size: status_list.list_size,
+        status_size: status_list.status_size,
+    })?;
+    bitstring.resize(bitstring.len().max(MIN_BITSTRING_LENGTH / 8), 0);
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&bitstring)
+        .map_err(|e| Error::Compression { source: e })?;
+    let compressed = encoder.finish().map_err(|e| Error::Compression { source: e })?;
+
+    let encoded = Base64UrlStrNoPad::from(compressed).to_string();
+    match format {
+        // multibase base64url (no padding) prefix
+        StatusListFormat::BitstringStatusList => Ok(format!("u{encoded}")),
+        StatusListFormat::StatusList2021 => Ok(encoded),
+    }
+}
@@ -0,0 +1,115 @@
+use identus_apollo::crypto::ed25519::Ed25519PrivateKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_apollo::crypto::x25519::X25519PrivateKey;
+use identus_did_core::DidDocument;
+use identus_did_prism::did::did_doc::VerificationMethodFormat;
+use identus_did_prism::did::operation::builder::{CreateDidOperationBuilder, sign_operation};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+use identus_did_prism::did::{CanonicalPrismDid, PrismDidOps};
+use identus_did_prism::protocol::resolver;
+
+mod test_utils;
+
+fn resolve_did_document(format: VerificationMethodFormat) -> DidDocument {
+    let master_sk = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap();
+    let issuing_sk = Secp256k1PrivateKey::from_slice(&[2; 32]).unwrap();
+    let auth_sk = Ed25519PrivateKey::from_slice(&[3; 32]).unwrap();
+    let agreement_sk = X25519PrivateKey::from_slice(&[4; 32]).unwrap();
+    let operation = CreateDidOperationBuilder::new()
+        .add_key(
+            "master-0",
+            KeyUsage::MasterKey,
+            NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+        )
+        .add_key(
+            "issuing-0",
+            KeyUsage::IssuingKey,
+            NonOperationPublicKey::Secp256k1(issuing_sk.to_public_key()),
+        )
+        .add_key(
+            "auth-0",
+            KeyUsage::AuthenticationKey,
+            NonOperationPublicKey::Ed25519(auth_sk.to_public_key()),
+        )
+        .add_key(
+            "agreement-0",
+            KeyUsage::KeyAgreementKey,
+            NonOperationPublicKey::X25519(agreement_sk.to_public_key()),
+        )
+        .build();
+    let did = CanonicalPrismDid::from_operation(&operation).unwrap();
+    let operations = test_utils::populate_metadata(vec![sign_operation(operation, "master-0", &master_sk)]);
+    let state = resolver::resolve_published(operations).0.unwrap();
+    state.to_did_document_with_format(&did.to_did(), format)
+}
+
+fn verification_method_types(did_doc: &DidDocument) -> Vec<(String, String)> {
+    let mut types = did_doc
+        .verification_method
+        .iter()
+        .map(|vm| (vm.id.rsplit('#').next().unwrap().to_string(), vm.r#type.clone()))
+        .collect::<Vec<_>>();
+    types.sort();
+    types
+}
+
+#[test]
+fn did_document_json_web_key_2020() {
+    let did_doc = resolve_did_document(VerificationMethodFormat::JsonWebKey2020);
+
+    assert_eq!(did_doc.context, vec!["https://www.w3.org/ns/did/v1"]);
+    assert_eq!(did_doc.verification_method.len(), 3);
+    for vm in &did_doc.verification_method {
+        assert_eq!(vm.r#type, "JsonWebKey2020");
+        assert!(vm.public_key_jwk.is_some());
+        assert!(vm.public_key_multibase.is_none());
+    }
+
+    let json = serde_json::to_value(&did_doc.verification_method[0]).unwrap();
+    assert!(json.get("publicKeyMultibase").is_none());
+}
+
+#[test]
+fn did_document_multikey() {
+    let did_doc = resolve_did_document(VerificationMethodFormat::Multikey);
+
+    assert_eq!(
+        did_doc.context,
+        vec!["https://www.w3.org/ns/did/v1", "https://w3id.org/security/multikey/v1"]
+    );
+    assert_eq!(did_doc.verification_method.len(), 3);
+    for vm in &did_doc.verification_method {
+        assert_eq!(vm.r#type, "Multikey");
+        assert!(vm.public_key_jwk.is_none());
+        assert!(vm.public_key_multibase.is_some());
+    }
+}
+
+#[test]
+fn did_document_key_type_specific() {
+    let did_doc = resolve_did_document(VerificationMethodFormat::KeyTypeSpecific);
+
+    assert_eq!(
+        verification_method_types(&did_doc),
+        vec![
+            ("agreement-0".to_string(), "X25519KeyAgreementKey2020".to_string()),
+            ("auth-0".to_string(), "Ed25519VerificationKey2020".to_string()),
+            ("issuing-0".to_string(), "EcdsaSecp256k1VerificationKey2019".to_string()),
+        ]
+    );
+    assert_eq!(did_doc.context.len(), 4);
+
+    let auth_key = did_doc
+        .verification_method
+        .iter()
+        .find(|vm| vm.id.ends_with("#auth-0"))
+        .unwrap();
+    assert!(
+        auth_key
+            .public_key_multibase
+            .as_ref()
+            .unwrap()
+            .to_string()
+            .starts_with("z6Mk")
+    );
+}
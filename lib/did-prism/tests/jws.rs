@@ -0,0 +1,245 @@
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use identus_apollo::base64::Base64UrlStrNoPad;
+use identus_apollo::crypto::Signer;
+use identus_apollo::crypto::ed25519::Ed25519PrivateKey;
+use identus_apollo::crypto::p256::P256PrivateKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_did_prism::did::operation::builder::{CreateDidOperationBuilder, sign_operation};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+use identus_did_prism::did::{CanonicalPrismDid, DidResolver, DidState, PrismDid, PrismDidOps};
+use identus_did_prism::dlt::OperationMetadata;
+use identus_did_prism::jws::{CompactJws, Error, VerificationRelationship, verify_jws};
+use identus_did_prism::proto::prism::SignedPrismOperation;
+use identus_did_prism::protocol::resolver;
+
+mod test_utils;
+
+const MASTER_KEY: [u8; 32] = [1; 32];
+const ISSUING_KEY: [u8; 32] = [2; 32];
+const AUTH_KEY: [u8; 32] = [3; 32];
+const P256_KEY: [u8; 32] = [4; 32];
+
+struct TestResolver {
+    did: CanonicalPrismDid,
+    operations: Vec<SignedPrismOperation>,
+}
+
+#[async_trait::async_trait]
+impl DidResolver for TestResolver {
+    type Error = Infallible;
+
+    async fn resolve(&self, did: &PrismDid) -> Result<Option<DidState>, Self::Error> {
+        let operations = self.get_operations(&did.clone().into_canonical()).await?;
+        Ok(resolver::resolve_published(operations).0)
+    }
+
+    async fn get_operations(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        if did != &self.did {
+            return Ok(vec![]);
+        }
+        Ok(test_utils::populate_metadata(self.operations.clone()))
+    }
+}
+
+fn test_resolver() -> TestResolver {
+    let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+    let operation = CreateDidOperationBuilder::new()
+        .add_key(
+            "master-0",
+            KeyUsage::MasterKey,
+            NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+        )
+        .add_key(
+            "issuing-0",
+            KeyUsage::IssuingKey,
+            NonOperationPublicKey::Secp256k1(Secp256k1PrivateKey::from_slice(&ISSUING_KEY).unwrap().to_public_key()),
+        )
+        .add_key(
+            "auth-0",
+            KeyUsage::AuthenticationKey,
+            NonOperationPublicKey::Ed25519(Ed25519PrivateKey::from_slice(&AUTH_KEY).unwrap().to_public_key()),
+        )
+        .add_key(
+            "issuing-1",
+            KeyUsage::IssuingKey,
+            NonOperationPublicKey::P256(P256PrivateKey::from_slice(&P256_KEY).unwrap().to_public_key()),
+        )
+        .build();
+    let did = CanonicalPrismDid::from_operation(&operation).unwrap();
+    TestResolver {
+        did,
+        operations: vec![sign_operation(operation, "master-0", &master_sk)],
+    }
+}
+
+/// Convert a DER signature to the fixed-size `r || s` format.
+fn der_to_fixed(der: &[u8], scalar_size: usize) -> Vec<u8> {
+    let r_len = der[3] as usize;
+    let r = &der[4..4 + r_len];
+    let s_len = der[5 + r_len] as usize;
+    let s = &der[6 + r_len..6 + r_len + s_len];
+    let pad = |i: &[u8]| {
+        let i = &i[i.len().saturating_sub(scalar_size)..];
+        let mut padded = vec![0; scalar_size - i.len()];
+        padded.extend_from_slice(i);
+        padded
+    };
+    [pad(r), pad(s)].concat()
+}
+
+fn encode_jws(header: serde_json::Value, payload: &[u8], sign: impl Fn(&[u8]) -> Vec<u8>) -> String {
+    let header = Base64UrlStrNoPad::from(serde_json::to_vec(&header).unwrap());
+    let payload = Base64UrlStrNoPad::from(payload);
+    let signing_input = format!("{}.{}", header, payload);
+    let signature = Base64UrlStrNoPad::from(sign(signing_input.as_bytes()));
+    format!("{}.{}", signing_input, signature)
+}
+
+#[test]
+fn verify_es256k_jwt_with_assertion_method() {
+    let resolver = test_resolver();
+    let kid = format!("{}#issuing-0", resolver.did);
+    let sk = Secp256k1PrivateKey::from_slice(&ISSUING_KEY).unwrap();
+    let jwt = encode_jws(
+        serde_json::json!({ "alg": "ES256K", "typ": "JWT", "kid": kid }),
+        br#"{"iss":"did:prism:issuer"}"#,
+        |m| der_to_fixed(&sk.sign(m), 32),
+    );
+
+    let verified =
+        test_utils::block_on(verify_jws(&resolver, &jwt, VerificationRelationship::AssertionMethod)).unwrap();
+    assert_eq!(verified.kid, kid);
+    assert_eq!(verified.did.clone().into_canonical(), resolver.did);
+    assert_eq!(verified.claims().unwrap()["iss"], "did:prism:issuer");
+    assert_eq!(verified.header["typ"], "JWT");
+
+    let result = test_utils::block_on(verify_jws(&resolver, &jwt, VerificationRelationship::Authentication));
+    assert!(matches!(result, Err(Error::KeyRelationshipMismatch { .. })));
+}
+
+#[test]
+fn verify_eddsa_and_es256_jws() {
+    let resolver = test_resolver();
+    let state = test_utils::block_on(resolver.resolve(&resolver.did.clone().into()))
+        .unwrap()
+        .unwrap();
+
+    let ed25519_sk = Ed25519PrivateKey::from_slice(&AUTH_KEY).unwrap();
+    let jws = encode_jws(
+        serde_json::json!({ "alg": "EdDSA", "kid": format!("{}#auth-0", resolver.did) }),
+        b"hello world",
+        |m| ed25519_sk.sign(m),
+    );
+    let verified = CompactJws::from_str(&jws)
+        .unwrap()
+        .verify(&state, VerificationRelationship::Authentication)
+        .unwrap();
+    assert_eq!(verified.payload, b"hello world");
+
+    let p256_sk = P256PrivateKey::from_slice(&P256_KEY).unwrap();
+    let jws = encode_jws(
+        serde_json::json!({ "alg": "ES256", "kid": format!("{}#issuing-1", resolver.did) }),
+        b"hello world",
+        |m| der_to_fixed(&p256_sk.sign(m), 32),
+    );
+    let result = CompactJws::from_str(&jws)
+        .unwrap()
+        .verify(&state, VerificationRelationship::AssertionMethod);
+    assert!(result.is_ok());
+
+    // JWS signature must use the fixed-size format
+    let jws = encode_jws(
+        serde_json::json!({ "alg": "ES256", "kid": format!("{}#issuing-1", resolver.did) }),
+        b"hello world",
+        |m| p256_sk.sign(m),
+    );
+    let result = CompactJws::from_str(&jws)
+        .unwrap()
+        .verify(&state, VerificationRelationship::AssertionMethod);
+    assert!(matches!(result, Err(Error::InvalidSignature)));
+}
+
+#[test]
+fn verify_jws_with_invalid_signature_or_key() {
+    let resolver = test_resolver();
+    let sk = Secp256k1PrivateKey::from_slice(&ISSUING_KEY).unwrap();
+    let other_sk = Secp256k1PrivateKey::from_slice(&[9; 32]).unwrap();
+
+    let jws = encode_jws(
+        serde_json::json!({ "alg": "ES256K", "kid": format!("{}#issuing-0", resolver.did) }),
+        b"hello world",
+        |m| der_to_fixed(&other_sk.sign(m), 32),
+    );
+    let result = test_utils::block_on(verify_jws(&resolver, &jws, VerificationRelationship::AssertionMethod));
+    assert!(matches!(result, Err(Error::InvalidSignature)));
+
+    let jws = encode_jws(
+        serde_json::json!({ "alg": "EdDSA", "kid": format!("{}#issuing-0", resolver.did) }),
+        b"hello world",
+        |m| der_to_fixed(&sk.sign(m), 32),
+    );
+    let result = test_utils::block_on(verify_jws(&resolver, &jws, VerificationRelationship::AssertionMethod));
+    assert!(matches!(result, Err(Error::AlgorithmKeyMismatch { .. })));
+
+    let jws = encode_jws(
+        serde_json::json!({ "alg": "ES256K", "kid": format!("{}#issuing-9", resolver.did) }),
+        b"hello world",
+        |m| der_to_fixed(&sk.sign(m), 32),
+    );
+    let result = test_utils::block_on(verify_jws(&resolver, &jws, VerificationRelationship::AssertionMethod));
+    assert!(matches!(result, Err(Error::KeyNotFound { .. })));
+
+    let unknown_did = "did:prism:0000000000000000000000000000000000000000000000000000000000000000";
+    let jws = encode_jws(
+        serde_json::json!({ "alg": "ES256K", "kid": format!("{}#issuing-0", unknown_did) }),
+        b"hello world",
+        |m| der_to_fixed(&sk.sign(m), 32),
+    );
+    let result = test_utils::block_on(verify_jws(&resolver, &jws, VerificationRelationship::AssertionMethod));
+    assert!(matches!(result, Err(Error::DidNotFound { .. })));
+}
+
+#[test]
+fn parse_invalid_compact_jws() {
+    let encode_header = |header: serde_json::Value| {
+        let header = Base64UrlStrNoPad::from(serde_json::to_vec(&header).unwrap());
+        format!("{}.aGVsbG8.c2ln", header)
+    };
+    let did = "did:prism:0000000000000000000000000000000000000000000000000000000000000000";
+
+    assert!(matches!(
+        CompactJws::from_str("aGVsbG8.aGVsbG8"),
+        Err(Error::InvalidFormat)
+    ));
+    assert!(matches!(
+        CompactJws::from_str(&encode_header(
+            serde_json::json!({ "alg": "none", "kid": format!("{did}#key-0") })
+        )),
+        Err(Error::UnsupportedAlgorithm { .. })
+    ));
+    assert!(matches!(
+        CompactJws::from_str(&encode_header(serde_json::json!({ "alg": "ES256K" }))),
+        Err(Error::MissingKid)
+    ));
+    assert!(matches!(
+        CompactJws::from_str(&encode_header(serde_json::json!({ "alg": "ES256K", "kid": did }))),
+        Err(Error::InvalidKid { .. })
+    ));
+    assert!(matches!(
+        CompactJws::from_str(&encode_header(
+            serde_json::json!({ "alg": "ES256K", "kid": "did:example:123#key-0" })
+        )),
+        Err(Error::InvalidDid { .. })
+    ));
+    assert!(matches!(
+        CompactJws::from_str(&encode_header(
+            serde_json::json!({ "alg": "ES256K", "kid": format!("{did}#key-0"), "crit": ["b64"] })
+        )),
+        Err(Error::UnsupportedCriticalHeader { .. })
+    ));
+}
@@ -0,0 +1,223 @@
+use identus_apollo::crypto::EncodeVec;
+use identus_apollo::crypto::ed25519::Ed25519PublicKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_did_prism::did::operation::builder::{
+    CreateDidOperationBuilder, CreateStorageOperationBuilder, DeactivateDidOperationBuilder,
+    DeactivateStorageOperationBuilder, UpdateDidOperationBuilder, UpdateStorageOperationBuilder, sign_operation,
+};
+use identus_did_prism::did::operation::{
+    KeyUsage, NonOperationPublicKey, ServiceEndpoint, ServiceEndpointValue, StatusListData, StatusListUpdate,
+    StorageData,
+};
+use identus_did_prism::did::{CanonicalPrismDid, DidState};
+use identus_did_prism::prelude::*;
+use identus_did_prism::protocol::resolver;
+
+mod test_utils;
+
+const MASTER_KEY: [u8; 32] = [1; 32];
+const VDR_KEY: [u8; 32] = [2; 32];
+
+fn resolve(operations: Vec<SignedPrismOperation>) -> DidState {
+    let operations = test_utils::populate_metadata(operations);
+    resolver::resolve_published(operations).0.unwrap()
+}
+
+fn create_did() -> (
+    SignedPrismOperation,
+    CanonicalPrismDid,
+    Secp256k1PrivateKey,
+    Secp256k1PrivateKey,
+) {
+    let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+    let vdr_sk = Secp256k1PrivateKey::from_slice(&VDR_KEY).unwrap();
+    let operation = CreateDidOperationBuilder::new()
+        .add_key(
+            "master-0",
+            KeyUsage::MasterKey,
+            NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+        )
+        .add_key(
+            "vdr-0",
+            KeyUsage::VdrKey,
+            NonOperationPublicKey::Secp256k1(vdr_sk.to_public_key()),
+        )
+        .add_service("service-0", "LinkedDomains", "https://example.com")
+        .add_context("https://www.w3.org/ns/did/v1")
+        .build();
+    let did = CanonicalPrismDid::from_operation(&operation).unwrap();
+    let signed_operation = sign_operation(operation, "master-0", &master_sk);
+    (signed_operation, did, master_sk, vdr_sk)
+}
+
+#[test]
+fn build_create_did_operation() {
+    let (create_did_op, did, _, vdr_sk) = create_did();
+    let state = resolve(vec![create_did_op]);
+
+    assert_eq!(state.did, did);
+    assert_eq!(state.public_keys.len(), 2);
+    let vdr_key = state.public_keys.iter().find(|pk| pk.id.as_str() == "vdr-0").unwrap();
+    assert_eq!(
+        vdr_key.data.encode_vec(),
+        vdr_sk.to_public_key().encode_compressed().to_vec()
+    );
+    assert_eq!(state.services.len(), 1);
+    assert_eq!(state.context, vec!["https://www.w3.org/ns/did/v1"]);
+}
+
+#[test]
+fn build_update_did_operation() {
+    let (create_did_op, _, master_sk, _) = create_did();
+    let state = resolve(vec![create_did_op.clone()]);
+
+    let ed25519_pk = Ed25519PublicKey::from_slice(&[3; 32]).unwrap();
+    let update_did_op = UpdateDidOperationBuilder::new(&state)
+        .add_key(
+            "auth-0",
+            KeyUsage::AuthenticationKey,
+            NonOperationPublicKey::Ed25519(ed25519_pk),
+        )
+        .remove_key("vdr-0")
+        .add_service("service-1", "LinkedDomains", "https://example.org")
+        .update_service("service-0", None, Some("https://example.com/updated"))
+        .remove_service("service-1")
+        .patch_context(vec![])
+        .build();
+    let update_did_op = sign_operation(update_did_op, "master-0", &master_sk);
+    let state = resolve(vec![create_did_op, update_did_op]);
+
+    let key_ids = state.public_keys.iter().map(|pk| pk.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(key_ids.len(), 2);
+    assert!(key_ids.contains(&"master-0"));
+    assert!(key_ids.contains(&"auth-0"));
+    assert_eq!(state.services.len(), 1);
+    assert_eq!(
+        state.services[0].service_endpoint,
+        ServiceEndpoint::Value(ServiceEndpointValue::Uri("https://example.com/updated".to_string()))
+    );
+    assert!(state.context.is_empty());
+}
+
+#[test]
+fn build_deactivate_did_operation() {
+    let (create_did_op, _, master_sk, _) = create_did();
+    let state = resolve(vec![create_did_op.clone()]);
+
+    let deactivate_did_op = sign_operation(
+        DeactivateDidOperationBuilder::new(&state).build(),
+        "master-0",
+        &master_sk,
+    );
+    let state = resolve(vec![create_did_op, deactivate_did_op]);
+
+    assert!(state.public_keys.is_empty());
+    assert!(state.services.is_empty());
+}
+
+#[test]
+fn build_storage_operations() {
+    let (create_did_op, did, _, vdr_sk) = create_did();
+
+    let create_storage_op = CreateStorageOperationBuilder::new(&did, StorageData::Bytes(vec![1, 2, 3]))
+        .nonce(&[0])
+        .build();
+    let create_storage_op = sign_operation(create_storage_op, "vdr-0", &vdr_sk);
+    let state = resolve(vec![create_did_op.clone(), create_storage_op.clone()]);
+    assert_eq!(state.storage.len(), 1);
+    assert_eq!(*state.storage[0].data, StorageData::Bytes(vec![1, 2, 3]));
+
+    let update_storage_op =
+        UpdateStorageOperationBuilder::new(&state.storage[0], StorageData::Bytes(vec![4, 5, 6])).build();
+    let update_storage_op = sign_operation(update_storage_op, "vdr-0", &vdr_sk);
+    let state = resolve(vec![
+        create_did_op.clone(),
+        create_storage_op.clone(),
+        update_storage_op.clone(),
+    ]);
+    assert_eq!(state.storage.len(), 1);
+    assert_eq!(*state.storage[0].data, StorageData::Bytes(vec![4, 5, 6]));
+
+    let deactivate_storage_op = DeactivateStorageOperationBuilder::new(&state.storage[0]).build();
+    let deactivate_storage_op = sign_operation(deactivate_storage_op, "vdr-0", &vdr_sk);
+    let state = resolve(vec![
+        create_did_op,
+        create_storage_op,
+        update_storage_op,
+        deactivate_storage_op,
+    ]);
+    assert!(state.storage.is_empty());
+}
+
+#[test]
+fn build_status_list_update_operations() {
+    let (create_did_op, did, _, vdr_sk) = create_did();
+    let status_list = StatusListData {
+        state: 1,
+        name: "revocation".to_string(),
+        detail: String::new(),
+        list_size: 12,
+        status_size: 2,
+        int_status: vec![0b0100_0000, 0, 0b1100_0000],
+    };
+
+    let create_storage_op = CreateStorageOperationBuilder::new(&did, StorageData::Bytes(vec![1, 2, 3])).build();
+    let create_storage_op = sign_operation(create_storage_op, "vdr-0", &vdr_sk);
+    let state = resolve(vec![create_did_op.clone(), create_storage_op.clone()]);
+
+    // an entry of another type becomes the whole status list
+    let update_storage_op =
+        UpdateStorageOperationBuilder::new(&state.storage[0], StorageData::StatusList(status_list.clone())).build();
+    let update_storage_op_1 = sign_operation(update_storage_op, "vdr-0", &vdr_sk);
+    let state = resolve(vec![
+        create_did_op.clone(),
+        create_storage_op.clone(),
+        update_storage_op_1.clone(),
+    ]);
+    assert_eq!(*state.storage[0].data, StorageData::StatusList(status_list.clone()));
+
+    // a status list is updated with the diff to the given status list
+    let shrunk_status_list = StatusListData {
+        state: 2,
+        list_size: 4,
+        int_status: vec![0b1000_0000],
+        ..status_list.clone()
+    };
+    let update_storage_op =
+        UpdateStorageOperationBuilder::new(&state.storage[0], StorageData::StatusList(shrunk_status_list)).build();
+    let update_storage_op_2 = sign_operation(update_storage_op, "vdr-0", &vdr_sk);
+    let state = resolve(vec![
+        create_did_op.clone(),
+        create_storage_op.clone(),
+        update_storage_op_1.clone(),
+        update_storage_op_2.clone(),
+    ]);
+    let expected = StatusListData {
+        state: 2,
+        int_status: vec![0b1000_0000, 0, 0],
+        ..status_list.clone()
+    };
+    assert_eq!(*state.storage[0].data, StorageData::StatusList(expected.clone()));
+
+    // a partial update only changes the given fields and statuses
+    let update = StatusListUpdate {
+        detail: Some("suspended".to_string()),
+        int_status: vec![0, 0b0011_0000],
+        ..Default::default()
+    };
+    let update_storage_op = UpdateStorageOperationBuilder::status_list(&state.storage[0], update).build();
+    let update_storage_op_3 = sign_operation(update_storage_op, "vdr-0", &vdr_sk);
+    let state = resolve(vec![
+        create_did_op,
+        create_storage_op,
+        update_storage_op_1,
+        update_storage_op_2,
+        update_storage_op_3,
+    ]);
+    let expected = StatusListData {
+        detail: "suspended".to_string(),
+        int_status: vec![0b1000_0000, 0b0011_0000, 0],
+        ..expected
+    };
+    assert_eq!(*state.storage[0].data, StorageData::StatusList(expected));
+}
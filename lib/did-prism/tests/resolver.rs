@@ -0,0 +1,127 @@
+use identus_apollo::crypto::ed25519::Ed25519PublicKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_apollo::hash::Sha256Digest;
+use identus_did_prism::did::operation::builder::{
+    CreateDidOperationBuilder, UpdateDidOperationBuilder, sign_operation,
+};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+use identus_did_prism::did::{CanonicalPrismDid, DidResolver, LongFormPrismDid, LongFormResolver, PrismDid};
+use identus_did_prism::prelude::*;
+use identus_did_prism::protocol::resolver;
+
+mod test_utils;
+
+const MASTER_KEY: [u8; 32] = [1; 32];
+
+fn create_did_operation() -> (PrismOperation, Secp256k1PrivateKey) {
+    let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+    let operation = CreateDidOperationBuilder::new()
+        .add_key(
+            "master-0",
+            KeyUsage::MasterKey,
+            NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+        )
+        .build();
+    (operation, master_sk)
+}
+
+#[test]
+fn long_form_resolver_resolves_only_long_form_did() {
+    let (operation, _) = create_did_operation();
+    let long_form_did = LongFormPrismDid::from_operation(&operation).unwrap();
+    let canonical_did = CanonicalPrismDid::from_operation(&operation).unwrap();
+    let operation_hash = operation.operation_hash();
+
+    let state = test_utils::block_on(LongFormResolver.resolve(&long_form_did.clone().into()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.did, canonical_did);
+    assert_eq!(state.public_keys.len(), 1);
+
+    let did: PrismDid = long_form_did.into();
+    let state = test_utils::block_on(LongFormResolver.resolve_at_version(&did, &operation_hash)).unwrap();
+    assert!(state.is_some());
+    let state =
+        test_utils::block_on(LongFormResolver.resolve_at_version(&did, &Sha256Digest::from_bytes(&[0; 32]).unwrap()))
+            .unwrap();
+    assert!(state.is_none());
+
+    let state = test_utils::block_on(LongFormResolver.resolve(&canonical_did.into())).unwrap();
+    assert!(state.is_none());
+}
+
+#[test]
+fn resolve_published_at_version() {
+    let (operation, master_sk) = create_did_operation();
+    let create_op_hash = operation.operation_hash();
+    let create_op = sign_operation(operation, "master-0", &master_sk);
+    let state = resolver::resolve_published(test_utils::populate_metadata(vec![create_op.clone()]))
+        .0
+        .unwrap();
+
+    let update_op = UpdateDidOperationBuilder::new(&state)
+        .add_key(
+            "auth-0",
+            KeyUsage::AuthenticationKey,
+            NonOperationPublicKey::Ed25519(Ed25519PublicKey::from_slice(&[3; 32]).unwrap()),
+        )
+        .build();
+    let update_op_hash = update_op.operation_hash();
+    let update_op = sign_operation(update_op, "master-0", &master_sk);
+    let operations = test_utils::populate_metadata(vec![create_op, update_op]);
+
+    let state = resolver::resolve_published_at_version(operations.clone(), &create_op_hash)
+        .0
+        .unwrap();
+    assert_eq!(state.public_keys.len(), 1);
+    assert_eq!(*state.last_operation_hash, create_op_hash);
+
+    let state = resolver::resolve_published_at_version(operations.clone(), &update_op_hash)
+        .0
+        .unwrap();
+    assert_eq!(state.public_keys.len(), 2);
+    assert_eq!(*state.last_operation_hash, update_op_hash);
+
+    let state = resolver::resolve_published_at_version(operations, &Sha256Digest::from_bytes(&[0; 32]).unwrap()).0;
+    assert!(state.is_none());
+}
+
+#[test]
+fn resolve_published_transition() {
+    let (operation, master_sk) = create_did_operation();
+    let create_op = sign_operation(operation, "master-0", &master_sk);
+    let state = resolver::resolve_published(test_utils::populate_metadata(vec![create_op.clone()]))
+        .0
+        .unwrap();
+    let update_op = UpdateDidOperationBuilder::new(&state)
+        .add_key(
+            "auth-0",
+            KeyUsage::AuthenticationKey,
+            NonOperationPublicKey::Ed25519(Ed25519PublicKey::from_slice(&[3; 32]).unwrap()),
+        )
+        .build();
+    let other_sk = Secp256k1PrivateKey::from_slice(&[2; 32]).unwrap();
+
+    let (before, after, debug) =
+        resolver::resolve_published_transition(test_utils::populate_metadata(vec![create_op.clone()]));
+    assert!(before.is_none());
+    assert_eq!(after.unwrap().public_keys.len(), 1);
+    assert!(debug.last().unwrap().2.is_none());
+
+    let operations = test_utils::populate_metadata(vec![
+        create_op.clone(),
+        sign_operation(update_op.clone(), "master-0", &master_sk),
+    ]);
+    let (before, after, debug) = resolver::resolve_published_transition(operations);
+    assert_eq!(before.unwrap().public_keys.len(), 1);
+    assert_eq!(after.unwrap().public_keys.len(), 2);
+    assert_eq!(debug.len(), 2);
+    assert!(debug.last().unwrap().2.is_none());
+
+    // the update signed with an unknown key is rejected and does not change the state
+    let operations = test_utils::populate_metadata(vec![create_op, sign_operation(update_op, "master-0", &other_sk)]);
+    let (before, after, debug) = resolver::resolve_published_transition(operations);
+    assert_eq!(before.unwrap().public_keys.len(), 1);
+    assert_eq!(after.unwrap().public_keys.len(), 1);
+    assert!(debug.last().unwrap().2.is_some());
+}
@@ -0,0 +1,88 @@
+use std::str::FromStr;
+
+use identus_apollo::crypto::hdkey::Seed;
+use identus_apollo::hex::HexStr;
+use identus_did_prism::did::CanonicalPrismDid;
+use identus_did_prism::did::operation::builder::{CreateDidOperationBuilder, sign_operation};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey};
+use identus_did_prism::prelude::*;
+use identus_did_prism::proto;
+use identus_did_prism::proto::prism_ssi::public_key::Key_data;
+use identus_did_prism::protocol::resolver;
+use protobuf::Enum;
+use serde_json::Value;
+
+mod test_utils;
+
+/// A `CreateDID` operation built from an HD seed following the Identus SDK conventions.
+///
+/// The same fixture is checked against the Identus SDK in the `prism-test` suite,
+/// so both implementations must produce identical keys, operation bytes and DID.
+const FIXTURE: &str = include_str!("fixtures/sdk_create_did.json");
+
+fn hex_bytes(value: &Value) -> Vec<u8> {
+    HexStr::from_str(value.as_str().unwrap()).unwrap().to_bytes()
+}
+
+#[test]
+fn sdk_create_did_fixture_resolves() {
+    let fixture: Value = serde_json::from_str(FIXTURE).unwrap();
+    let seed = Seed::from_mnemonic(
+        fixture["mnemonic"].as_str().unwrap(),
+        fixture["passphrase"].as_str().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(seed.as_bytes(), hex_bytes(&fixture["seed"]));
+
+    let mut builder = CreateDidOperationBuilder::new();
+    let mut master_sk = None;
+    for key in fixture["publicKeys"].as_array().unwrap() {
+        let sk = seed
+            .derive_secp256k1_from_str(key["derivationPath"].as_str().unwrap())
+            .unwrap();
+        let pk = sk.to_public_key();
+        assert_eq!(pk.encode_compressed().to_vec(), hex_bytes(&key["data"]));
+
+        let proto_usage = proto::prism_ssi::KeyUsage::from_str(key["usage"].as_str().unwrap()).unwrap();
+        let usage = KeyUsage::parse(&proto_usage).unwrap();
+        if usage == KeyUsage::MasterKey {
+            master_sk = Some(sk);
+        }
+        builder = builder.add_key(key["id"].as_str().unwrap(), usage, NonOperationPublicKey::Secp256k1(pk));
+    }
+    for service in fixture["services"].as_array().unwrap() {
+        builder = builder.add_service(
+            service["id"].as_str().unwrap(),
+            service["type"].as_str().unwrap(),
+            service["serviceEndpoint"].as_str().unwrap(),
+        );
+    }
+    let operation = builder.build();
+    assert_eq!(operation.encode_to_vec(), hex_bytes(&fixture["operation"]));
+
+    let did = CanonicalPrismDid::from_operation(&operation).unwrap();
+    assert_eq!(did.to_string(), fixture["did"].as_str().unwrap());
+
+    let signed_operation = sign_operation(operation, "master-0", &master_sk.unwrap());
+    let operations = test_utils::populate_metadata(vec![signed_operation]);
+    let state = resolver::resolve_published(operations).0.unwrap();
+    assert_eq!(state.did, did);
+    assert_eq!(state.public_keys.len(), fixture["publicKeys"].as_array().unwrap().len());
+    for expected in fixture["publicKeys"].as_array().unwrap() {
+        let pk = state
+            .public_keys
+            .iter()
+            .find(|pk| pk.orig.id == expected["id"].as_str().unwrap())
+            .unwrap();
+        let Some(Key_data::CompressedEcKeyData(key_data)) = &pk.orig.key_data else {
+            panic!("public key is not compressed")
+        };
+        assert_eq!(key_data.curve, expected["curve"].as_str().unwrap());
+        assert_eq!(key_data.data, hex_bytes(&expected["data"]));
+    }
+    assert_eq!(state.services.len(), 1);
+    assert_eq!(
+        state.services[0].orig.service_endpoint,
+        fixture["services"][0]["serviceEndpoint"].as_str().unwrap()
+    );
+}
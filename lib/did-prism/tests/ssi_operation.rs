@@ -1,5 +1,9 @@
+use identus_apollo::crypto::EncodeVec;
+use identus_apollo::crypto::p256::P256PrivateKey;
+use identus_apollo::crypto::p384::P384PrivateKey;
 use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
-use identus_did_prism::did::operation::KeyUsage;
+use identus_did_prism::did::operation::builder::{CreateDidOperationBuilder, sign_operation};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey, PublicKeyData};
 use identus_did_prism::proto;
 use identus_did_prism::protocol::resolver;
 
@@ -54,3 +58,80 @@ fn create_did_with_non_master_key() {
     assert_eq!(vdr_key.data.usage(), KeyUsage::VdrKey);
     assert_eq!(auth_key.data.usage(), KeyUsage::AuthenticationKey);
 }
+
+#[test]
+fn create_did_with_nist_curve_key() {
+    let p256_pk = P256PrivateKey::from_slice(&[2; 32]).unwrap().to_public_key();
+    let p384_pk = P384PrivateKey::from_slice(&[3; 48]).unwrap().to_public_key();
+    let new_key = |id: &str, usage, curve: &str, data: Vec<u8>| proto::prism_ssi::PublicKey {
+        id: id.to_string(),
+        usage,
+        key_data: Some(proto::prism_ssi::public_key::Key_data::CompressedEcKeyData(
+            proto::prism_ssi::CompressedECKeyData {
+                curve: curve.to_string(),
+                data,
+                special_fields: Default::default(),
+            },
+        )),
+        special_fields: Default::default(),
+    };
+    let options = test_utils::CreateDidOptions {
+        public_keys: Some(vec![
+            new_key(
+                "issuing-0",
+                proto::prism_ssi::KeyUsage::ISSUING_KEY.into(),
+                "P-256",
+                p256_pk.encode_vec(),
+            ),
+            new_key(
+                "auth-0",
+                proto::prism_ssi::KeyUsage::AUTHENTICATION_KEY.into(),
+                "P-384",
+                p384_pk.encode_vec(),
+            ),
+        ]),
+        ..Default::default()
+    };
+    let (create_did_op, _, _) = test_utils::new_create_did_operation(Some(options));
+
+    let operations = test_utils::populate_metadata(vec![create_did_op]);
+    let state = resolver::resolve_published(operations).0.unwrap();
+
+    let issuing_key = state
+        .public_keys
+        .iter()
+        .find(|pk| pk.id.as_str() == "issuing-0")
+        .unwrap();
+    let auth_key = state.public_keys.iter().find(|pk| pk.id.as_str() == "auth-0").unwrap();
+
+    assert_eq!(state.public_keys.len(), 3);
+    assert_eq!(
+        issuing_key.data,
+        PublicKeyData::Other {
+            data: NonOperationPublicKey::P256(p256_pk),
+            usage: KeyUsage::IssuingKey
+        }
+    );
+    assert_eq!(
+        auth_key.data,
+        PublicKeyData::Other {
+            data: NonOperationPublicKey::P384(p384_pk),
+            usage: KeyUsage::AuthenticationKey
+        }
+    );
+}
+
+#[test]
+fn create_did_with_nist_curve_master_key() {
+    let p256_pk = P256PrivateKey::from_slice(&[2; 32]).unwrap().to_public_key();
+    let operation = CreateDidOperationBuilder::new()
+        .add_key("master-0", KeyUsage::MasterKey, NonOperationPublicKey::P256(p256_pk))
+        .build();
+    let signing_key = Secp256k1PrivateKey::from_slice(&[1; 32]).unwrap();
+    let create_did_op = sign_operation(operation, "master-0", &signing_key);
+
+    let operations = test_utils::populate_metadata(vec![create_did_op]);
+    let state = resolver::resolve_published(operations).0;
+
+    assert!(state.is_none());
+}
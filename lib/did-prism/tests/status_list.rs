@@ -0,0 +1,140 @@
+use std::io::Read;
+use std::str::FromStr;
+
+use flate2::read::GzDecoder;
+use identus_apollo::base64::Base64UrlStrNoPad;
+use identus_did_prism::did::operation::StatusListData;
+use identus_did_prism::status_list::{
+    Error, MAX_BITSTRING_LENGTH, MIN_BITSTRING_LENGTH, StatusListCredentialSubject, StatusListFormat, encode_list,
+};
+
+fn status_list(list_size: u32, status_size: u32, int_status: Vec<u8>) -> StatusListData {
+    StatusListData {
+        state: 0,
+        name: String::new(),
+        detail: String::new(),
+        list_size,
+        status_size,
+        int_status,
+    }
+}
+
+fn decode_list(encoded: &str) -> Vec<u8> {
+    let compressed = Base64UrlStrNoPad::from_str(encoded).unwrap().to_bytes();
+    let mut bitstring = vec![];
+    GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut bitstring)
+        .unwrap();
+    bitstring
+}
+
+#[test]
+fn bitstring_status_list_subject() {
+    let mut data = status_list(16, 1, vec![0b1010_0000, 0b0000_0001]);
+    data.name = "revocation".to_string();
+    let subject = StatusListCredentialSubject::new(
+        "https://example.com/status/1#list".to_string(),
+        &data,
+        StatusListFormat::BitstringStatusList,
+    )
+    .unwrap();
+
+    assert_eq!(subject.status_purpose, "revocation");
+    assert_eq!(subject.status_size, None);
+    let encoded = subject.encoded_list.strip_prefix('u').unwrap();
+    let bitstring = decode_list(encoded);
+    assert_eq!(bitstring.len(), MIN_BITSTRING_LENGTH / 8);
+    assert_eq!(&bitstring[..2], &[0b1010_0000, 0b0000_0001]);
+    assert!(bitstring[2..].iter().all(|b| *b == 0));
+
+    let json = serde_json::to_value(&subject).unwrap();
+    assert_eq!(json["type"], "BitstringStatusList");
+    assert_eq!(json["statusPurpose"], "revocation");
+    assert!(json.get("statusSize").is_none());
+}
+
+#[test]
+fn status_list_2021_subject() {
+    let mut data = status_list(8, 1, vec![0b0100_0000]);
+    data.name = "suspension".to_string();
+    let subject = StatusListCredentialSubject::new(
+        "https://example.com/status/1#list".to_string(),
+        &data,
+        StatusListFormat::StatusList2021,
+    )
+    .unwrap();
+
+    assert_eq!(subject.status_purpose, "suspension");
+    assert_eq!(decode_list(&subject.encoded_list)[0], 0b0100_0000);
+
+    data.status_size = 2;
+    let result = StatusListCredentialSubject::new(
+        "https://example.com/status/1#list".to_string(),
+        &data,
+        StatusListFormat::StatusList2021,
+    );
+    assert!(matches!(
+        result,
+        Err(Error::UnsupportedStatusSize { status_size: 2, .. })
+    ));
+}
+
+#[test]
+fn encode_list_covers_list_size() {
+    let data = status_list(100_000, 2, vec![0xff]);
+    let encoded = encode_list(&data, StatusListFormat::BitstringStatusList).unwrap();
+    let bitstring = decode_list(encoded.strip_prefix('u').unwrap());
+    assert_eq!(bitstring.len(), 200_000 / 8);
+    assert_eq!(bitstring[0], 0xff);
+}
+
+#[test]
+fn encode_list_cuts_statuses_beyond_list_size() {
+    let data = status_list(10, 1, vec![0xff, 0xff, 0xff]);
+    let encoded = encode_list(&data, StatusListFormat::BitstringStatusList).unwrap();
+    let bitstring = decode_list(encoded.strip_prefix('u').unwrap());
+    assert_eq!(bitstring.len(), MIN_BITSTRING_LENGTH / 8);
+    assert_eq!(&bitstring[..2], &[0xff, 0b1100_0000]);
+    assert!(bitstring[2..].iter().all(|b| *b == 0));
+}
+
+#[test]
+fn encode_list_rejects_list_longer_than_max_length() {
+    let data = status_list((MAX_BITSTRING_LENGTH / 8) as u32, 8, vec![0xff]);
+    assert!(encode_list(&data, StatusListFormat::BitstringStatusList).is_ok());
+
+    for (list_size, status_size) in [((MAX_BITSTRING_LENGTH / 8) as u32 + 1, 8), (u32::MAX, u32::MAX)] {
+        let data = status_list(list_size, status_size, vec![0xff]);
+        let result = encode_list(&data, StatusListFormat::BitstringStatusList);
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedListSize { list_size: l, status_size: s }) if l == list_size && s == status_size
+        ));
+    }
+}
+
+#[test]
+fn status_purpose_must_be_defined_by_format() {
+    let mut data = status_list(8, 1, vec![0]);
+    for (name, format) in [
+        ("", StatusListFormat::BitstringStatusList),
+        ("credential status", StatusListFormat::BitstringStatusList),
+        ("refresh", StatusListFormat::StatusList2021),
+    ] {
+        data.name = name.to_string();
+        let result = StatusListCredentialSubject::new("https://example.com/status/1#list".to_string(), &data, format);
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedStatusPurpose { status_purpose, .. }) if status_purpose == name
+        ));
+    }
+
+    data.name = "refresh".to_string();
+    let subject = StatusListCredentialSubject::new(
+        "https://example.com/status/1#list".to_string(),
+        &data,
+        StatusListFormat::BitstringStatusList,
+    )
+    .unwrap();
+    assert_eq!(subject.status_purpose, "refresh");
+}
@@ -2,7 +2,7 @@ use std::ops::Deref;
 
 use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
 use identus_apollo::hash::Sha256Digest;
-use identus_did_prism::did::operation::StorageData;
+use identus_did_prism::did::operation::{StatusListData, StorageData};
 use identus_did_prism::did::{CanonicalPrismDid, PrismDidOps};
 use identus_did_prism::proto;
 use identus_did_prism::protocol::resolver;
@@ -402,7 +402,7 @@ fn storage_revoked_after_deactivate_did() {
             special_fields: Default::default(),
         }),
     );
-    let (deactivate_did_op, _) = test_utils::new_signed_operation(
+    let (deactivate_did_op, deactivate_did_op_hash) = test_utils::new_signed_operation(
         "master-0",
         &master_sk,
         proto::prism::prism_operation::Operation::DeactivateDid(proto::prism_ssi::ProtoDeactivateDID {
@@ -413,9 +413,297 @@ fn storage_revoked_after_deactivate_did() {
     );
 
     let operations = test_utils::populate_metadata(vec![create_did_op, create_storage_op, deactivate_did_op]);
+    let state = resolver::resolve_published(operations.clone()).0.unwrap();
+    assert!(state.storage.is_empty());
+
+    let history = resolver::resolve_storage_history(operations, &create_storage_op_hash);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].operation_hash, create_storage_op_hash);
+    assert_eq!(history[0].data, Some(StorageData::Bytes(vec![1, 2, 3])));
+    assert_eq!(history[1].operation_hash, deactivate_did_op_hash);
+    assert_eq!(history[1].metadata.osn, 2);
+    assert_eq!(history[1].data, None);
+}
+
+/// Create a status list entry of 16 statuses and update it with the given change.
+fn status_list_operations(
+    did: &CanonicalPrismDid,
+    vdr_sk: &Secp256k1PrivateKey,
+    update: proto::prism_storage::StatusListEntry,
+) -> (proto::prism::SignedPrismOperation, proto::prism::SignedPrismOperation) {
+    let (create_storage_op, create_storage_op_hash) = test_utils::new_signed_operation(
+        VDR_KEY_NAME,
+        vdr_sk,
+        proto::prism::prism_operation::Operation::CreateStorageEntry(proto::prism_storage::ProtoCreateStorageEntry {
+            did_prism_hash: did.suffix.to_vec(),
+            nonce: vec![0],
+            data: Some(proto::prism_storage::proto_create_storage_entry::Data::StatusListEntry(
+                proto::prism_storage::StatusListEntry {
+                    state: Some(1),
+                    name: Some("revocation".to_string()),
+                    details: Some("first batch".to_string()),
+                    status_list: Some(proto::prism_storage::StatusList {
+                        list_size: 16,
+                        status_size: 1,
+                        int_status: vec![0b1000_0000, 0b0000_0001],
+                        ..Default::default()
+                    })
+                    .into(),
+                    ..Default::default()
+                },
+            )),
+            special_fields: Default::default(),
+        }),
+    );
+    let (update_storage_op, _) = test_utils::new_signed_operation(
+        VDR_KEY_NAME,
+        vdr_sk,
+        proto::prism::prism_operation::Operation::UpdateStorageEntry(proto::prism_storage::ProtoUpdateStorageEntry {
+            previous_event_hash: create_storage_op_hash.to_vec(),
+            data: Some(proto::prism_storage::proto_update_storage_entry::Data::StatusListEntry(
+                update,
+            )),
+            special_fields: Default::default(),
+        }),
+    );
+    (create_storage_op, update_storage_op)
+}
+
+#[test]
+fn update_status_list_entry_applies_diff() {
+    let (create_did_op, _, did, _, vdr_sk) = create_did_with_vdr_key();
+    // reset state and name, keep details and flip the statuses 1, 8 and 15
+    // while the smaller list size is ignored
+    let update = proto::prism_storage::StatusListEntry {
+        state: Some(0),
+        name: Some(String::new()),
+        status_list: Some(proto::prism_storage::StatusList {
+            list_size: 8,
+            status_size: 1,
+            int_status: vec![0b0100_0000, 0b1000_0001],
+            ..Default::default()
+        })
+        .into(),
+        ..Default::default()
+    };
+    let (create_storage_op, update_storage_op) = status_list_operations(&did, &vdr_sk, update);
+
+    let operations = test_utils::populate_metadata(vec![create_did_op, create_storage_op, update_storage_op]);
     let state = resolver::resolve_published(operations).0.unwrap();
 
-    assert!(state.storage.is_empty());
+    assert_eq!(state.storage.len(), 1);
+    assert_eq!(
+        *state.storage[0].data,
+        StorageData::StatusList(StatusListData {
+            state: 0,
+            name: String::new(),
+            detail: "first batch".to_string(),
+            list_size: 16,
+            status_size: 1,
+            int_status: vec![0b1100_0000, 0b1000_0000],
+        })
+    );
+}
+
+#[test]
+fn update_status_list_entry_grows_list() {
+    let (create_did_op, _, did, _, vdr_sk) = create_did_with_vdr_key();
+    // bits beyond the 20 statuses are ignored
+    let update = proto::prism_storage::StatusListEntry {
+        status_list: Some(proto::prism_storage::StatusList {
+            list_size: 20,
+            int_status: vec![0, 0, 0b1010_1111],
+            ..Default::default()
+        })
+        .into(),
+        ..Default::default()
+    };
+    let (create_storage_op, update_storage_op) = status_list_operations(&did, &vdr_sk, update);
+
+    let operations = test_utils::populate_metadata(vec![create_did_op, create_storage_op, update_storage_op]);
+    let state = resolver::resolve_published(operations).0.unwrap();
+
+    assert_eq!(state.storage.len(), 1);
+    assert_eq!(
+        *state.storage[0].data,
+        StorageData::StatusList(StatusListData {
+            state: 1,
+            name: "revocation".to_string(),
+            detail: "first batch".to_string(),
+            list_size: 20,
+            status_size: 1,
+            int_status: vec![0b1000_0000, 0b0000_0001, 0b1010_0000],
+        })
+    );
+}
+
+#[test]
+fn update_status_list_entry_does_not_grow_beyond_max_length() {
+    let (create_did_op, _, did, _, vdr_sk) = create_did_with_vdr_key();
+    // the list size is kept while the statuses within the list are still flipped
+    let update = proto::prism_storage::StatusListEntry {
+        status_list: Some(proto::prism_storage::StatusList {
+            list_size: u32::MAX,
+            int_status: vec![0b1000_0000],
+            ..Default::default()
+        })
+        .into(),
+        ..Default::default()
+    };
+    let (create_storage_op, update_storage_op) = status_list_operations(&did, &vdr_sk, update);
+
+    let operations = test_utils::populate_metadata(vec![create_did_op, create_storage_op, update_storage_op]);
+    let state = resolver::resolve_published(operations).0.unwrap();
+
+    assert_eq!(state.storage.len(), 1);
+    let StorageData::StatusList(status_list) = state.storage[0].data.as_ref() else {
+        panic!("storage entry is not a status list");
+    };
+    assert_eq!(status_list.list_size, 16);
+    assert_eq!(status_list.int_status, vec![0b0000_0000, 0b0000_0001]);
+}
+
+#[test]
+fn did_data_contains_all_storage_entry_types() {
+    let (create_did_op, _, did, _, vdr_sk) = create_did_with_vdr_key();
+    let (create_ipfs_op, create_ipfs_op_hash) = test_utils::new_signed_operation(
+        VDR_KEY_NAME,
+        &vdr_sk,
+        proto::prism::prism_operation::Operation::CreateStorageEntry(proto::prism_storage::ProtoCreateStorageEntry {
+            did_prism_hash: did.suffix.to_vec(),
+            nonce: vec![0],
+            data: Some(proto::prism_storage::proto_create_storage_entry::Data::Ipfs(
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+            )),
+            special_fields: Default::default(),
+        }),
+    );
+    let status_list_entry = proto::prism_storage::StatusListEntry {
+        state: Some(1),
+        name: Some("revocation".to_string()),
+        details: Some("credential status".to_string()),
+        status_list: Some(proto::prism_storage::StatusList {
+            list_size: 8,
+            status_size: 1,
+            int_status: vec![0],
+            ..Default::default()
+        })
+        .into(),
+        ..Default::default()
+    };
+    let (create_status_list_op, create_status_list_op_hash) = test_utils::new_signed_operation(
+        VDR_KEY_NAME,
+        &vdr_sk,
+        proto::prism::prism_operation::Operation::CreateStorageEntry(proto::prism_storage::ProtoCreateStorageEntry {
+            did_prism_hash: did.suffix.to_vec(),
+            nonce: vec![1],
+            data: Some(proto::prism_storage::proto_create_storage_entry::Data::StatusListEntry(
+                status_list_entry.clone(),
+            )),
+            special_fields: Default::default(),
+        }),
+    );
+
+    let operations = test_utils::populate_metadata(vec![create_did_op, create_ipfs_op, create_status_list_op]);
+    let state = resolver::resolve_published(operations).0.unwrap();
+    let did_data: proto::node_api::DIDData = state.into();
+
+    assert_eq!(did_data.storage_data.len(), 2);
+    let find_data = |hash: &Sha256Digest| {
+        did_data
+            .storage_data
+            .iter()
+            .find(|s| s.init_operation_hash == hash.to_vec())
+            .unwrap()
+            .data
+            .clone()
+    };
+    assert_eq!(
+        find_data(&create_ipfs_op_hash),
+        Some(proto::node_api::storage_data::Data::Ipfs(
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string()
+        ))
+    );
+    assert_eq!(
+        find_data(&create_status_list_op_hash),
+        Some(proto::node_api::storage_data::Data::StatusListEntry(status_list_entry))
+    );
+}
+
+#[test]
+fn storage_history_follows_entry_chain() {
+    let (create_did_op, _, did, _, vdr_sk) = create_did_with_vdr_key();
+    let create_storage = |nonce: u8| {
+        test_utils::new_signed_operation(
+            VDR_KEY_NAME,
+            &vdr_sk,
+            proto::prism::prism_operation::Operation::CreateStorageEntry(
+                proto::prism_storage::ProtoCreateStorageEntry {
+                    did_prism_hash: did.suffix.to_vec(),
+                    nonce: vec![nonce],
+                    data: Some(proto::prism_storage::proto_create_storage_entry::Data::Bytes(vec![
+                        nonce,
+                    ])),
+                    special_fields: Default::default(),
+                },
+            ),
+        )
+    };
+    let update_storage = |prev: &Sha256Digest, value: u8| {
+        test_utils::new_signed_operation(
+            VDR_KEY_NAME,
+            &vdr_sk,
+            proto::prism::prism_operation::Operation::UpdateStorageEntry(
+                proto::prism_storage::ProtoUpdateStorageEntry {
+                    previous_event_hash: prev.to_vec(),
+                    data: Some(proto::prism_storage::proto_update_storage_entry::Data::Bytes(vec![
+                        value,
+                    ])),
+                    special_fields: Default::default(),
+                },
+            ),
+        )
+    };
+    let (create_storage_op, create_storage_op_hash) = create_storage(0);
+    let (other_storage_op, other_storage_op_hash) = create_storage(1);
+    let (update_storage_op, update_storage_op_hash) = update_storage(&create_storage_op_hash, 2);
+    let (other_update_op, _) = update_storage(&other_storage_op_hash, 3);
+    let (deactivate_storage_op, deactivate_storage_op_hash) = test_utils::new_signed_operation(
+        VDR_KEY_NAME,
+        &vdr_sk,
+        proto::prism::prism_operation::Operation::DeactivateStorageEntry(
+            proto::prism_storage::ProtoDeactivateStorageEntry {
+                previous_event_hash: update_storage_op_hash.to_vec(),
+                special_fields: Default::default(),
+            },
+        ),
+    );
+    // ignored since the entry is already deactivated
+    let (late_update_op, _) = update_storage(&deactivate_storage_op_hash, 4);
+
+    let operations = test_utils::populate_metadata(vec![
+        create_did_op,
+        create_storage_op,
+        other_storage_op,
+        update_storage_op,
+        other_update_op,
+        deactivate_storage_op,
+        late_update_op,
+    ]);
+    let history = resolver::resolve_storage_history(operations.clone(), &create_storage_op_hash);
+
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].operation_hash, create_storage_op_hash);
+    assert_eq!(history[0].metadata.osn, 1);
+    assert_eq!(history[0].data, Some(StorageData::Bytes(vec![0])));
+    assert_eq!(history[1].operation_hash, update_storage_op_hash);
+    assert_eq!(history[1].metadata.osn, 3);
+    assert_eq!(history[1].data, Some(StorageData::Bytes(vec![2])));
+    assert_eq!(history[2].operation_hash, deactivate_storage_op_hash);
+    assert_eq!(history[2].data, None);
+
+    let history = resolver::resolve_storage_history(operations, &update_storage_op_hash);
+    assert!(history.is_empty());
 }
 
 fn create_did_with_vdr_key() -> (
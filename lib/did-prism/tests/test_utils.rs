@@ -1,5 +1,8 @@
 #![allow(unused)]
 
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
 use chrono::DateTime;
 use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
 use identus_apollo::hash::Sha256Digest;
@@ -98,8 +101,10 @@ pub fn populate_metadata(
         block_metadata: BlockMetadata {
             slot_number: 0.into(),
             block_number: 0.into(),
+            block_hash: None,
             cbt: DateTime::UNIX_EPOCH,
             absn: 0,
+            tx_hash: None,
         },
         osn: 0,
     };
@@ -115,3 +120,13 @@ pub fn populate_metadata(
         })
         .collect()
 }
+
+/// Poll a future that is expected to complete immediately, such as an in-memory resolver.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    match future.as_mut().poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is expected to be ready"),
+    }
+}
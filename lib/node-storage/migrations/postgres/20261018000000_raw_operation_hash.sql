@@ -0,0 +1,5 @@
+-- operation hash of existing rows is populated by the application after migration
+ALTER TABLE raw_operation
+ADD COLUMN operation_hash BYTEA;
+
+CREATE INDEX raw_operation_operation_hash_idx ON raw_operation (operation_hash);
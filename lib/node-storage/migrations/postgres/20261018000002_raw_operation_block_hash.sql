@@ -0,0 +1,31 @@
+-- block hash is only recorded for operations synced after this migration
+ALTER TABLE raw_operation
+ADD COLUMN block_hash BYTEA;
+
+-- expose the hashes of raw_operation to DID resolution
+CREATE OR REPLACE VIEW raw_operation_by_did AS
+WITH unioned AS (
+    SELECT
+        did,
+        raw_operation_id
+    FROM indexed_ssi_operation
+    UNION
+    SELECT
+        did,
+        raw_operation_id
+    FROM indexed_vdr_operation
+)
+SELECT
+    ro.id,
+    ro.signed_operation_data,
+    ro.slot,
+    ro.block_number,
+    ro.cbt,
+    ro.absn,
+    ro.osn,
+    ro.is_indexed,
+    u.did,
+    ro.operation_hash,
+    ro.tx_hash,
+    ro.block_hash
+FROM unioned AS u LEFT JOIN raw_operation AS ro ON u.raw_operation_id = ro.id;
@@ -0,0 +1,2 @@
+-- DID lookups and the DID prefix search scan the DIDs in order
+CREATE INDEX indexed_ssi_operation_did_idx ON indexed_ssi_operation (did);
@@ -0,0 +1,78 @@
+CREATE TABLE IF NOT EXISTS dlt_cursor (
+    id BLOB NOT NULL DEFAULT (randomblob(16)),
+    slot INTEGER NOT NULL,
+    block_hash BLOB NOT NULL,
+    CONSTRAINT dlt_cursor_pkey PRIMARY KEY (id),
+    CONSTRAINT dlt_cursor_abs_order UNIQUE (slot, block_hash)
+);
+
+CREATE TABLE IF NOT EXISTS raw_operation (
+    id BLOB NOT NULL DEFAULT (randomblob(16)),
+    signed_operation_data BLOB NOT NULL,
+    slot INTEGER NOT NULL,
+    block_number INTEGER NOT NULL,
+    cbt DATETIME NOT NULL,
+    absn INTEGER NOT NULL,
+    osn INTEGER NOT NULL,
+    is_indexed BOOLEAN NOT NULL,
+    CONSTRAINT raw_operation_pkey PRIMARY KEY (id),
+    CONSTRAINT raw_operation_abs_order UNIQUE (block_number, absn, osn)
+);
+
+CREATE TABLE IF NOT EXISTS indexed_ssi_operation (
+    id BLOB NOT NULL DEFAULT (randomblob(16)),
+    raw_operation_id BLOB NOT NULL UNIQUE,
+    did BLOB NOT NULL,
+    indexed_at DATETIME NOT NULL,
+    CONSTRAINT indexed_ssi_operation_pkey PRIMARY KEY (id),
+    FOREIGN KEY (raw_operation_id) REFERENCES raw_operation (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS indexed_vdr_operation (
+    id BLOB NOT NULL DEFAULT (randomblob(16)),
+    raw_operation_id BLOB NOT NULL UNIQUE,
+    operation_hash BLOB NOT NULL,
+    init_operation_hash BLOB NOT NULL,
+    prev_operation_hash BLOB,
+    did BLOB NOT NULL,
+    indexed_at DATETIME NOT NULL,
+    CONSTRAINT indexed_vdr_operation_pkey PRIMARY KEY (id),
+    FOREIGN KEY (raw_operation_id) REFERENCES raw_operation (id) ON DELETE CASCADE
+);
+
+CREATE VIEW raw_operation_by_did AS
+WITH unioned AS (
+    SELECT
+        did,
+        raw_operation_id
+    FROM indexed_ssi_operation
+    UNION
+    SELECT
+        did,
+        raw_operation_id
+    FROM indexed_vdr_operation
+)
+SELECT
+    ro.id,
+    ro.signed_operation_data,
+    ro.slot,
+    ro.block_number,
+    ro.cbt,
+    ro.absn,
+    ro.osn,
+    ro.is_indexed,
+    u.did
+FROM unioned AS u LEFT JOIN raw_operation AS ro ON u.raw_operation_id = ro.id;
+
+CREATE VIEW did_stats AS
+SELECT
+    did,
+    count(*) AS operation_count,
+    max(block_number) AS last_block,
+    max(slot) AS last_slot,
+    max(cbt) AS last_cbt,
+    min(block_number) AS first_block,
+    min(slot) AS first_slot,
+    min(cbt) AS first_cbt
+FROM raw_operation_by_did
+GROUP BY 1;
@@ -0,0 +1,5 @@
+-- tx hash is only recorded for operations synced after this migration
+ALTER TABLE raw_operation
+ADD COLUMN tx_hash BLOB;
+
+CREATE INDEX raw_operation_tx_hash_idx ON raw_operation (tx_hash);
@@ -0,0 +1,29 @@
+-- DIDs indexed before this migration have their references populated by the indexer
+ALTER TABLE indexed_ssi_operation
+ADD COLUMN references_indexed BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX indexed_ssi_operation_references_indexed_idx ON indexed_ssi_operation (references_indexed);
+
+CREATE TABLE IF NOT EXISTS indexed_public_key (
+    id BLOB NOT NULL DEFAULT (randomblob(16)),
+    did BLOB NOT NULL,
+    key_id TEXT NOT NULL,
+    key_data BLOB NOT NULL,
+    CONSTRAINT indexed_public_key_pkey PRIMARY KEY (id)
+);
+
+CREATE INDEX indexed_public_key_did_idx ON indexed_public_key (did);
+
+CREATE INDEX indexed_public_key_key_data_idx ON indexed_public_key (key_data);
+
+CREATE TABLE IF NOT EXISTS indexed_service_endpoint (
+    id BLOB NOT NULL DEFAULT (randomblob(16)),
+    did BLOB NOT NULL,
+    service_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    CONSTRAINT indexed_service_endpoint_pkey PRIMARY KEY (id)
+);
+
+CREATE INDEX indexed_service_endpoint_did_idx ON indexed_service_endpoint (did);
+
+CREATE INDEX indexed_service_endpoint_url_idx ON indexed_service_endpoint (url);
@@ -0,0 +1,220 @@
+use identus_apollo::hash::Sha256Digest;
+use identus_did_prism::dlt::{BlockNo, DltCursor, OperationMetadata, SlotNo, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::utils::paging::Paginated;
+use identus_did_prism_indexer::repo::{DidReference, DltCursorRepo, IndexedOperation, OperationRepo, RawOperationId};
+
+use crate::{Error, PostgresDb, SqliteDb};
+
+/// A storage backend selected at runtime from the database URL scheme.
+#[derive(Debug, Clone)]
+pub enum NodeDb {
+    Postgres(PostgresDb),
+    Sqlite(SqliteDb),
+}
+
+impl NodeDb {
+    /// Connect to the database using the backend matching the URL scheme
+    /// (`postgres://`, `postgresql://` or `sqlite:`).
+    pub async fn connect(db_url: &str) -> Result<Self, Error> {
+        if db_url.starts_with("postgres://") || db_url.starts_with("postgresql://") {
+            Ok(Self::Postgres(PostgresDb::connect(db_url).await?))
+        } else if db_url.starts_with("sqlite:") {
+            Ok(Self::Sqlite(SqliteDb::connect(db_url).await?))
+        } else {
+            let scheme = db_url.split_once(':').map(|(scheme, _)| scheme).unwrap_or_default();
+            Err(Error::UnsupportedDbScheme {
+                scheme: scheme.to_string(),
+            })
+        }
+    }
+
+    /// Connect to the database for read queries only.
+    ///
+    /// A SQLite database is opened read-only and must already exist, since an empty one created on demand
+    /// would never be populated. A PostgreSQL session defaults to read-only transactions.
+    pub async fn connect_read_only(db_url: &str) -> Result<Self, Error> {
+        if db_url.starts_with("postgres://") || db_url.starts_with("postgresql://") {
+            Ok(Self::Postgres(PostgresDb::connect_read_only(db_url).await?))
+        } else if db_url.starts_with("sqlite:") {
+            Ok(Self::Sqlite(SqliteDb::connect_read_only(db_url).await?))
+        } else {
+            Self::connect(db_url).await
+        }
+    }
+
+    pub async fn migrate(&self) -> Result<(), Error> {
+        match self {
+            Self::Postgres(db) => db.migrate().await,
+            Self::Sqlite(db) => db.migrate().await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OperationRepo for NodeDb {
+    type Error = Error;
+
+    async fn get_last_indexed_block(&self) -> Result<Option<(SlotNo, BlockNo)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_last_indexed_block().await,
+            Self::Sqlite(db) => db.get_last_indexed_block().await,
+        }
+    }
+
+    async fn get_all_dids(&self, page: u32, page_size: u32) -> Result<Paginated<CanonicalPrismDid>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_all_dids(page, page_size).await,
+            Self::Sqlite(db) => db.get_all_dids(page, page_size).await,
+        }
+    }
+
+    async fn get_dids_by_suffix_prefix(
+        &self,
+        suffix_prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<CanonicalPrismDid>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_dids_by_suffix_prefix(suffix_prefix, limit).await,
+            Self::Sqlite(db) => db.get_dids_by_suffix_prefix(suffix_prefix, limit).await,
+        }
+    }
+
+    async fn get_raw_operations(
+        &self,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_raw_operations(page, page_size).await,
+            Self::Sqlite(db) => db.get_raw_operations(page, page_size).await,
+        }
+    }
+
+    async fn get_raw_operations_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_raw_operations_by_operation_hash(operation_hash).await,
+            Self::Sqlite(db) => db.get_raw_operations_by_operation_hash(operation_hash).await,
+        }
+    }
+
+    async fn get_raw_operations_by_tx_hash(
+        &self,
+        tx_hash: &TxId,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_raw_operations_by_tx_hash(tx_hash).await,
+            Self::Sqlite(db) => db.get_raw_operations_by_tx_hash(tx_hash).await,
+        }
+    }
+
+    async fn get_raw_operations_unindexed(
+        &self,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_raw_operations_unindexed().await,
+            Self::Sqlite(db) => db.get_raw_operations_unindexed().await,
+        }
+    }
+
+    async fn get_raw_operations_by_did(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_raw_operations_by_did(did).await,
+            Self::Sqlite(db) => db.get_raw_operations_by_did(did).await,
+        }
+    }
+
+    async fn get_raw_operation_vdr_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Option<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_raw_operation_vdr_by_operation_hash(operation_hash).await,
+            Self::Sqlite(db) => db.get_raw_operation_vdr_by_operation_hash(operation_hash).await,
+        }
+    }
+
+    async fn insert_raw_operations(
+        &self,
+        operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    ) -> Result<(), Self::Error> {
+        match self {
+            Self::Postgres(db) => db.insert_raw_operations(operations).await,
+            Self::Sqlite(db) => db.insert_raw_operations(operations).await,
+        }
+    }
+
+    async fn insert_indexed_operations(&self, operations: Vec<IndexedOperation>) -> Result<(), Self::Error> {
+        match self {
+            Self::Postgres(db) => db.insert_indexed_operations(operations).await,
+            Self::Sqlite(db) => db.insert_indexed_operations(operations).await,
+        }
+    }
+
+    async fn get_dids_with_stale_references(&self, limit: u32) -> Result<Vec<CanonicalPrismDid>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_dids_with_stale_references(limit).await,
+            Self::Sqlite(db) => db.get_dids_with_stale_references(limit).await,
+        }
+    }
+
+    async fn set_did_references(
+        &self,
+        did: &CanonicalPrismDid,
+        references: Vec<DidReference>,
+    ) -> Result<(), Self::Error> {
+        match self {
+            Self::Postgres(db) => db.set_did_references(did, references).await,
+            Self::Sqlite(db) => db.set_did_references(did, references).await,
+        }
+    }
+
+    async fn get_dids_by_public_key(
+        &self,
+        data: &[u8],
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_dids_by_public_key(data, page, page_size).await,
+            Self::Sqlite(db) => db.get_dids_by_public_key(data, page, page_size).await,
+        }
+    }
+
+    async fn get_dids_by_service_endpoint(
+        &self,
+        url: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_dids_by_service_endpoint(url, page, page_size).await,
+            Self::Sqlite(db) => db.get_dids_by_service_endpoint(url, page, page_size).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DltCursorRepo for NodeDb {
+    type Error = Error;
+
+    async fn get_cursor(&self) -> Result<Option<DltCursor>, Self::Error> {
+        match self {
+            Self::Postgres(db) => db.get_cursor().await,
+            Self::Sqlite(db) => db.get_cursor().await,
+        }
+    }
+
+    async fn set_cursor(&self, cursor: DltCursor) -> Result<(), Self::Error> {
+        match self {
+            Self::Postgres(db) => db.set_cursor(cursor).await,
+            Self::Sqlite(db) => db.set_cursor(cursor).await,
+        }
+    }
+}
@@ -6,6 +6,7 @@ use crate::entity::DidSuffix;
 
 #[derive(Entity)]
 #[lazybe(table = "dlt_cursor")]
+#[allow(unused)]
 pub struct DltCursor {
     #[lazybe(primary_key)]
     pub id: Uuid,
@@ -26,6 +27,9 @@ pub struct RawOperation {
     pub absn: i32,
     pub osn: i32,
     pub is_indexed: bool,
+    pub operation_hash: Option<Vec<u8>>,
+    pub tx_hash: Option<Vec<u8>>,
+    pub block_hash: Option<Vec<u8>>,
 }
 
 #[derive(Entity)]
@@ -38,6 +42,7 @@ pub struct IndexedSsiOperation {
     pub did: DidSuffix,
     #[lazybe(created_at)]
     pub indexed_at: DateTime<Utc>,
+    pub references_indexed: bool,
 }
 
 #[derive(Entity)]
@@ -55,6 +60,28 @@ pub struct IndexedVdrOperation {
     pub indexed_at: DateTime<Utc>,
 }
 
+#[derive(Entity)]
+#[lazybe(table = "indexed_public_key")]
+#[allow(unused)]
+pub struct IndexedPublicKey {
+    #[lazybe(primary_key)]
+    pub id: Uuid,
+    pub did: DidSuffix,
+    pub key_id: String,
+    pub key_data: Vec<u8>,
+}
+
+#[derive(Entity)]
+#[lazybe(table = "indexed_service_endpoint")]
+#[allow(unused)]
+pub struct IndexedServiceEndpoint {
+    #[lazybe(primary_key)]
+    pub id: Uuid,
+    pub did: DidSuffix,
+    pub service_id: String,
+    pub url: String,
+}
+
 #[derive(Entity)]
 #[lazybe(table = "did_stats")]
 #[allow(unused)]
@@ -84,6 +111,9 @@ pub struct RawOperationByDid {
     pub osn: i32,
     pub is_indexed: bool,
     pub did: DidSuffix,
+    pub operation_hash: Option<Vec<u8>>,
+    pub tx_hash: Option<Vec<u8>>,
+    pub block_hash: Option<Vec<u8>>,
 }
 
 impl From<RawOperationByDid> for RawOperation {
@@ -97,6 +127,9 @@ impl From<RawOperationByDid> for RawOperation {
             absn: value.absn,
             osn: value.osn,
             is_indexed: value.is_indexed,
+            operation_hash: value.operation_hash,
+            tx_hash: value.tx_hash,
+            block_hash: value.block_hash,
         }
     }
 }
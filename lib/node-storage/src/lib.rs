@@ -1,10 +1,16 @@
 use identus_did_prism::did::Error as DidError;
 use identus_did_prism::did::error::DidSyntaxError;
 
+mod db;
 mod entity;
 mod pg;
+mod repo;
+mod sqlite;
 
+pub use db::NodeDb;
 pub use pg::PostgresDb;
+pub use repo::SqlDb;
+pub use sqlite::SqliteDb;
 
 #[derive(Debug, derive_more::From, derive_more::Display, derive_more::Error)]
 pub enum Error {
@@ -14,11 +20,18 @@ pub enum Error {
     #[from]
     #[display("database migration error")]
     DbMigration { source: sqlx::migrate::MigrateError },
+    #[display("unsupported database url scheme '{scheme}'")]
+    UnsupportedDbScheme { scheme: String },
     #[display("unable to decode to protobuf message into type {target_type} from stored data")]
     ProtobufDecode {
         source: protobuf::Error,
         target_type: &'static str,
     },
+    #[display("invalid {column} digest in stored data")]
+    InvalidStoredDigest {
+        source: identus_apollo::hash::Error,
+        column: &'static str,
+    },
     #[from]
     #[display("cannot compute did index from SignedPrismOperation")]
     DidIndexFromSignedPrismOperation { source: DidError },
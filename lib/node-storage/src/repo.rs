@@ -0,0 +1,719 @@
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use identus_apollo::hash::Sha256Digest;
+use identus_apollo::hex::HexStr;
+use identus_did_prism::dlt::{BlockMetadata, BlockNo, DltCursor, OperationMetadata, SlotNo, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::utils::paging::Paginated;
+use identus_did_prism_indexer::repo::{DidReference, DltCursorRepo, IndexedOperation, OperationRepo, RawOperationId};
+use lazybe::TableEntity;
+use lazybe::db::{DbCtx, DbOps};
+use lazybe::entity::ops::{CreateEntity, ListEntity};
+use lazybe::filter::Filter;
+use lazybe::page::PaginationInput;
+use lazybe::sort::Sort;
+use lazybe::uuid::Uuid;
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::{ColumnIndex, Database, Decode, Encode, Executor, FromRow, IntoArguments, Pool, Type};
+
+use crate::{Error, entity};
+
+/// The version of the migration adding the `operation_hash` column to `raw_operation`.
+const OPERATION_HASH_MIGRATION_VERSION: i64 = 20261018000000;
+
+/// A storage backend made of a sqlx connection pool and the matching lazybe context.
+///
+/// `OperationRepo` and `DltCursorRepo` are implemented once for all backends since lazybe abstracts
+/// over the SQL dialect. Queries involving a UUID value are bound with sqlx instead, because lazybe renders
+/// the value as a string literal which does not match the BLOB UUID columns of SQLite.
+pub struct SqlDb<Db: Database, Ctx> {
+    pub pool: Pool<Db>,
+    pub(crate) db_ctx: Ctx,
+}
+
+impl<Db: Database, Ctx: Clone> Clone for SqlDb<Db, Ctx> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            db_ctx: self.db_ctx.clone(),
+        }
+    }
+}
+
+impl<Db: Database, Ctx: Debug> Debug for SqlDb<Db, Ctx> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlDb")
+            .field("pool", &self.pool)
+            .field("db_ctx", &self.db_ctx)
+            .finish()
+    }
+}
+
+impl<Db, Ctx> SqlDb<Db, Ctx>
+where
+    Db: Database,
+    Db::Connection: Migrate,
+    for<'q> Db::Arguments<'q>: IntoArguments<'q, Db>,
+    for<'c> &'c mut Db::Connection: Executor<'c, Database = Db>,
+    for<'q> Uuid: Encode<'q, Db> + Decode<'q, Db> + Type<Db>,
+    for<'q> Vec<u8>: Encode<'q, Db> + Decode<'q, Db> + Type<Db>,
+    for<'q> i64: Encode<'q, Db> + Type<Db>,
+    usize: ColumnIndex<Db::Row>,
+{
+    /// Run the migrations and, only when the migration adding the operation hash column
+    /// is applied by this run, populate the operation hash of the existing raw operations.
+    pub(crate) async fn run_migrations(&self, migrator: Migrator) -> Result<(), Error> {
+        let backfill_pending = {
+            let mut conn = self.pool.acquire().await?;
+            conn.ensure_migrations_table().await?;
+            !conn
+                .list_applied_migrations()
+                .await?
+                .iter()
+                .any(|m| m.version == OPERATION_HASH_MIGRATION_VERSION)
+        };
+        migrator.run(&self.pool).await?;
+        if backfill_pending {
+            self.backfill_operation_hash().await?;
+        }
+        Ok(())
+    }
+
+    /// Populate the operation hash of raw operations inserted before the column existed.
+    async fn backfill_operation_hash(&self) -> Result<(), Error> {
+        const BATCH_SIZE: i64 = 1000;
+        let mut last_id = Uuid::nil();
+        loop {
+            let mut tx = self.pool.begin().await?;
+            let rows = sqlx::query_as::<_, (Uuid, Vec<u8>)>(
+                "SELECT id, signed_operation_data FROM raw_operation \
+                 WHERE operation_hash IS NULL AND id > $1 ORDER BY id LIMIT $2",
+            )
+            .bind(last_id)
+            .bind(BATCH_SIZE)
+            .fetch_all(&mut *tx)
+            .await?;
+            let Some((id, _)) = rows.last() else {
+                break;
+            };
+            last_id = *id;
+            for (id, signed_operation_data) in rows {
+                let signed_operation =
+                    SignedPrismOperation::decode(&signed_operation_data).map_err(|e| Error::ProtobufDecode {
+                        source: e,
+                        target_type: std::any::type_name::<SignedPrismOperation>(),
+                    })?;
+                let Some(operation_hash) = signed_operation.operation_hash() else {
+                    continue;
+                };
+                sqlx::query("UPDATE raw_operation SET operation_hash = $1 WHERE id = $2")
+                    .bind(operation_hash.to_vec())
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<Db, Ctx> OperationRepo for SqlDb<Db, Ctx>
+where
+    Db: Database,
+    Ctx: DbCtx<Db> + Send + Sync,
+    for<'q> Db::Arguments<'q>: IntoArguments<'q, Db>,
+    for<'c> &'c mut Db::Connection: Executor<'c, Database = Db>,
+    for<'q> Uuid: Encode<'q, Db> + Type<Db>,
+    for<'q> Vec<u8>: Encode<'q, Db> + Decode<'q, Db> + Type<Db>,
+    for<'q> Option<Vec<u8>>: Encode<'q, Db>,
+    for<'q> i64: Encode<'q, Db> + Type<Db>,
+    for<'q> bool: Encode<'q, Db> + Type<Db>,
+    for<'q> DateTime<Utc>: Encode<'q, Db> + Type<Db>,
+    usize: ColumnIndex<Db::Row>,
+    entity::RawOperation: ListEntity<Db> + CreateEntity<Db>,
+    <entity::RawOperation as TableEntity>::Row: for<'r> FromRow<'r, Db::Row> + Send + Unpin,
+    entity::RawOperationByDid: ListEntity<Db>,
+    entity::DidStats: ListEntity<Db>,
+    entity::IndexedVdrOperation: ListEntity<Db>,
+    entity::IndexedPublicKey: ListEntity<Db> + CreateEntity<Db>,
+    entity::IndexedServiceEndpoint: ListEntity<Db> + CreateEntity<Db>,
+{
+    type Error = Error;
+
+    async fn get_last_indexed_block(&self) -> Result<Option<(SlotNo, BlockNo)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let ops_page = self
+            .db_ctx
+            .list::<entity::RawOperation>(
+                &mut tx,
+                Filter::all([entity::RawOperationFilter::is_indexed().eq(true)]),
+                Sort::new([entity::RawOperationSort::slot().desc()]),
+                Some(PaginationInput { page: 0, limit: 1 }),
+            )
+            .await?;
+        tx.commit().await?;
+
+        let last_op = ops_page.data.into_iter().next();
+        Ok(last_op.map(|op| {
+            let slot: SlotNo = u64::try_from(op.slot).expect("slot_number does not fit in u64").into();
+            let block: BlockNo = u64::try_from(op.block_number)
+                .expect("block_number does not fit in u64")
+                .into();
+            (slot, block)
+        }))
+    }
+
+    async fn get_all_dids(&self, page: u32, page_size: u32) -> Result<Paginated<CanonicalPrismDid>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let did_page = self
+            .db_ctx
+            .list::<entity::DidStats>(
+                &mut tx,
+                Filter::empty(),
+                Sort::new([
+                    entity::DidStatsSort::first_slot().desc(),
+                    entity::DidStatsSort::did().asc(),
+                ]),
+                Some(PaginationInput { page, limit: page_size }),
+            )
+            .await?;
+        tx.commit().await?;
+
+        let items = did_page
+            .data
+            .into_iter()
+            .map(|stats| stats.did.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Paginated {
+            items,
+            current_page: did_page.page,
+            page_size: did_page.page_size,
+            total_items: did_page.total_records,
+        })
+    }
+
+    async fn get_dids_by_suffix_prefix(
+        &self,
+        suffix_prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<CanonicalPrismDid>, Self::Error> {
+        // the prefix is padded to the lowest suffix it can match
+        let mut lower_bound = suffix_prefix.to_string();
+        if lower_bound.len() % 2 == 1 {
+            lower_bound.push('0');
+        }
+        let Ok(lower_bound) = HexStr::from_str(&lower_bound) else {
+            return Ok(vec![]);
+        };
+
+        let mut tx = self.pool.begin().await?;
+        let suffixes = sqlx::query_scalar::<_, Vec<u8>>(
+            "SELECT DISTINCT did FROM indexed_ssi_operation WHERE did >= $1 ORDER BY did LIMIT $2",
+        )
+        .bind(lower_bound.to_bytes())
+        .bind(i64::from(limit))
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        // suffixes are ordered, so the ones matching the prefix come first
+        suffixes
+            .into_iter()
+            .take_while(|suffix| HexStr::from(suffix).to_string().starts_with(suffix_prefix))
+            .map(|suffix| entity::DidSuffix::from(suffix).try_into())
+            .collect()
+    }
+
+    async fn get_raw_operations(
+        &self,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let ops_page = self
+            .db_ctx
+            .list::<entity::RawOperation>(
+                &mut tx,
+                Filter::empty(),
+                Sort::new([
+                    entity::RawOperationSort::block_number().desc(),
+                    entity::RawOperationSort::absn().desc(),
+                    entity::RawOperationSort::osn().desc(),
+                ]),
+                Some(PaginationInput { page, limit: page_size }),
+            )
+            .await?;
+        tx.commit().await?;
+
+        let items = ops_page
+            .data
+            .into_iter()
+            .map(parse_raw_operation)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Paginated {
+            items,
+            current_page: ops_page.page,
+            page_size: ops_page.page_size,
+            total_items: ops_page.total_records,
+        })
+    }
+
+    async fn get_raw_operations_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = self
+            .db_ctx
+            .list::<entity::RawOperation>(
+                &mut tx,
+                Filter::all([entity::RawOperationFilter::operation_hash().eq(Some(operation_hash.to_vec()))]),
+                Sort::new([
+                    entity::RawOperationSort::block_number().asc(),
+                    entity::RawOperationSort::absn().asc(),
+                    entity::RawOperationSort::osn().asc(),
+                ]),
+                None,
+            )
+            .await?
+            .data
+            .into_iter()
+            .map(parse_raw_operation)
+            .collect::<Result<Vec<_>, _>>()?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    async fn get_raw_operations_by_tx_hash(
+        &self,
+        tx_hash: &TxId,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = self
+            .db_ctx
+            .list::<entity::RawOperation>(
+                &mut tx,
+                Filter::all([entity::RawOperationFilter::tx_hash().eq(Some(tx_hash.as_bytes().to_vec()))]),
+                Sort::new([
+                    entity::RawOperationSort::block_number().asc(),
+                    entity::RawOperationSort::absn().asc(),
+                    entity::RawOperationSort::osn().asc(),
+                ]),
+                None,
+            )
+            .await?
+            .data
+            .into_iter()
+            .map(parse_raw_operation)
+            .collect::<Result<Vec<_>, _>>()?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    async fn get_raw_operations_unindexed(
+        &self,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = self
+            .db_ctx
+            .list::<entity::RawOperation>(
+                &mut tx,
+                Filter::all([entity::RawOperationFilter::is_indexed().eq(false)]),
+                Sort::new([
+                    entity::RawOperationSort::block_number().asc(),
+                    entity::RawOperationSort::absn().asc(),
+                    entity::RawOperationSort::osn().asc(),
+                ]),
+                Some(PaginationInput { page: 0, limit: 200 }),
+            )
+            .await?
+            .data
+            .into_iter()
+            .map(parse_raw_operation)
+            .collect::<Result<Vec<_>, _>>()?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    async fn get_raw_operations_by_did(
+        &self,
+        did: &CanonicalPrismDid,
+    ) -> Result<Vec<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let suffix_bytes = did.suffix().to_vec();
+        let mut tx = self.pool.begin().await?;
+        let result = self
+            .db_ctx
+            .list::<entity::RawOperationByDid>(
+                &mut tx,
+                Filter::all([entity::RawOperationByDidFilter::did().eq(suffix_bytes.into())]),
+                Sort::empty(),
+                None,
+            )
+            .await?
+            .data
+            .into_iter()
+            .map(|i| parse_raw_operation(i.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    async fn get_raw_operation_vdr_by_operation_hash(
+        &self,
+        operation_hash: &Sha256Digest,
+    ) -> Result<Option<(RawOperationId, OperationMetadata, SignedPrismOperation)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let vdr_operation = self
+            .db_ctx
+            .list::<entity::IndexedVdrOperation>(
+                &mut tx,
+                Filter::all([entity::IndexedVdrOperationFilter::operation_hash().eq(operation_hash.to_vec())]),
+                Sort::empty(),
+                Some(PaginationInput { page: 0, limit: 1 }),
+            )
+            .await?
+            .data
+            .into_iter()
+            .next();
+
+        let result = match vdr_operation {
+            None => None,
+            Some(op) => sqlx::query_as::<_, <entity::RawOperation as TableEntity>::Row>(
+                "SELECT * FROM raw_operation WHERE id = $1",
+            )
+            .bind(op.raw_operation_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .map(|row| parse_raw_operation(row.into()))
+            .transpose()?,
+        };
+
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    async fn insert_raw_operations(
+        &self,
+        operations: Vec<(OperationMetadata, SignedPrismOperation)>,
+    ) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        for (metadata, signed_operation) in operations {
+            let create_op = entity::CreateRawOperation {
+                signed_operation_data: signed_operation.encode_to_vec(),
+                slot: metadata
+                    .block_metadata
+                    .slot_number
+                    .inner()
+                    .try_into()
+                    .expect("slot_number does not fit in i64"),
+                block_number: metadata
+                    .block_metadata
+                    .block_number
+                    .inner()
+                    .try_into()
+                    .expect("block_number does not fit in i64"),
+                cbt: metadata.block_metadata.cbt,
+                absn: metadata
+                    .block_metadata
+                    .absn
+                    .try_into()
+                    .expect("absn does not fit in i32"),
+                osn: metadata.osn.try_into().expect("osn does not fit in i32"),
+                is_indexed: false,
+                operation_hash: signed_operation.operation_hash().map(|h| h.to_vec()),
+                tx_hash: metadata.block_metadata.tx_hash.as_ref().map(|h| h.as_bytes().to_vec()),
+                block_hash: metadata
+                    .block_metadata
+                    .block_hash
+                    .as_ref()
+                    .map(|h| h.as_bytes().to_vec()),
+            };
+            self.db_ctx.create::<entity::RawOperation>(&mut tx, create_op).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn insert_indexed_operations(&self, operations: Vec<IndexedOperation>) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        for op in operations {
+            // mark raw_operation as indexed
+            sqlx::query("UPDATE raw_operation SET is_indexed = $1 WHERE id = $2")
+                .bind(true)
+                .bind(*op.raw_operation_id().as_ref())
+                .execute(&mut *tx)
+                .await?;
+
+            // write to indexing table
+            match op {
+                IndexedOperation::Ssi { raw_operation_id, did } => {
+                    sqlx::query(
+                        "INSERT INTO indexed_ssi_operation \
+                         (raw_operation_id, did, indexed_at, references_indexed) \
+                         VALUES ($1, $2, $3, $4)",
+                    )
+                    .bind(Uuid::from(raw_operation_id))
+                    .bind(did.suffix().to_vec())
+                    .bind(Utc::now())
+                    .bind(false)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                IndexedOperation::Vdr {
+                    raw_operation_id,
+                    operation_hash,
+                    init_operation_hash,
+                    prev_operation_hash,
+                    did,
+                } => {
+                    sqlx::query(
+                        "INSERT INTO indexed_vdr_operation \
+                         (raw_operation_id, operation_hash, init_operation_hash, prev_operation_hash, did, indexed_at) \
+                         VALUES ($1, $2, $3, $4, $5, $6)",
+                    )
+                    .bind(Uuid::from(raw_operation_id))
+                    .bind(operation_hash)
+                    .bind(init_operation_hash)
+                    .bind(prev_operation_hash)
+                    .bind(did.suffix().to_vec())
+                    .bind(Utc::now())
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                IndexedOperation::Ignored { .. } => (),
+            };
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_dids_with_stale_references(&self, limit: u32) -> Result<Vec<CanonicalPrismDid>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let suffixes = sqlx::query_scalar::<_, Vec<u8>>(
+            "SELECT DISTINCT did FROM indexed_ssi_operation \
+             WHERE NOT references_indexed ORDER BY did LIMIT $1",
+        )
+        .bind(i64::from(limit))
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        suffixes
+            .into_iter()
+            .map(|suffix| entity::DidSuffix::from(suffix).try_into())
+            .collect()
+    }
+
+    async fn set_did_references(
+        &self,
+        did: &CanonicalPrismDid,
+        references: Vec<DidReference>,
+    ) -> Result<(), Self::Error> {
+        let suffix_bytes = did.suffix().to_vec();
+        let mut tx = self.pool.begin().await?;
+        for table in ["indexed_public_key", "indexed_service_endpoint"] {
+            sqlx::query(&format!("DELETE FROM {table} WHERE did = $1"))
+                .bind(&suffix_bytes)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for reference in references {
+            match reference {
+                DidReference::PublicKey { key_id, data } => {
+                    self.db_ctx
+                        .create::<entity::IndexedPublicKey>(
+                            &mut tx,
+                            entity::CreateIndexedPublicKey {
+                                did: did.clone().into(),
+                                key_id,
+                                key_data: data,
+                            },
+                        )
+                        .await?;
+                }
+                DidReference::ServiceEndpoint { service_id, url } => {
+                    self.db_ctx
+                        .create::<entity::IndexedServiceEndpoint>(
+                            &mut tx,
+                            entity::CreateIndexedServiceEndpoint {
+                                did: did.clone().into(),
+                                service_id,
+                                url,
+                            },
+                        )
+                        .await?;
+                }
+            }
+        }
+        sqlx::query("UPDATE indexed_ssi_operation SET references_indexed = $1 WHERE did = $2")
+            .bind(true)
+            .bind(&suffix_bytes)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_dids_by_public_key(
+        &self,
+        data: &[u8],
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let reference_page = self
+            .db_ctx
+            .list::<entity::IndexedPublicKey>(
+                &mut tx,
+                Filter::all([entity::IndexedPublicKeyFilter::key_data().eq(data.to_vec())]),
+                Sort::new([
+                    entity::IndexedPublicKeySort::did().asc(),
+                    entity::IndexedPublicKeySort::key_id().asc(),
+                ]),
+                Some(PaginationInput { page, limit: page_size }),
+            )
+            .await?;
+        tx.commit().await?;
+
+        let items = reference_page
+            .data
+            .into_iter()
+            .map(|i| Ok((i.did.try_into()?, i.key_id)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Paginated {
+            items,
+            current_page: reference_page.page,
+            page_size: reference_page.page_size,
+            total_items: reference_page.total_records,
+        })
+    }
+
+    async fn get_dids_by_service_endpoint(
+        &self,
+        url: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Paginated<(CanonicalPrismDid, String)>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let reference_page = self
+            .db_ctx
+            .list::<entity::IndexedServiceEndpoint>(
+                &mut tx,
+                Filter::all([entity::IndexedServiceEndpointFilter::url().eq(url.to_string())]),
+                Sort::new([
+                    entity::IndexedServiceEndpointSort::did().asc(),
+                    entity::IndexedServiceEndpointSort::service_id().asc(),
+                ]),
+                Some(PaginationInput { page, limit: page_size }),
+            )
+            .await?;
+        tx.commit().await?;
+
+        let items = reference_page
+            .data
+            .into_iter()
+            .map(|i| Ok((i.did.try_into()?, i.service_id)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Paginated {
+            items,
+            current_page: reference_page.page,
+            page_size: reference_page.page_size,
+            total_items: reference_page.total_records,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<Db, Ctx> DltCursorRepo for SqlDb<Db, Ctx>
+where
+    Db: Database,
+    Ctx: DbCtx<Db> + Send + Sync,
+    for<'q> Db::Arguments<'q>: IntoArguments<'q, Db>,
+    for<'c> &'c mut Db::Connection: Executor<'c, Database = Db>,
+    entity::DltCursor: ListEntity<Db> + CreateEntity<Db>,
+{
+    type Error = Error;
+
+    async fn get_cursor(&self) -> Result<Option<DltCursor>, Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = self
+            .db_ctx
+            .list::<entity::DltCursor>(&mut tx, Filter::empty(), Sort::empty(), None)
+            .await?
+            .data
+            .into_iter()
+            .next()
+            .map(|model| DltCursor {
+                slot: model.slot as u64,
+                block_hash: model.block_hash,
+                cbt: None,
+            });
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    async fn set_cursor(&self, cursor: DltCursor) -> Result<(), Self::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM dlt_cursor").execute(&mut *tx).await?;
+        self.db_ctx
+            .create::<entity::DltCursor>(
+                &mut tx,
+                entity::CreateDltCursor {
+                    slot: cursor.slot as i64,
+                    block_hash: cursor.block_hash,
+                },
+            )
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+fn parse_raw_operation(
+    value: entity::RawOperation,
+) -> Result<(RawOperationId, OperationMetadata, SignedPrismOperation), Error> {
+    let metadata = OperationMetadata {
+        block_metadata: BlockMetadata {
+            slot_number: u64::try_from(value.slot)
+                .expect("slot value does not fit in u64")
+                .into(),
+            block_number: u64::try_from(value.block_number)
+                .expect("block_number value does not fit in u64")
+                .into(),
+            block_hash: value
+                .block_hash
+                .map(|h| Sha256Digest::from_bytes(&h))
+                .transpose()
+                .map_err(|e| Error::InvalidStoredDigest {
+                    source: e,
+                    column: "block_hash",
+                })?
+                .map(Into::into),
+            cbt: value.cbt,
+            absn: value.absn.try_into().expect("absn value does not fit in u32"),
+            tx_hash: value
+                .tx_hash
+                .map(|h| Sha256Digest::from_bytes(&h))
+                .transpose()
+                .map_err(|e| Error::InvalidStoredDigest {
+                    source: e,
+                    column: "tx_hash",
+                })?
+                .map(Into::into),
+        },
+        osn: value.osn.try_into().expect("osn value does not fit in u32"),
+    };
+    SignedPrismOperation::decode(value.signed_operation_data.as_slice())
+        .map(|op| (value.id.into(), metadata, op))
+        .map_err(|e| Error::ProtobufDecode {
+            source: e,
+            target_type: std::any::type_name::<SignedPrismOperation>(),
+        })
+}
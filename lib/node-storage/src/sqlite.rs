@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use lazybe::db::sqlite::SqliteDbCtx;
+use sqlx::Sqlite;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+
+use crate::Error;
+use crate::repo::SqlDb;
+
+pub type SqliteDb = SqlDb<Sqlite, SqliteDbCtx>;
+
+impl SqliteDb {
+    /// Connect to a SQLite database (e.g. `sqlite://neoprism.db` or `sqlite::memory:`).
+    /// The database file is created if it does not exist.
+    pub async fn connect(db_url: &str) -> Result<Self, Error> {
+        let options = SqliteConnectOptions::from_str(db_url)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .foreign_keys(true);
+        // keep at least one connection open so an in-memory database is not dropped while idle
+        let pool = SqlitePoolOptions::new()
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+        Ok(Self {
+            db_ctx: SqliteDbCtx,
+            pool,
+        })
+    }
+
+    /// Connect to an existing SQLite database without writing to it.
+    /// Unlike [`SqliteDb::connect`], this fails if the database file does not exist.
+    pub async fn connect_read_only(db_url: &str) -> Result<Self, Error> {
+        let options = SqliteConnectOptions::from_str(db_url)?
+            .create_if_missing(false)
+            .read_only(true);
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        Ok(Self {
+            db_ctx: SqliteDbCtx,
+            pool,
+        })
+    }
+
+    pub async fn migrate(&self) -> Result<(), Error> {
+        self.run_migrations(sqlx::migrate!("./migrations/sqlite")).await
+    }
+}
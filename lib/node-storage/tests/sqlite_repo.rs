@@ -0,0 +1,333 @@
+use chrono::DateTime;
+use identus_apollo::crypto::EncodeVec;
+use identus_apollo::crypto::ed25519::Ed25519PrivateKey;
+use identus_apollo::crypto::secp256k1::Secp256k1PrivateKey;
+use identus_apollo::hash::sha256;
+use identus_did_prism::did::operation::builder::{
+    CreateDidOperationBuilder, CreateStorageOperationBuilder, DeactivateDidOperationBuilder, UpdateDidOperationBuilder,
+    UpdateStorageOperationBuilder, sign_operation,
+};
+use identus_did_prism::did::operation::{KeyUsage, NonOperationPublicKey, StorageData};
+use identus_did_prism::did::{CanonicalPrismDid, PrismDidOps};
+use identus_did_prism::dlt::{BlockHash, BlockMetadata, DltCursor, OperationMetadata, TxId};
+use identus_did_prism::prelude::*;
+use identus_did_prism::protocol::resolver::resolve_published;
+use identus_did_prism_indexer::repo::{DidReference, DltCursorRepo, OperationRepo};
+use identus_did_prism_indexer::run_indexer_loop;
+use node_storage::SqliteDb;
+
+const MASTER_KEY: [u8; 32] = [1; 32];
+const VDR_KEY: [u8; 32] = [2; 32];
+
+async fn new_db() -> SqliteDb {
+    let db = SqliteDb::connect("sqlite::memory:").await.unwrap();
+    db.migrate().await.unwrap();
+    db
+}
+
+fn metadata(block_number: u64, osn: u32) -> OperationMetadata {
+    OperationMetadata {
+        block_metadata: BlockMetadata {
+            slot_number: (block_number * 10).into(),
+            block_number: block_number.into(),
+            block_hash: Some(BlockHash::from(sha256(format!("block-{block_number}")))),
+            cbt: DateTime::UNIX_EPOCH,
+            absn: 0,
+            tx_hash: Some(TxId::from(sha256(format!("tx-{block_number}")))),
+        },
+        osn,
+    }
+}
+
+fn create_did_operation() -> SignedPrismOperation {
+    let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+    let vdr_sk = Secp256k1PrivateKey::from_slice(&VDR_KEY).unwrap();
+    let operation = CreateDidOperationBuilder::new()
+        .add_key(
+            "master-0",
+            KeyUsage::MasterKey,
+            NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+        )
+        .add_key(
+            "vdr-0",
+            KeyUsage::VdrKey,
+            NonOperationPublicKey::Secp256k1(vdr_sk.to_public_key()),
+        )
+        .build();
+    sign_operation(operation, "master-0", &master_sk)
+}
+
+#[tokio::test]
+async fn migrate_is_idempotent() {
+    let db = new_db().await;
+    db.migrate().await.unwrap();
+    assert!(db.get_last_indexed_block().await.unwrap().is_none());
+    assert!(db.get_cursor().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn insert_and_index_operations() {
+    let db = new_db().await;
+    let vdr_sk = Secp256k1PrivateKey::from_slice(&VDR_KEY).unwrap();
+    let create_did_op = create_did_operation();
+    let did = CanonicalPrismDid::from_operation(create_did_op.operation.as_ref().unwrap()).unwrap();
+    let state = resolve_published(vec![(metadata(1, 0), create_did_op.clone())])
+        .0
+        .unwrap();
+    let create_storage_op = sign_operation(
+        CreateStorageOperationBuilder::new(&did, StorageData::Bytes(vec![1, 2, 3])).build(),
+        "vdr-0",
+        &vdr_sk,
+    );
+    let storage_state = resolve_published(vec![
+        (metadata(1, 0), create_did_op.clone()),
+        (metadata(1, 1), create_storage_op.clone()),
+    ])
+    .0
+    .unwrap()
+    .storage
+    .remove(0);
+    let update_storage_op = sign_operation(
+        UpdateStorageOperationBuilder::new(&storage_state, StorageData::Bytes(vec![4, 5, 6])).build(),
+        "vdr-0",
+        &vdr_sk,
+    );
+    assert_eq!(state.did, did);
+
+    db.insert_raw_operations(vec![
+        (metadata(1, 0), create_did_op.clone()),
+        (metadata(1, 1), create_storage_op),
+    ])
+    .await
+    .unwrap();
+    db.insert_raw_operations(vec![(metadata(2, 0), update_storage_op)])
+        .await
+        .unwrap();
+    assert_eq!(db.get_raw_operations_unindexed().await.unwrap().len(), 3);
+
+    let mut indexed_dids = vec![];
+    run_indexer_loop(&db, |did| indexed_dids.push(did.clone()))
+        .await
+        .unwrap();
+    assert_eq!(indexed_dids, vec![did.clone(), did.clone(), did.clone()]);
+    assert!(db.get_raw_operations_unindexed().await.unwrap().is_empty());
+
+    let mut operations = db.get_raw_operations_by_did(&did).await.unwrap();
+    operations.sort_by(|a, b| OperationMetadata::compare_time_asc(&a.1, &b.1));
+    assert_eq!(operations.len(), 3);
+    assert_eq!(operations[0].1, metadata(1, 0));
+    assert_eq!(operations[0].2, create_did_op);
+
+    let state = resolve_published(operations.into_iter().map(|(_, m, o)| (m, o)).collect())
+        .0
+        .unwrap();
+    assert_eq!(*state.storage[0].data, StorageData::Bytes(vec![4, 5, 6]));
+
+    let operation_hash = create_did_op.operation_hash().unwrap();
+    let by_hash = db.get_raw_operations_by_operation_hash(&operation_hash).await.unwrap();
+    assert_eq!(by_hash.len(), 1);
+    let by_tx_hash = db
+        .get_raw_operations_by_tx_hash(&TxId::from(sha256("tx-2")))
+        .await
+        .unwrap();
+    assert_eq!(by_tx_hash.len(), 1);
+    assert_eq!(by_tx_hash[0].1, metadata(2, 0));
+
+    let dids = db.get_all_dids(0, 10).await.unwrap();
+    assert_eq!(dids.items, vec![did.clone()]);
+    assert_eq!(dids.total_items, 1);
+    let dids = db
+        .get_dids_by_suffix_prefix(&did.suffix_hex().to_string()[..6], 10)
+        .await
+        .unwrap();
+    assert_eq!(dids, vec![did]);
+
+    let page = db.get_raw_operations(0, 2).await.unwrap();
+    assert_eq!(page.total_items, 3);
+    assert_eq!(page.items[0].1, metadata(2, 0));
+
+    let last_indexed = db.get_last_indexed_block().await.unwrap().unwrap();
+    assert_eq!(last_indexed, (20.into(), 2.into()));
+}
+
+#[tokio::test]
+async fn index_did_references() {
+    let db = new_db().await;
+    let master_sk = Secp256k1PrivateKey::from_slice(&MASTER_KEY).unwrap();
+    let shared_pk = Ed25519PrivateKey::from_slice(&[3; 32]).unwrap().to_public_key();
+    let create_did = |seed: &str| {
+        let operation = CreateDidOperationBuilder::new()
+            .add_key(
+                "master-0",
+                KeyUsage::MasterKey,
+                NonOperationPublicKey::Secp256k1(master_sk.to_public_key()),
+            )
+            .add_key(
+                "auth-0",
+                KeyUsage::AuthenticationKey,
+                NonOperationPublicKey::Ed25519(shared_pk.clone()),
+            )
+            .add_service("service-0", "LinkedDomains", "https://example.com")
+            .add_context(seed)
+            .build();
+        sign_operation(operation, "master-0", &master_sk)
+    };
+    let create_did_op_1 = create_did("https://example.com/1");
+    let create_did_op_2 = create_did("https://example.com/2");
+    let did_1 = CanonicalPrismDid::from_operation(create_did_op_1.operation.as_ref().unwrap()).unwrap();
+    let did_2 = CanonicalPrismDid::from_operation(create_did_op_2.operation.as_ref().unwrap()).unwrap();
+
+    db.insert_raw_operations(vec![
+        (metadata(1, 0), create_did_op_1.clone()),
+        (metadata(1, 1), create_did_op_2.clone()),
+    ])
+    .await
+    .unwrap();
+    run_indexer_loop(&db, |_| {}).await.unwrap();
+    assert!(db.get_dids_with_stale_references(10).await.unwrap().is_empty());
+
+    let mut expected = vec![
+        (did_1.clone(), "auth-0".to_string()),
+        (did_2.clone(), "auth-0".to_string()),
+    ];
+    expected.sort_by(|a, b| a.0.suffix.as_bytes().cmp(b.0.suffix.as_bytes()));
+    let dids = db.get_dids_by_public_key(&shared_pk.encode_vec(), 0, 10).await.unwrap();
+    assert_eq!(dids.items, expected);
+    assert_eq!(dids.total_items, 2);
+    let dids = db.get_dids_by_public_key(&shared_pk.encode_vec(), 1, 1).await.unwrap();
+    assert_eq!(dids.items, expected[1..]);
+    assert_eq!(dids.total_pages(), 2);
+    let dids = db
+        .get_dids_by_service_endpoint("https://example.com", 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items.len(), 2);
+
+    // a removed key and a deactivated DID are no longer found
+    let state_1 = resolve_published(vec![(metadata(1, 0), create_did_op_1)]).0.unwrap();
+    let state_2 = resolve_published(vec![(metadata(1, 1), create_did_op_2)]).0.unwrap();
+    let update_did_op = UpdateDidOperationBuilder::new(&state_1).remove_key("auth-0").build();
+    let deactivate_did_op = DeactivateDidOperationBuilder::new(&state_2).build();
+    db.insert_raw_operations(vec![
+        (metadata(2, 0), sign_operation(update_did_op, "master-0", &master_sk)),
+        (
+            metadata(2, 1),
+            sign_operation(deactivate_did_op, "master-0", &master_sk),
+        ),
+    ])
+    .await
+    .unwrap();
+    run_indexer_loop(&db, |_| {}).await.unwrap();
+    assert!(db.get_dids_with_stale_references(10).await.unwrap().is_empty());
+
+    let dids = db.get_dids_by_public_key(&shared_pk.encode_vec(), 0, 10).await.unwrap();
+    assert!(dids.items.is_empty());
+    let dids = db
+        .get_dids_by_public_key(&master_sk.to_public_key().encode_compressed(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items, vec![(did_1.clone(), "master-0".to_string())]);
+    let dids = db
+        .get_dids_by_service_endpoint("https://example.com", 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(dids.items, vec![(did_1, "service-0".to_string())]);
+}
+
+#[tokio::test]
+async fn set_did_references_replaces_previous_references() {
+    let db = new_db().await;
+    let did = CanonicalPrismDid::from_operation(create_did_operation().operation.as_ref().unwrap()).unwrap();
+    db.set_did_references(
+        &did,
+        vec![
+            DidReference::PublicKey {
+                key_id: "key-0".to_string(),
+                data: vec![1, 2, 3],
+            },
+            DidReference::ServiceEndpoint {
+                service_id: "service-0".to_string(),
+                url: "https://example.com".to_string(),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    let dids = db.get_dids_by_public_key(&[1, 2, 3], 0, 10).await.unwrap();
+    assert_eq!(dids.items, vec![(did.clone(), "key-0".to_string())]);
+
+    db.set_did_references(
+        &did,
+        vec![DidReference::PublicKey {
+            key_id: "key-1".to_string(),
+            data: vec![4, 5, 6],
+        }],
+    )
+    .await
+    .unwrap();
+    assert!(
+        db.get_dids_by_public_key(&[1, 2, 3], 0, 10)
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+    assert!(
+        db.get_dids_by_service_endpoint("https://example.com", 0, 10)
+            .await
+            .unwrap()
+            .items
+            .is_empty()
+    );
+    let dids = db.get_dids_by_public_key(&[4, 5, 6], 0, 10).await.unwrap();
+    assert_eq!(dids.items, vec![(did, "key-1".to_string())]);
+}
+
+#[tokio::test]
+async fn insert_duplicate_raw_operation_fails() {
+    let db = new_db().await;
+    db.insert_raw_operations(vec![(metadata(1, 0), create_did_operation())])
+        .await
+        .unwrap();
+    let result = db
+        .insert_raw_operations(vec![(metadata(1, 0), create_did_operation())])
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn set_and_get_cursor() {
+    let db = new_db().await;
+    for slot in [10, 20] {
+        db.set_cursor(DltCursor {
+            slot,
+            block_hash: vec![slot as u8; 32],
+            cbt: None,
+        })
+        .await
+        .unwrap();
+    }
+    let cursor = db.get_cursor().await.unwrap().unwrap();
+    assert_eq!(cursor.slot, 20);
+    assert_eq!(cursor.block_hash, vec![20; 32]);
+}
+
+#[tokio::test]
+async fn connect_read_only_requires_existing_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_url = format!("sqlite://{}", dir.path().join("neoprism.db").display());
+    assert!(SqliteDb::connect_read_only(&db_url).await.is_err());
+
+    let db = SqliteDb::connect(&db_url).await.unwrap();
+    db.migrate().await.unwrap();
+    db.insert_raw_operations(vec![(metadata(1, 0), create_did_operation())])
+        .await
+        .unwrap();
+
+    let read_db = SqliteDb::connect_read_only(&db_url).await.unwrap();
+    assert_eq!(read_db.get_raw_operations_unindexed().await.unwrap().len(), 1);
+    let result = read_db
+        .insert_raw_operations(vec![(metadata(2, 0), create_did_operation())])
+        .await;
+    assert!(result.is_err());
+}
@@ -26,7 +26,8 @@ rustPlatform.buildRustPackage {
   buildPhase = "cargo b --all-features --all-targets";
   checkPhase = ''
     deadnix -f
-    sqlfluff lint --dialect postgres ./lib/node-storage/migrations
+    sqlfluff lint --dialect postgres ./lib/node-storage/migrations/postgres
+    sqlfluff lint --dialect sqlite ./lib/node-storage/migrations/sqlite
     cargo fmt --check
     cargo clippy --all-features --all-targets -- -D warnings
     cargo test --all-features
@@ -36,9 +37,14 @@ rustPlatform.buildRustPackage {
     cargo clippy -p identus-apollo --all-targets --features base64 -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features ed25519 -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features hash -- -D warnings
+    cargo clippy -p identus-apollo --all-targets --features hdkey -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features hex -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features jwk -- -D warnings
+    cargo clippy -p identus-apollo --all-targets --features multibase -- -D warnings
+    cargo clippy -p identus-apollo --all-targets --features multikey -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features openapi -- -D warnings
+    cargo clippy -p identus-apollo --all-targets --features p256 -- -D warnings
+    cargo clippy -p identus-apollo --all-targets --features p384 -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features secp256k1 -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features serde -- -D warnings
     cargo clippy -p identus-apollo --all-targets --features x25519 -- -D warnings
@@ -4,6 +4,7 @@ import org.hyperledger.identus.prismtest.suite.CreateDidOperationSuite
 import org.hyperledger.identus.prismtest.suite.CreateStorageOperationSuite
 import org.hyperledger.identus.prismtest.suite.DeactivateDidOperationSuite
 import org.hyperledger.identus.prismtest.suite.DeactivateStorageOperationSuite
+import org.hyperledger.identus.prismtest.suite.SdkFixtureSuite
 import org.hyperledger.identus.prismtest.suite.UpdateDidOperationSuite
 import org.hyperledger.identus.prismtest.suite.UpdateStorageOperationSuite
 import org.hyperledger.identus.prismtest.utils.TestUtils
@@ -41,7 +42,7 @@ object MainSpec extends ZIOSpecDefault, TestUtils:
         NodeName.layer("scala-did")
       )
 
-    (neoprismSpec + prismNodeSpec + scalaDidSpec).provide(Runtime.removeDefaultLoggers)
+    (SdkFixtureSuite.allSpecs + neoprismSpec + prismNodeSpec + scalaDidSpec).provide(Runtime.removeDefaultLoggers)
       @@ TestAspect.timed
       @@ TestAspect.withLiveEnvironment
       @@ TestAspect.parallelN(1)
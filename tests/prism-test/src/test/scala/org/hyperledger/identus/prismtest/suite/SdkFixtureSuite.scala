@@ -0,0 +1,50 @@
+package org.hyperledger.identus.prismtest.suite
+
+import org.hyperledger.identus.prismtest.utils.TestUtils
+import proto.prism_ssi.KeyUsage
+import zio.*
+import zio.json.*
+import zio.test.*
+import zio.test.Assertion.*
+
+import scala.io.Source
+
+/** Check that the Identus SDK produces the same keys, operation and DID as the fixture used by the NeoPRISM tests. */
+object SdkFixtureSuite extends TestUtils:
+  private val fixturePath = "../../lib/did-prism/tests/fixtures/sdk_create_did.json"
+
+  final case class FixtureKey(id: String, usage: String, derivationPath: String, curve: String, data: String)
+      derives JsonDecoder
+  final case class FixtureService(id: String, `type`: String, serviceEndpoint: String) derives JsonDecoder
+  final case class Fixture(
+      seed: String,
+      publicKeys: Seq[FixtureKey],
+      services: Seq[FixtureService],
+      operation: String,
+      did: String
+  ) derives JsonDecoder
+
+  def allSpecs = suite("SdkFixture")(createDidSpec)
+
+  private def loadFixture: Task[Fixture] =
+    ZIO
+      .attemptBlocking(Source.fromFile(fixturePath).mkString)
+      .flatMap(json => ZIO.fromEither(json.fromJson[Fixture]).mapError(Exception(_)))
+
+  private def createDidSpec = test("create operation built with the SDK should match the fixture") {
+    for
+      fixture <- loadFixture
+      seed = fixture.seed.decodeHex
+      withKeys = fixture.publicKeys.foldLeft(builder(seed).createDid) { (b, key) =>
+        b.key(key.id)(KeyUsage.fromName(key.usage).get.secp256k1(key.derivationPath))
+      }
+      op = fixture.services
+        .foldLeft(withKeys)((b, service) => b.service(service.id)(service.`type`, service.serviceEndpoint))
+        .build
+      publicKeys = fixture.publicKeys.map { key =>
+        deriveSecp256k1(seed)(key.derivationPath).getKMMSecp256k1PrivateKey().getPublicKey().getCompressed().toHexString
+      }
+    yield assert(publicKeys)(equalTo(fixture.publicKeys.map(_.data))) &&
+      assert(op.toByteArray.toHexString)(equalTo(fixture.operation)) &&
+      assert(s"did:prism:${sha256(op.toByteArray).toHexString}")(equalTo(fixture.did))
+  }